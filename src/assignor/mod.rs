@@ -0,0 +1,101 @@
+// Client-side partition assignors of the classic group protocol.
+//
+// Group leader receives subscriptions of all members in JoinGroupResponse and
+// sends back assignment for every member in SyncGroupRequest. Assignors here
+// are deterministic: same input always produces the same assignment, regardless
+// of which member happens to be the leader.
+use std::collections::BTreeMap;
+
+use linked_hash_map::LinkedHashMap;
+
+use crate::messages::{
+    ConsumerProtocolAssignment, ConsumerProtocolSubscription, ConsumerProtocolTopicPartition,
+};
+use crate::TopicName;
+
+mod range;
+mod round_robin;
+mod sticky;
+#[cfg(test)]
+mod tests;
+
+pub use range::RangeAssignor;
+pub use round_robin::RoundRobinAssignor;
+pub use sticky::{CooperativeStickyAssignor, StickyAssignor, StickyAssignorUserData};
+
+pub type MemberId = String;
+pub type TopicPartition = (TopicName, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebalanceProtocol {
+    // Members revoke all partitions before rejoining the group
+    Eager,
+    // Members keep their partitions and revoke only those which moved to other
+    // members, followed by another rebalance
+    Cooperative,
+}
+
+pub trait PartitionAssignor {
+    // Protocol name used in JoinGroupRequestProtocol
+    fn name(&self) -> &'static str;
+
+    fn rebalance_protocol(&self) -> RebalanceProtocol {
+        RebalanceProtocol::Eager
+    }
+
+    // Every member of `subscriptions` gets an entry in returned map, possibly with
+    // no partitions. Subscribed topics missing from `partitions_per_topic` are
+    // ignored.
+    fn assign(
+        &self,
+        partitions_per_topic: &BTreeMap<TopicName, i32>,
+        subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    ) -> BTreeMap<MemberId, ConsumerProtocolAssignment>;
+}
+
+fn empty_assignment(
+    subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+) -> BTreeMap<MemberId, Vec<TopicPartition>> {
+    subscriptions
+        .keys()
+        .map(|m| (m.clone(), Vec::new()))
+        .collect()
+}
+
+fn topic_partitions<'a>(
+    topics: impl Iterator<Item = &'a ConsumerProtocolTopicPartition>,
+) -> Vec<TopicPartition> {
+    topics
+        .flat_map(|t| t.partitions.iter().map(move |&p| (t.topic.clone(), p)))
+        .collect()
+}
+
+fn to_assignment(mut partitions: Vec<TopicPartition>) -> ConsumerProtocolAssignment {
+    partitions.sort();
+
+    let mut assigned_partitions = LinkedHashMap::new();
+    for (topic, p) in partitions {
+        assigned_partitions
+            .entry(topic.clone())
+            .or_insert_with(|| ConsumerProtocolTopicPartition {
+                topic,
+                partitions: Vec::new(),
+            })
+            .partitions
+            .push(p);
+    }
+
+    ConsumerProtocolAssignment {
+        assigned_partitions,
+        user_data: None,
+    }
+}
+
+fn to_assignments(
+    assignment: BTreeMap<MemberId, Vec<TopicPartition>>,
+) -> BTreeMap<MemberId, ConsumerProtocolAssignment> {
+    assignment
+        .into_iter()
+        .map(|(m, partitions)| (m, to_assignment(partitions)))
+        .collect()
+}
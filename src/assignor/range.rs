@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+
+use super::{empty_assignment, to_assignments, MemberId, PartitionAssignor};
+use crate::messages::{ConsumerProtocolAssignment, ConsumerProtocolSubscription};
+use crate::TopicName;
+
+// Assigns consecutive ranges of partitions of every topic to members subscribed
+// to it. First `partitions % members` members get one extra partition.
+pub struct RangeAssignor;
+
+impl PartitionAssignor for RangeAssignor {
+    fn name(&self) -> &'static str {
+        "range"
+    }
+
+    fn assign(
+        &self,
+        partitions_per_topic: &BTreeMap<TopicName, i32>,
+        subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    ) -> BTreeMap<MemberId, ConsumerProtocolAssignment> {
+        let mut assignment = empty_assignment(subscriptions);
+
+        for (topic, &num_partitions) in partitions_per_topic {
+            let members: Vec<&MemberId> = subscriptions
+                .iter()
+                .filter(|(_, s)| s.topics.contains(topic))
+                .map(|(m, _)| m)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+
+            let per_member = num_partitions / members.len() as i32;
+            let extra = num_partitions % members.len() as i32;
+            for (i, m) in members.into_iter().enumerate() {
+                let i = i as i32;
+                let start = per_member * i + i.min(extra);
+                let len = per_member + (i < extra) as i32;
+                if let Some(partitions) = assignment.get_mut(m) {
+                    partitions.extend((start..start + len).map(|p| (topic.clone(), p)));
+                }
+            }
+        }
+
+        to_assignments(assignment)
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{empty_assignment, to_assignments, MemberId, PartitionAssignor};
+use crate::messages::{ConsumerProtocolAssignment, ConsumerProtocolSubscription};
+use crate::TopicName;
+
+// Lays out all partitions sorted by topic and partition and deals them to members
+// in turn, skipping members not subscribed to partition's topic.
+pub struct RoundRobinAssignor;
+
+impl PartitionAssignor for RoundRobinAssignor {
+    fn name(&self) -> &'static str {
+        "roundrobin"
+    }
+
+    fn assign(
+        &self,
+        partitions_per_topic: &BTreeMap<TopicName, i32>,
+        subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    ) -> BTreeMap<MemberId, ConsumerProtocolAssignment> {
+        let mut assignment = empty_assignment(subscriptions);
+
+        let members: Vec<(&MemberId, &ConsumerProtocolSubscription)> =
+            subscriptions.iter().collect();
+        let topics: BTreeSet<&TopicName> = subscriptions
+            .values()
+            .flat_map(|s| s.topics.iter())
+            .filter(|t| partitions_per_topic.contains_key(*t))
+            .collect();
+
+        let mut next = 0;
+        for topic in topics {
+            for p in 0..partitions_per_topic[topic] {
+                // Topic is subscribed by at least one member, so this terminates
+                while !members[next % members.len()].1.topics.contains(topic) {
+                    next += 1;
+                }
+                let m = members[next % members.len()].0;
+                if let Some(partitions) = assignment.get_mut(m) {
+                    partitions.push((topic.clone(), p));
+                }
+                next += 1;
+            }
+        }
+
+        to_assignments(assignment)
+    }
+}
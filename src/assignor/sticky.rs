@@ -0,0 +1,270 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::{
+    empty_assignment, to_assignments, topic_partitions, MemberId, PartitionAssignor,
+    RebalanceProtocol, TopicPartition,
+};
+use crate::de::{KafkaFlexiDeserializer, KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::messages::{
+    ConsumerProtocolAssignment, ConsumerProtocolSubscription, ConsumerProtocolTopicPartition,
+};
+use crate::ser::{KafkaFlexiSerializer, KafkaFlexibleEncoder, KafkaProtoEncodable};
+use crate::{Bytes, TopicName};
+
+// Balances partitions across members while keeping as many of previously owned
+// partitions in place as possible. Previous ownership comes from
+// ConsumerProtocolSubscription.owned_partitions, or from StickyAssignorUserData
+// for members which do not report owned partitions.
+pub struct StickyAssignor;
+
+impl PartitionAssignor for StickyAssignor {
+    fn name(&self) -> &'static str {
+        "sticky"
+    }
+
+    fn assign(
+        &self,
+        partitions_per_topic: &BTreeMap<TopicName, i32>,
+        subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    ) -> BTreeMap<MemberId, ConsumerProtocolAssignment> {
+        let prior = previous_owners(subscriptions.iter().map(|(m, s)| {
+            if !s.owned_partitions.is_empty() {
+                return (
+                    m,
+                    s.generation_id,
+                    topic_partitions(s.owned_partitions.values()),
+                );
+            }
+            match s
+                .user_data
+                .as_deref()
+                .map(StickyAssignorUserData::from_bytes)
+            {
+                Some(Ok(u)) => (
+                    m,
+                    u.generation,
+                    topic_partitions(u.previous_assignment.iter()),
+                ),
+                _ => (m, s.generation_id, Vec::new()),
+            }
+        }));
+
+        to_assignments(sticky_assign(partitions_per_topic, subscriptions, &prior))
+    }
+}
+
+// Same assignment as StickyAssignor, except that partitions moving between
+// members are withheld for one rebalance: previous owner revokes them and
+// rejoins, then they get assigned to the new owner.
+pub struct CooperativeStickyAssignor;
+
+impl PartitionAssignor for CooperativeStickyAssignor {
+    fn name(&self) -> &'static str {
+        "cooperative-sticky"
+    }
+
+    fn rebalance_protocol(&self) -> RebalanceProtocol {
+        RebalanceProtocol::Cooperative
+    }
+
+    fn assign(
+        &self,
+        partitions_per_topic: &BTreeMap<TopicName, i32>,
+        subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    ) -> BTreeMap<MemberId, ConsumerProtocolAssignment> {
+        let prior = previous_owners(subscriptions.iter().map(|(m, s)| {
+            (
+                m,
+                s.generation_id,
+                topic_partitions(s.owned_partitions.values()),
+            )
+        }));
+
+        let mut assignment = sticky_assign(partitions_per_topic, subscriptions, &prior);
+        for (m, partitions) in assignment.iter_mut() {
+            partitions.retain(|tp| prior.get(tp).is_none_or(|owner| *owner == m));
+        }
+
+        to_assignments(assignment)
+    }
+}
+
+// Resolves previous owner of every partition. Only claims of members from the
+// highest generation are trusted, as others may have missed a rebalance. If
+// several of them claim the same partition, first member in order wins.
+fn previous_owners<'a>(
+    claims: impl Iterator<Item = (&'a MemberId, i32, Vec<TopicPartition>)>,
+) -> HashMap<TopicPartition, &'a MemberId> {
+    let claims: Vec<_> = claims.collect();
+    let max_generation = claims.iter().map(|(_, g, _)| *g).max().unwrap_or(-1);
+
+    let mut owners = HashMap::new();
+    for (m, generation, partitions) in claims {
+        if generation != max_generation {
+            continue;
+        }
+        for tp in partitions {
+            owners.entry(tp).or_insert(m);
+        }
+    }
+    owners
+}
+
+fn sticky_assign(
+    partitions_per_topic: &BTreeMap<TopicName, i32>,
+    subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    prior: &HashMap<TopicPartition, &MemberId>,
+) -> BTreeMap<MemberId, Vec<TopicPartition>> {
+    let is_subscribed = |m: &MemberId, topic: &TopicName| subscriptions[m].topics.contains(topic);
+    let members: Vec<&MemberId> = subscriptions.keys().collect();
+    let mut assignment = empty_assignment(subscriptions);
+
+    let topics: BTreeSet<&TopicName> = subscriptions
+        .values()
+        .flat_map(|s| s.topics.iter())
+        .collect();
+    let mut unassigned: BTreeSet<TopicPartition> = topics
+        .into_iter()
+        .filter_map(|t| partitions_per_topic.get_key_value(t))
+        .flat_map(|(t, &n)| (0..n).map(move |p| (t.clone(), p)))
+        .collect();
+
+    // Keep previously owned partitions which still exist and are still subscribed
+    let mut owned: Vec<(&TopicPartition, &&MemberId)> = prior.iter().collect();
+    owned.sort();
+    for (tp, m) in owned {
+        if assignment.contains_key(*m) && is_subscribed(m, &tp.0) && unassigned.remove(tp) {
+            assignment.entry((*m).clone()).or_default().push(tp.clone());
+        }
+    }
+
+    // Members ordered by load, ties broken by member id. Only members whose
+    // load changes are reordered.
+    let mut by_load: BTreeSet<(usize, &MemberId)> =
+        members.iter().map(|m| (assignment[*m].len(), *m)).collect();
+
+    // Partitions with fewest potential consumers go first, so they are not left
+    // with overloaded members only
+    let consumers: HashMap<&TopicName, usize> = partitions_per_topic
+        .keys()
+        .map(|t| (t, members.iter().filter(|m| is_subscribed(m, t)).count()))
+        .collect();
+    let mut remaining: Vec<TopicPartition> = unassigned.into_iter().collect();
+    remaining.sort_by_cached_key(|tp| (consumers[&tp.0], tp.clone()));
+    for tp in remaining {
+        let target = by_load
+            .iter()
+            .find(|&&(_, m)| is_subscribed(m, &tp.0))
+            .copied();
+        if let Some((load, m)) = target {
+            by_load.remove(&(load, m));
+            assignment.entry(m.clone()).or_default().push(tp);
+            by_load.insert((load + 1, m));
+        }
+    }
+
+    // Move partitions from more loaded members to less loaded ones until no
+    // member can take a partition from another member with at least 2 more.
+    // Every move strictly decreases sum of squared loads, so this terminates.
+    // Newly assigned partitions are at the end and are moved first.
+    let mut by_load_desc: BTreeSet<(Reverse<usize>, &MemberId)> = by_load
+        .iter()
+        .map(|&(load, m)| (Reverse(load), m))
+        .collect();
+    loop {
+        let min_load = by_load.iter().next().map_or(0, |&(load, _)| load);
+        let mv = by_load_desc
+            .iter()
+            .take_while(|&&(Reverse(load), _)| load >= min_load + 2)
+            .find_map(|&(Reverse(load), hi)| {
+                assignment[hi].iter().enumerate().rev().find_map(|(i, tp)| {
+                    by_load
+                        .iter()
+                        .take_while(|&&(lo_load, _)| lo_load + 1 < load)
+                        .find(|&&(_, lo)| lo != hi && is_subscribed(lo, &tp.0))
+                        .map(|&(_, lo)| (hi, i, lo))
+                })
+            });
+
+        let (hi, i, lo) = match mv {
+            Some(mv) => mv,
+            None => break,
+        };
+        for m in [hi, lo] {
+            let load = assignment[m].len();
+            by_load.remove(&(load, m));
+            by_load_desc.remove(&(Reverse(load), m));
+        }
+        let tp = assignment.entry(hi.clone()).or_default().remove(i);
+        assignment.entry(lo.clone()).or_default().push(tp);
+        for m in [hi, lo] {
+            let load = assignment[m].len();
+            by_load.insert((load, m));
+            by_load_desc.insert((Reverse(load), m));
+        }
+    }
+
+    assignment
+}
+
+// Eager members revoke all partitions before rejoining, so StickyAssignor
+// carries previous assignment in ConsumerProtocolSubscription.user_data.
+// Version 0 has no generation.
+pub struct StickyAssignorUserData {
+    pub previous_assignment: Vec<ConsumerProtocolTopicPartition>,
+
+    // versions: 1+, default=-1
+    pub generation: i32,
+}
+
+impl Default for StickyAssignorUserData {
+    fn default() -> Self {
+        StickyAssignorUserData {
+            previous_assignment: Vec::new(),
+            generation: -1,
+        }
+    }
+}
+
+impl StickyAssignorUserData {
+    pub fn to_bytes(&self) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        self.serialize(1, &mut KafkaFlexiSerializer::new(false, &mut buf))?;
+        Ok(buf)
+    }
+
+    // User data isn't prefixed with its version. V1 is accepted only if it
+    // consumes the whole buffer, V0 is tried otherwise.
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let mut d = KafkaFlexiDeserializer::new(false, buf);
+        match Self::deserialize(1, &mut d) {
+            Ok(r) if d.de.position() == buf.len() => Ok(r),
+            _ => Self::deserialize(0, &mut KafkaFlexiDeserializer::new(false, buf)),
+        }
+    }
+}
+
+impl KafkaProtoEncodable for StickyAssignorUserData {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        let r = s.emit_array(ver, self.previous_assignment.iter())?;
+        if ver >= 1 {
+            return s.emit_int32(self.generation);
+        }
+        s.check_absent_field("generation", ver, self.generation == -1)?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for StickyAssignorUserData {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        Ok(StickyAssignorUserData {
+            previous_assignment: d.read_field("previous_assignment", |d| d.read_array(ver))?,
+            generation: if ver >= 1 {
+                d.read_field("generation", |d| d.read_int32())?
+            } else {
+                -1
+            },
+        })
+    }
+}
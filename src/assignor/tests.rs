@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use linked_hash_map::LinkedHashMap;
+use proptest::prelude::*;
+
+use super::*;
+
+type Partitions = BTreeMap<MemberId, Vec<TopicPartition>>;
+
+const MAX_MEMBERS: usize = 6;
+const MAX_PARTITIONS: usize = 4 * 12;
+
+// Topic "t{i}" has `partitions[i]` partitions. Member "m{j}" subscribes to
+// topic i if `subscribed(j, i)` and owns partitions mapped to j by `owners`,
+// which covers partitions of all topics in order.
+fn input(
+    partitions: &[i32],
+    members: usize,
+    subscribed: impl Fn(usize, usize) -> bool,
+    owners: &[usize],
+) -> (
+    BTreeMap<TopicName, i32>,
+    BTreeMap<MemberId, ConsumerProtocolSubscription>,
+) {
+    let topics: BTreeMap<TopicName, i32> = partitions
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| (format!("t{}", i), n))
+        .collect();
+    let all: Vec<TopicPartition> = topics
+        .iter()
+        .flat_map(|(t, &n)| (0..n).map(move |p| (t.clone(), p)))
+        .collect();
+
+    let subscriptions = (0..members)
+        .map(|j| {
+            let owned: Vec<TopicPartition> = all
+                .iter()
+                .zip(owners)
+                .filter(|(_, &o)| o == j)
+                .map(|(tp, _)| tp.clone())
+                .collect();
+            let subscription = ConsumerProtocolSubscription {
+                topics: (0..partitions.len())
+                    .filter(|&i| subscribed(j, i))
+                    .map(|i| format!("t{}", i))
+                    .collect(),
+                owned_partitions: owned_partitions(&owned),
+                generation_id: 1,
+                ..Default::default()
+            };
+            (format!("m{}", j), subscription)
+        })
+        .collect();
+    (topics, subscriptions)
+}
+
+fn owned_partitions(
+    partitions: &[TopicPartition],
+) -> LinkedHashMap<TopicName, ConsumerProtocolTopicPartition> {
+    let mut owned = LinkedHashMap::new();
+    for (topic, p) in partitions {
+        owned
+            .entry(topic.clone())
+            .or_insert_with(|| ConsumerProtocolTopicPartition {
+                topic: topic.clone(),
+                partitions: Vec::new(),
+            })
+            .partitions
+            .push(*p);
+    }
+    owned
+}
+
+fn partitions(assignment: &BTreeMap<MemberId, ConsumerProtocolAssignment>) -> Partitions {
+    assignment
+        .iter()
+        .map(|(m, a)| (m.clone(), topic_partitions(a.assigned_partitions.values())))
+        .collect()
+}
+
+// Members report what they got from `assignment` as owned partitions of the
+// next generation
+fn rejoin(
+    subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    assignment: &Partitions,
+) -> BTreeMap<MemberId, ConsumerProtocolSubscription> {
+    subscriptions
+        .iter()
+        .map(|(m, s)| {
+            let subscription = ConsumerProtocolSubscription {
+                topics: s.topics.clone(),
+                owned_partitions: owned_partitions(&assignment[m]),
+                generation_id: s.generation_id + 1,
+                ..Default::default()
+            };
+            (m.clone(), subscription)
+        })
+        .collect()
+}
+
+fn loads(assignment: &Partitions) -> (usize, usize) {
+    let loads = assignment.values().map(Vec::len);
+    (loads.clone().min().unwrap_or(0), loads.max().unwrap_or(0))
+}
+
+// Every partition of a subscribed topic goes to exactly one of its subscribers
+fn check_complete(
+    topics: &BTreeMap<TopicName, i32>,
+    subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+    assignment: &Partitions,
+) {
+    assert_eq!(
+        assignment.keys().collect::<Vec<_>>(),
+        subscriptions.keys().collect::<Vec<_>>()
+    );
+    let mut seen = BTreeSet::new();
+    for (m, partitions) in assignment {
+        for tp in partitions {
+            assert!(subscriptions[m].topics.contains(&tp.0), "{:?} to {}", tp, m);
+            assert!(seen.insert(tp.clone()), "{:?} assigned twice", tp);
+        }
+    }
+    let expected: BTreeSet<TopicPartition> = topics
+        .iter()
+        .filter(|(t, _)| subscriptions.values().any(|s| s.topics.contains(t)))
+        .flat_map(|(t, &n)| (0..n).map(move |p| (t.clone(), p)))
+        .collect();
+    assert_eq!(seen, expected);
+}
+
+fn all_assignors() -> Vec<Box<dyn PartitionAssignor>> {
+    vec![
+        Box::new(RangeAssignor),
+        Box::new(RoundRobinAssignor),
+        Box::new(StickyAssignor),
+        Box::new(CooperativeStickyAssignor),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn assigns_every_partition_once(
+        partitions in prop::collection::vec(1..12i32, 1..4),
+        members in 1..=MAX_MEMBERS,
+        subscribed in prop::collection::vec(any::<u8>(), MAX_MEMBERS),
+    ) {
+        let (topics, subscriptions) =
+            input(&partitions, members, |j, i| subscribed[j] & (1 << i) != 0, &[]);
+        for a in all_assignors() {
+            if a.rebalance_protocol() == RebalanceProtocol::Eager {
+                check_complete(&topics, &subscriptions, &partitions_of(&*a, &topics, &subscriptions));
+            }
+        }
+    }
+
+    #[test]
+    fn balanced_with_same_subscriptions(
+        partitions in prop::collection::vec(1..12i32, 1..4),
+        members in 1..=MAX_MEMBERS,
+        owners in prop::collection::vec(0..MAX_MEMBERS + 2, MAX_PARTITIONS),
+    ) {
+        let (topics, subscriptions) = input(&partitions, members, |_, _| true, &owners);
+
+        // Range balances every topic on its own
+        let range = partitions_of(&RangeAssignor, &topics, &subscriptions);
+        for topic in topics.keys() {
+            let per_topic: Partitions = range
+                .iter()
+                .map(|(m, ps)| (m.clone(), ps.iter().filter(|tp| tp.0 == *topic).cloned().collect()))
+                .collect();
+            let (min, max) = loads(&per_topic);
+            prop_assert!(max - min <= 1, "{}: {:?}", topic, per_topic);
+        }
+
+        for a in [&RoundRobinAssignor as &dyn PartitionAssignor, &StickyAssignor] {
+            let assignment = partitions_of(a, &topics, &subscriptions);
+            let (min, max) = loads(&assignment);
+            prop_assert!(max - min <= 1, "{}: {:?}", a.name(), assignment);
+        }
+    }
+
+    #[test]
+    fn sticky_keeps_owned_partitions(
+        partitions in prop::collection::vec(1..12i32, 1..4),
+        members in 2..=MAX_MEMBERS,
+        subscribed in prop::collection::vec(any::<u8>(), MAX_MEMBERS),
+    ) {
+        let (topics, subscriptions) =
+            input(&partitions, members, |j, i| subscribed[j] & (1 << i) != 0, &[]);
+        for a in [&StickyAssignor as &dyn PartitionAssignor, &CooperativeStickyAssignor] {
+            let first = partitions_of(a, &topics, &subscriptions);
+
+            // Nothing changed, nothing moves
+            let rejoined = rejoin(&subscriptions, &first);
+            prop_assert_eq!(&partitions_of(a, &topics, &rejoined), &first);
+        }
+    }
+
+    #[test]
+    fn sticky_keeps_owned_partitions_when_member_leaves(
+        partitions in prop::collection::vec(1..12i32, 1..4),
+        members in 2..=MAX_MEMBERS,
+        leaving in 0..MAX_MEMBERS,
+    ) {
+        let (topics, subscriptions) = input(&partitions, members, |_, _| true, &[]);
+        for a in [&StickyAssignor as &dyn PartitionAssignor, &CooperativeStickyAssignor] {
+            let first = partitions_of(a, &topics, &subscriptions);
+            let mut rest = rejoin(&subscriptions, &first);
+            rest.remove(&format!("m{}", leaving % members));
+
+            // Partitions of the leaving member are spread over the rest, nobody
+            // else loses anything
+            let second = partitions_of(a, &topics, &rest);
+            check_complete(&topics, &rest, &second);
+            for (m, partitions) in &second {
+                for tp in &first[m] {
+                    prop_assert!(partitions.contains(tp), "{}: {:?} moved from {}", a.name(), tp, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn assignors_are_deterministic(
+        partitions in prop::collection::vec(1..12i32, 1..4),
+        members in 1..=MAX_MEMBERS,
+        subscribed in prop::collection::vec(any::<u8>(), MAX_MEMBERS),
+        owners in prop::collection::vec(0..MAX_MEMBERS + 2, MAX_PARTITIONS),
+    ) {
+        let (topics, subscriptions) =
+            input(&partitions, members, |j, i| subscribed[j] & (1 << i) != 0, &owners);
+
+        // Order in which members list owned partitions does not matter either
+        let reversed = subscriptions
+            .iter()
+            .map(|(m, s)| {
+                let mut owned = topic_partitions(s.owned_partitions.values());
+                owned.reverse();
+                let s = ConsumerProtocolSubscription {
+                    topics: s.topics.clone(),
+                    owned_partitions: owned_partitions(&owned),
+                    generation_id: s.generation_id,
+                    ..Default::default()
+                };
+                (m.clone(), s)
+            })
+            .collect();
+
+        for a in all_assignors() {
+            let assignment = partitions_of(&*a, &topics, &subscriptions);
+            prop_assert_eq!(&partitions_of(&*a, &topics, &subscriptions), &assignment);
+            prop_assert_eq!(&partitions_of(&*a, &topics, &reversed), &assignment);
+        }
+    }
+
+    #[test]
+    fn cooperative_sticky_revokes_before_moving(
+        partitions in prop::collection::vec(1..12i32, 1..4),
+        members in 1..=MAX_MEMBERS,
+        owners in prop::collection::vec(0..MAX_MEMBERS + 2, MAX_PARTITIONS),
+    ) {
+        let (topics, subscriptions) = input(&partitions, members, |_, _| true, &owners);
+        let owner: BTreeMap<TopicPartition, &MemberId> = subscriptions
+            .iter()
+            .flat_map(|(m, s)| topic_partitions(s.owned_partitions.values()).into_iter().map(move |tp| (tp, m)))
+            .collect();
+
+        // Partition owned by another member is not handed out until that member
+        // revokes it
+        let first = partitions_of(&CooperativeStickyAssignor, &topics, &subscriptions);
+        for (m, partitions) in &first {
+            for tp in partitions {
+                prop_assert!(owner.get(tp).is_none_or(|o| *o == m), "{:?} moved from {:?} to {}", tp, owner.get(tp), m);
+            }
+        }
+
+        // Once revoked partitions are released, follow-up rebalance assigns
+        // everything
+        let second = partitions_of(&CooperativeStickyAssignor, &topics, &rejoin(&subscriptions, &first));
+        check_complete(&topics, &subscriptions, &second);
+        let (min, max) = loads(&second);
+        prop_assert!(max - min <= 1, "{:?}", second);
+        for (m, partitions) in &first {
+            for tp in partitions {
+                prop_assert!(second[m].contains(tp), "{:?} moved from {}", tp, m);
+            }
+        }
+    }
+}
+
+fn partitions_of(
+    a: &dyn PartitionAssignor,
+    topics: &BTreeMap<TopicName, i32>,
+    subscriptions: &BTreeMap<MemberId, ConsumerProtocolSubscription>,
+) -> Partitions {
+    partitions(&a.assign(topics, subscriptions))
+}
+
+#[test]
+fn sticky_user_data_versions() {
+    let data = StickyAssignorUserData {
+        previous_assignment: vec![ConsumerProtocolTopicPartition {
+            topic: "t0".to_string(),
+            partitions: vec![0, 2],
+        }],
+        generation: 3,
+    };
+    let v1 = data.to_bytes().unwrap();
+    let decoded = StickyAssignorUserData::from_bytes(&v1).unwrap();
+    assert_eq!(decoded.generation, 3);
+    assert_eq!(decoded.previous_assignment[0].partitions, vec![0, 2]);
+
+    // V0 has no generation
+    let v0 = &v1[..v1.len() - 4];
+    let decoded = StickyAssignorUserData::from_bytes(v0).unwrap();
+    assert_eq!(decoded.generation, -1);
+    assert_eq!(decoded.previous_assignment[0].partitions, vec![0, 2]);
+}
@@ -0,0 +1,49 @@
+use bytes::Bytes;
+
+use super::{read_message, KafkaDeserializer, KafkaFlexiDeserializer};
+use super::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::messages::ResponseHeader;
+use crate::KafkaMessage;
+
+// Turns a slice borrowed from buf while decoding, e.g. Cow::Borrowed metadata of
+// JoinGroupRequestProtocol<'a>, into Bytes sharing buf, so that it outlives the
+// borrow without a copy. Slices which don't point into buf are copied.
+pub fn share_bytes(buf: &Bytes, v: &[u8]) -> Bytes {
+    let start = buf.as_ptr() as usize;
+    let p = v.as_ptr() as usize;
+    if v.is_empty() {
+        Bytes::new()
+    } else if p >= start && p + v.len() <= start + buf.len() {
+        buf.slice_ref(v)
+    } else {
+        Bytes::copy_from_slice(v)
+    }
+}
+
+impl<'a> KafkaFlexiDeserializer<'a> {
+    // Deserializer over a refcounted buffer. Bytes fields which support it,
+    // e.g. records of FetchResponsePartition, are slices sharing buf.
+    pub fn new_shared(use_flexible: bool, buf: &'a Bytes) -> KafkaFlexiDeserializer<'a> {
+        KafkaFlexiDeserializer {
+            use_flexible,
+            de: KafkaDeserializer::new(buf),
+            shared: Some(buf),
+        }
+    }
+}
+
+// Response as received by a client, records of a FetchResponse refer to buf
+// instead of copying it
+pub fn deserialize_response_shared<M>(
+    ver: i16,
+    buf: &Bytes,
+) -> crate::error::Result<(ResponseHeader, M)>
+where
+    M: KafkaProtoDecodable + KafkaMessage,
+{
+    let mut d = KafkaFlexiDeserializer::new_shared(ver >= M::FLEXIBLE_VERSION, buf);
+    let hdr = ResponseHeader::deserialize(&mut d.de)?;
+    // Response header v1 of flexible versions ends with tagged fields
+    d.skip_tagged_fields()?;
+    Ok((hdr, read_message(ver, &mut d)?))
+}
@@ -7,6 +7,78 @@ pub trait KafkaProtoDecodable {
     where
         Self: Sized;
 }
+
+// Primitive types, which appear as array elements
+impl KafkaProtoDecodable for i8 {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(_v: i16, d: &mut D) -> Result<Self, D::Error> {
+        d.read_int8()
+    }
+}
+
+impl KafkaProtoDecodable for i16 {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(_v: i16, d: &mut D) -> Result<Self, D::Error> {
+        d.read_int16()
+    }
+}
+
+impl KafkaProtoDecodable for i32 {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(_v: i16, d: &mut D) -> Result<Self, D::Error> {
+        d.read_int32()
+    }
+}
+
+impl KafkaProtoDecodable for i64 {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(_v: i16, d: &mut D) -> Result<Self, D::Error> {
+        d.read_int64()
+    }
+}
+
+impl KafkaProtoDecodable for String {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(_v: i16, d: &mut D) -> Result<Self, D::Error> {
+        d.read_string().map(Into::into)
+    }
+}
+
+impl KafkaProtoDecodable for uuid::Uuid {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(_v: i16, d: &mut D) -> Result<Self, D::Error> {
+        d.read_uuid()
+    }
+}
+
+impl KafkaProtoDecodable for crate::TopicId {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(_v: i16, d: &mut D) -> Result<Self, D::Error> {
+        d.read_uuid().map(crate::TopicId)
+    }
+}
+
+// Types which borrow strings and bytes from the buffer being decoded instead of
+// copying them. So far only JoinGroupRequest<'a> does, other messages are owned
+// and go through the impl below, as every owned decodable type is trivially a
+// borrowed one.
+pub trait KafkaProtoBorrowDecodable<'de> {
+    fn deserialize_borrowed<D: KafkaFlexibleDecoder<'de>>(
+        version: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error>
+    where
+        Self: Sized;
+}
+
+impl<'de, T: KafkaProtoDecodable> KafkaProtoBorrowDecodable<'de> for T {
+    fn deserialize_borrowed<D: KafkaFlexibleDecoder<'de>>(
+        version: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        T::deserialize(version, d)
+    }
+}
+
+use std::hash::Hash;
+
+use linked_hash_map::LinkedHashMap;
+
+use crate::error::PathSegment;
+
 // Ref: https://kafka.apache.org/protocol#protocol_types
 pub trait KafkaProtoDecoder<'de> {
     type Error;
@@ -16,9 +88,11 @@ pub trait KafkaProtoDecoder<'de> {
     fn read_int16(&mut self) -> Result<i16, Self::Error>;
     fn read_int32(&mut self) -> Result<i32, Self::Error>;
     fn read_int64(&mut self) -> Result<i64, Self::Error>;
+    fn read_uint16(&mut self) -> Result<u16, Self::Error>;
     fn read_uint32(&mut self) -> Result<u32, Self::Error>;
     fn read_varint(&mut self) -> Result<i32, Self::Error>;
     fn read_varlong(&mut self) -> Result<i64, Self::Error>;
+    fn read_unsigned_varint(&mut self) -> Result<u32, Self::Error>;
     fn read_uuid(&mut self) -> Result<uuid::Uuid, Self::Error>;
 
     fn read_float64(&mut self) -> Result<f64, Self::Error>;
@@ -32,7 +106,9 @@ pub trait KafkaProtoDecoder<'de> {
     fn read_compact_nullable_bytes(&mut self) -> Result<Option<&'de [u8]>, Self::Error>;
     //fn read_records(&mut self) -> Result< &[Records], Self::Error>;
     fn read_array_hdr(&mut self) -> Result<usize, Self::Error>;
+    fn read_nullable_array_hdr(&mut self) -> Result<Option<usize>, Self::Error>;
     fn read_compact_array_hdr(&mut self) -> Result<usize, Self::Error>;
+    fn read_compact_nullable_array_hdr(&mut self) -> Result<Option<usize>, Self::Error>;
 }
 
 pub trait KafkaFlexibleDecoder<'de> {
@@ -43,6 +119,7 @@ pub trait KafkaFlexibleDecoder<'de> {
     fn read_int16(&mut self) -> Result<i16, Self::Error>;
     fn read_int32(&mut self) -> Result<i32, Self::Error>;
     fn read_int64(&mut self) -> Result<i64, Self::Error>;
+    fn read_uint16(&mut self) -> Result<u16, Self::Error>;
     fn read_uint32(&mut self) -> Result<u32, Self::Error>;
     fn read_varint(&mut self) -> Result<i32, Self::Error>;
     fn read_varlong(&mut self) -> Result<i64, Self::Error>;
@@ -55,5 +132,119 @@ pub trait KafkaFlexibleDecoder<'de> {
     fn read_nullable_bytes(&mut self) -> Result<Option<&'de [u8]>, Self::Error>;
     //fn emit_records(&mut self) -> Result< &[Records], Self::Error>;
     fn read_array_hdr(&mut self) -> Result<usize, Self::Error>;
-    //        T: KafkaProtoDecodable;
+    fn read_nullable_array_hdr(&mut self) -> Result<Option<usize>, Self::Error>;
+
+    // Adds the segment to the field path of a decode error, see Error::Decode.
+    // Decoders which don't track paths return errors as is.
+    fn annotate_error(&self, e: Self::Error, _segment: PathSegment) -> Self::Error {
+        e
+    }
+
+    // Same as read_nullable_bytes, but the value outlives the buffer. Decoders
+    // over a refcounted buffer return a slice sharing it, others a copy.
+    #[cfg(feature = "bytes")]
+    fn read_shared_nullable_bytes(&mut self) -> Result<Option<bytes::Bytes>, Self::Error> {
+        Ok(self
+            .read_nullable_bytes()?
+            .map(bytes::Bytes::copy_from_slice))
+    }
+
+    // Upper bound of bytes left to read. Array lengths come from the wire, so
+    // reservations are capped by it: every element takes at least a byte.
+    // Decoders which don't know it don't cap.
+    fn remaining(&self) -> usize {
+        usize::MAX
+    }
+
+    // Value of a struct field read by `f`, errors are annotated with its name
+    fn read_field<T, F>(&mut self, name: &str, f: F) -> Result<T, Self::Error>
+    where
+        Self: Sized,
+        F: FnOnce(&mut Self) -> Result<T, Self::Error>,
+    {
+        f(self).map_err(|e| self.annotate_error(e, PathSegment::Field(name)))
+    }
+
+    fn read_array<T: KafkaProtoBorrowDecodable<'de>>(
+        &mut self,
+        version: i16,
+    ) -> Result<Vec<T>, Self::Error>
+    where
+        Self: Sized,
+    {
+        let len = self.read_array_hdr()?;
+        let mut v = Vec::with_capacity(len.min(self.remaining()));
+        for i in 0..len {
+            match T::deserialize_borrowed(version, self) {
+                Ok(e) => v.push(e),
+                Err(e) => return Err(self.annotate_error(e, PathSegment::Index(i))),
+            }
+        }
+        Ok(v)
+    }
+
+    fn read_nullable_array<T: KafkaProtoBorrowDecodable<'de>>(
+        &mut self,
+        version: i16,
+    ) -> Result<Option<Vec<T>>, Self::Error>
+    where
+        Self: Sized,
+    {
+        let len = match self.read_nullable_array_hdr()? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        let mut v = Vec::with_capacity(len.min(self.remaining()));
+        for i in 0..len {
+            match T::deserialize_borrowed(version, self) {
+                Ok(e) => v.push(e),
+                Err(e) => return Err(self.annotate_error(e, PathSegment::Index(i))),
+            }
+        }
+        Ok(Some(v))
+    }
+
+    // Array of structs keyed by one of their fields, e.g. JoinGroupRequest
+    // protocols keyed by name
+    fn read_map<K, T, F>(
+        &mut self,
+        version: i16,
+        key: F,
+    ) -> Result<LinkedHashMap<K, T>, Self::Error>
+    where
+        Self: Sized,
+        K: Hash + Eq,
+        T: KafkaProtoBorrowDecodable<'de>,
+        F: Fn(&T) -> K,
+    {
+        let len = self.read_array_hdr()?;
+        let mut m = LinkedHashMap::with_capacity(len.min(self.remaining()));
+        for i in 0..len {
+            match T::deserialize_borrowed(version, self) {
+                Ok(e) => {
+                    m.insert(key(&e), e);
+                }
+                Err(e) => return Err(self.annotate_error(e, PathSegment::Index(i))),
+            }
+        }
+        Ok(m)
+    }
+
+    // Tagged fields section, which ends every struct in flexible versions.
+    // In non-flexible versions there is no such section and nothing is read.
+    //
+    // `f` is called with a tag and a size of every tagged field found and
+    // returns whether it has consumed the field. Fields which are not consumed
+    // are skipped.
+    fn read_tagged_fields<F>(&mut self, f: F) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&mut Self, u32, usize) -> Result<bool, Self::Error>;
+
+    fn skip_tagged_fields(&mut self) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        self.read_tagged_fields(|_, _, _| Ok(false))
+    }
 }
@@ -1,10 +1,17 @@
 use std::convert::TryInto;
 
-use crate::{error::Error, KafkaMessage};
+use crate::error::{Error, PathSegment};
+use crate::KafkaMessage;
 
+#[cfg(feature = "bytes")]
+mod bytes;
 mod decoders;
+#[cfg(test)]
+mod tests;
 mod varint;
 
+#[cfg(feature = "bytes")]
+pub use self::bytes::*;
 pub use decoders::*;
 
 pub struct KafkaDeserializer<'a> {
@@ -12,12 +19,15 @@ pub struct KafkaDeserializer<'a> {
     i: usize,
 }
 
+// Cursor stays put if there are not enough bytes, so that errors point at the
+// value which failed
 macro_rules! read_n {
     ($self:expr,$x:expr) => {{
         let i = $self.i;
-        //$self.i += TryInto::<usize>::try_into($x)?;
-        $self.i += $x;
-        $self.buf.get(i..$self.i).ok_or(Error::OutOfBounds)?
+        let end = i + $x;
+        let r = $self.buf.get(i..end).ok_or(Error::OutOfBounds)?;
+        $self.i = end;
+        r
     }};
 }
 
@@ -31,23 +41,45 @@ macro_rules! read_primitive {
     }};
 }
 
-impl KafkaDeserializer<'_> {
-    pub fn new(buf: &[u8]) -> KafkaDeserializer {
+impl<'a> KafkaDeserializer<'a> {
+    pub fn new(buf: &[u8]) -> KafkaDeserializer<'_> {
         KafkaDeserializer { buf, i: 0 }
     }
 
+    // Number of bytes consumed so far
+    pub fn position(&self) -> usize {
+        self.i
+    }
+
+    // Number of bytes left to read
+    pub(crate) fn remaining(&self) -> usize {
+        self.buf.len().saturating_sub(self.i)
+    }
+
+    // Bytes which are not length prefixed, e.g. key and value of a record
+    pub(crate) fn read_raw_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        Ok(read_n!(self, len))
+    }
+
+    // Consumes only the bytes varint occupies, cursor stays put on error
     fn read_varuint(&mut self) -> Result<u32, Error> {
-        let buf: [u8; 5] = read_n!(self, 5)
-            .try_into()
-            .map_err(|_| Error::OutOfBounds)?;
-        Ok(varint::decode_varint64(&buf)? as u32)
+        let (v, len) = varint::decode_varint32(self.buf.get(self.i..).unwrap_or_default())?;
+        self.i += len;
+        Ok(v)
+    }
+
+    // Compact strings, bytes and arrays encode length N as N+1, 0 is null
+    fn read_compact_len(&mut self) -> Result<Option<usize>, Error> {
+        match self.read_varuint()? {
+            0 => Ok(None),
+            len => Ok(Some((len - 1).try_into()?)),
+        }
     }
 
     fn read_varulong(&mut self) -> Result<u64, Error> {
-        let buf: [u8; 10] = read_n!(self, 10)
-            .try_into()
-            .map_err(|_| Error::OutOfBounds)?;
-        varint::decode_varint64(&buf)
+        let (v, len) = varint::decode_varint64(self.buf.get(self.i..).unwrap_or_default())?;
+        self.i += len;
+        Ok(v)
     }
 }
 
@@ -71,6 +103,10 @@ impl<'de, 'a: 'de> KafkaProtoDecoder<'de> for KafkaDeserializer<'a> {
         Ok(read_primitive!(self, i64))
     }
 
+    fn read_uint16(&mut self) -> Result<u16, Self::Error> {
+        Ok(read_primitive!(self, u16))
+    }
+
     fn read_uint32(&mut self) -> Result<u32, Self::Error> {
         Ok(read_primitive!(self, u32))
     }
@@ -83,8 +119,14 @@ impl<'de, 'a: 'de> KafkaProtoDecoder<'de> for KafkaDeserializer<'a> {
         Ok(varint::decode_zig_zag_64(self.read_varulong()?))
     }
 
+    fn read_unsigned_varint(&mut self) -> Result<u32, Self::Error> {
+        self.read_varuint()
+    }
+
     fn read_uuid(&mut self) -> Result<uuid::Uuid, Self::Error> {
-        unimplemented!()
+        Ok(uuid::Uuid::from_bytes(
+            read_primitive!(self, u128).to_be_bytes(),
+        ))
     }
 
     fn read_float64(&mut self) -> Result<f64, Self::Error> {
@@ -97,8 +139,8 @@ impl<'de, 'a: 'de> KafkaProtoDecoder<'de> for KafkaDeserializer<'a> {
     }
 
     fn read_compact_string(&mut self) -> Result<&'de str, Self::Error> {
-        let len: usize = self.read_varuint()?.try_into()?;
-        std::str::from_utf8(read_n!(self, len)).map_err(Into::into)
+        self.read_compact_nullable_string()?
+            .ok_or(Error::UnexpectedNull)
     }
 
     fn read_nullable_string(&mut self) -> Result<Option<&'de str>, Self::Error> {
@@ -112,11 +154,9 @@ impl<'de, 'a: 'de> KafkaProtoDecoder<'de> for KafkaDeserializer<'a> {
     }
 
     fn read_compact_nullable_string(&mut self) -> Result<Option<&'de str>, Self::Error> {
-        let len: usize = self.read_varuint()?.try_into()?;
-        if len == (0) {
-            Ok(None)
-        } else {
-            Ok(Some(std::str::from_utf8(read_n!(self, len))?))
+        match self.read_compact_len()? {
+            Some(len) => Ok(Some(std::str::from_utf8(read_n!(self, len))?)),
+            None => Ok(None),
         }
     }
 
@@ -126,8 +166,8 @@ impl<'de, 'a: 'de> KafkaProtoDecoder<'de> for KafkaDeserializer<'a> {
     }
 
     fn read_compact_bytes(&mut self) -> Result<&'de [u8], Self::Error> {
-        let len: usize = self.read_varuint()?.try_into()?;
-        Ok(read_n!(self, len))
+        self.read_compact_nullable_bytes()?
+            .ok_or(Error::UnexpectedNull)
     }
 
     fn read_nullable_bytes(&mut self) -> Result<Option<&'de [u8]>, Self::Error> {
@@ -141,39 +181,48 @@ impl<'de, 'a: 'de> KafkaProtoDecoder<'de> for KafkaDeserializer<'a> {
     }
 
     fn read_compact_nullable_bytes(&mut self) -> Result<Option<&'de [u8]>, Self::Error> {
-        let len: usize = self.read_varuint()?.try_into()?;
-        if len == 0 {
-            Ok(None)
-        } else {
-            Ok(Some(read_n!(self, len)))
+        match self.read_compact_len()? {
+            Some(len) => Ok(Some(read_n!(self, len))),
+            None => Ok(None),
         }
     }
 
     //fn read_records(&mut self) -> Result< &[Records], Self::Error>;
 
     fn read_array_hdr(&mut self) -> Result<usize, Self::Error> {
-        let len = self.read_int32()?;
-        if len == (-1) {
-            Ok(0)
-        } else {
-            len.try_into().map_err(Into::into)
+        self.read_nullable_array_hdr()?.ok_or(Error::UnexpectedNull)
+    }
+
+    fn read_nullable_array_hdr(&mut self) -> Result<Option<usize>, Self::Error> {
+        match self.read_int32()? {
+            -1 => Ok(None),
+            len => Ok(Some(len.try_into()?)),
         }
     }
     fn read_compact_array_hdr(&mut self) -> Result<usize, Self::Error> {
-        self.read_varuint()?.try_into().map_err(Into::into)
+        self.read_compact_len()?.ok_or(Error::UnexpectedNull)
+    }
+
+    fn read_compact_nullable_array_hdr(&mut self) -> Result<Option<usize>, Self::Error> {
+        self.read_compact_len()
     }
 }
 
 pub struct KafkaFlexiDeserializer<'a> {
     use_flexible: bool,
     pub de: KafkaDeserializer<'a>,
+    // Refcounted buffer being decoded, see KafkaFlexiDeserializer::new_shared
+    #[cfg(feature = "bytes")]
+    shared: Option<&'a ::bytes::Bytes>,
 }
 
 impl<'a> KafkaFlexiDeserializer<'a> {
-    pub fn new(use_flexible: bool, buf: &[u8]) -> KafkaFlexiDeserializer {
+    pub fn new(use_flexible: bool, buf: &[u8]) -> KafkaFlexiDeserializer<'_> {
         KafkaFlexiDeserializer {
             use_flexible,
             de: KafkaDeserializer::new(buf),
+            #[cfg(feature = "bytes")]
+            shared: None,
         }
     }
 }
@@ -197,6 +246,9 @@ impl<'de, 'a: 'de> KafkaFlexibleDecoder<'de> for KafkaFlexiDeserializer<'a> {
     fn read_int64(&mut self) -> Result<i64, Self::Error> {
         self.de.read_int64()
     }
+    fn read_uint16(&mut self) -> Result<u16, Self::Error> {
+        self.de.read_uint16()
+    }
     fn read_uint32(&mut self) -> Result<u32, Self::Error> {
         self.de.read_uint32()
     }
@@ -256,16 +308,131 @@ impl<'de, 'a: 'de> KafkaFlexibleDecoder<'de> for KafkaFlexiDeserializer<'a> {
             self.de.read_array_hdr()
         }
     }
+
+    fn read_nullable_array_hdr(&mut self) -> Result<Option<usize>, Self::Error> {
+        if self.use_flexible {
+            self.de.read_compact_nullable_array_hdr()
+        } else {
+            self.de.read_nullable_array_hdr()
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.de.remaining()
+    }
+
+    #[cfg(feature = "bytes")]
+    fn read_shared_nullable_bytes(&mut self) -> Result<Option<::bytes::Bytes>, Self::Error> {
+        let shared = self.shared;
+        Ok(self.read_nullable_bytes()?.map(|b| match shared {
+            Some(buf) => share_bytes(buf, b),
+            None => ::bytes::Bytes::copy_from_slice(b),
+        }))
+    }
+
+    fn annotate_error(&self, e: Self::Error, segment: PathSegment) -> Self::Error {
+        e.in_path(self.de.position(), segment)
+    }
+
+    fn read_tagged_fields<F>(&mut self, mut f: F) -> Result<(), Self::Error>
+    where
+        F: FnMut(&mut Self, u32, usize) -> Result<bool, Self::Error>,
+    {
+        if !self.use_flexible {
+            return Ok(());
+        }
+
+        let count = self.de.read_varuint()?;
+        for _ in 0..count {
+            let tag = self.de.read_varuint()?;
+            let size: usize = self.de.read_varuint()?.try_into()?;
+            if !f(self, tag, size)? {
+                read_n!(self.de, size);
+            }
+        }
+        Ok(())
+    }
 }
 
-use crate::messages::ResponseHeader;
+use crate::messages::{RequestHeader, ResponseHeader};
 
-pub fn deserialize_message<M>(ver: i16, buf: &[u8]) -> crate::error::Result<M>
+pub fn deserialize_message<'a, M>(ver: i16, buf: &'a [u8]) -> crate::error::Result<M>
 where
-    M: KafkaProtoDecodable + KafkaMessage,
+    M: KafkaProtoBorrowDecodable<'a> + KafkaMessage,
 {
     let is_flexible = ver >= M::FLEXIBLE_VERSION;
     let mut d = KafkaFlexiDeserializer::new(is_flexible, buf);
     ResponseHeader::deserialize(&mut d.de)?;
-    M::deserialize(ver, &mut KafkaFlexiDeserializer::new(is_flexible, buf))
+    read_message(ver, &mut KafkaFlexiDeserializer::new(is_flexible, buf))
+}
+
+// Request as received by a broker, version is taken from the header. Borrowed
+// messages, e.g. JoinGroupRequest<'a>, refer to `buf` instead of copying it.
+// Null client id is returned as an empty one.
+pub fn deserialize_request<'a, M>(buf: &'a [u8]) -> crate::error::Result<(RequestHeader<'a>, M)>
+where
+    M: KafkaProtoBorrowDecodable<'a> + KafkaMessage,
+{
+    let mut d = KafkaFlexiDeserializer::new(false, buf);
+    let request_api_key = d.de.read_int16()?;
+    if request_api_key != M::API_KEY {
+        return Err(Error::UnknownApiKey(request_api_key));
+    }
+    let ver = d.de.read_int16()?;
+    if ver < M::MIN_API_VERSION || ver > M::MAX_API_VERSION {
+        return Err(Error::UnsupportedVersion(ver));
+    }
+    let hdr_ver = M::request_header_version(ver);
+    let hdr = RequestHeader {
+        request_api_key,
+        request_api_version: ver,
+        correlation_id: d.de.read_int32()?,
+        // Client id is never compact, even in flexible header versions
+        client_id: if hdr_ver > 0 {
+            d.de.read_nullable_string()?.unwrap_or_default()
+        } else {
+            ""
+        },
+    };
+    if hdr_ver >= 2 {
+        d.use_flexible = true;
+        d.skip_tagged_fields()?;
+    }
+
+    d.use_flexible = ver >= M::FLEXIBLE_VERSION;
+    Ok((hdr, read_message(ver, &mut d)?))
+}
+
+// Counterpart of `ser::serialize_versioned`. Versions newer than `max_ver` are
+// decoded as `max_ver`: newer versions only append fields, which are ignored.
+// Returns the version found in the payload alongside the message.
+pub fn deserialize_versioned<'a, M>(
+    min_ver: i16,
+    max_ver: i16,
+    flexible_ver: i16,
+    buf: &'a [u8],
+) -> crate::error::Result<(i16, M)>
+where
+    M: KafkaProtoBorrowDecodable<'a>,
+{
+    let mut d = KafkaFlexiDeserializer::new(false, buf);
+    let ver = d.de.read_int16()?;
+    if ver < min_ver {
+        return Err(Error::UnsupportedVersion(ver));
+    }
+    d.use_flexible = ver >= flexible_ver;
+    Ok((ver, read_message(ver.min(max_ver), &mut d)?))
+}
+
+// Errors are rooted at the name of the message, e.g. JoinGroupRequest.group_id
+fn read_message<'a, M>(ver: i16, d: &mut KafkaFlexiDeserializer<'a>) -> crate::error::Result<M>
+where
+    M: KafkaProtoBorrowDecodable<'a>,
+{
+    M::deserialize_borrowed(ver, d).map_err(|e| {
+        let name = std::any::type_name::<M>();
+        let name = name.split('<').next().unwrap_or(name);
+        let name = name.rsplit("::").next().unwrap_or(name);
+        d.annotate_error(e, PathSegment::Message(name))
+    })
 }
@@ -0,0 +1,344 @@
+use super::varint::{decode_varint32, decode_varint64};
+use super::*;
+use crate::messages::{
+    DescribeUserScramCredentialsRequest, FetchRequest, FetchRequestPartition, FetchRequestTopic,
+    FetchResponse, FetchResponsePartition,
+};
+use crate::ser::{
+    serialize_message, KafkaFlexiSerializer, KafkaProtoEncodable, KafkaProtoEncoder,
+    KafkaSerializer,
+};
+
+#[test]
+fn varint_at_end_of_buffer() {
+    assert_eq!(decode_varint32(&[0x05]).unwrap(), (5, 1));
+    assert_eq!(decode_varint64(&[0x7f]).unwrap(), (127, 1));
+
+    let mut d = KafkaDeserializer::new(&[0x01, 0x02]);
+    assert_eq!(d.read_int8().unwrap(), 1);
+    assert_eq!(d.read_unsigned_varint().unwrap(), 2);
+    assert_eq!(d.position(), 2);
+    assert!(matches!(d.read_unsigned_varint(), Err(Error::OutOfBounds)));
+}
+
+#[test]
+fn truncated_varint() {
+    assert!(matches!(decode_varint32(&[]), Err(Error::OutOfBounds)));
+    assert!(matches!(decode_varint32(&[0x80]), Err(Error::OutOfBounds)));
+    assert!(matches!(
+        decode_varint64(&[0xff, 0xff, 0xff]),
+        Err(Error::OutOfBounds)
+    ));
+
+    // Cursor stays at the start of varint
+    let mut d = KafkaDeserializer::new(&[0x00, 0x80, 0x80]);
+    assert_eq!(d.read_int8().unwrap(), 0);
+    assert!(matches!(d.read_varint(), Err(Error::OutOfBounds)));
+    assert_eq!(d.position(), 1);
+}
+
+#[test]
+fn overlong_varint() {
+    assert_eq!(
+        decode_varint32(&[0xff, 0xff, 0xff, 0xff, 0x0f]).unwrap(),
+        (u32::MAX, 5)
+    );
+    // 6 bytes
+    assert!(matches!(
+        decode_varint32(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x00]),
+        Err(Error::IncorrectVarint)
+    ));
+    // Bits above 32
+    assert!(matches!(
+        decode_varint32(&[0xff, 0xff, 0xff, 0xff, 0x1f]),
+        Err(Error::IncorrectVarint)
+    ));
+    assert!(matches!(
+        decode_varint64(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]),
+        Err(Error::IncorrectVarint)
+    ));
+}
+
+#[test]
+fn non_minimal_varint() {
+    assert_eq!(decode_varint32(&[0x80, 0x00]).unwrap(), (0, 2));
+    assert_eq!(decode_varint32(&[0x81, 0x80, 0x00]).unwrap(), (1, 3));
+}
+
+#[test]
+fn zig_zag_round_trip() {
+    for (v, len) in [(0, 1), (-1, 1), (1, 1), (i32::MIN, 5), (i32::MAX, 5)] {
+        let mut buf = Vec::new();
+        KafkaSerializer::new(&mut buf).emit_varint(v).unwrap();
+        assert_eq!(buf.len(), len, "{}", v);
+        assert_eq!(KafkaDeserializer::new(&buf).read_varint().unwrap(), v);
+    }
+    for (v, len) in [(0, 1), (-1, 1), (i64::MIN, 10), (i64::MAX, 10)] {
+        let mut buf = Vec::new();
+        KafkaSerializer::new(&mut buf).emit_varlong(v).unwrap();
+        assert_eq!(buf.len(), len, "{}", v);
+        assert_eq!(KafkaDeserializer::new(&buf).read_varlong().unwrap(), v);
+    }
+}
+
+#[test]
+fn truncated_nested_message() {
+    let partition = |partition| FetchRequestPartition {
+        partition,
+        fetch_offset: 100,
+        partition_max_bytes: 1 << 20,
+        ..Default::default()
+    };
+    let m = FetchRequest {
+        topics: vec![FetchRequestTopic {
+            topic: "t".to_string(),
+            partitions: vec![partition(0), partition(1)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    serialize_message(&m, 4, "c", &mut buf).unwrap();
+
+    // Header takes 11 bytes, fields of the request up to topics 17, topics
+    // array, topic name and partitions array 11, first partition 16.
+    // fetch_offset of the second partition starts at 59 and is cut in half.
+    match deserialize_request::<FetchRequest>(&buf[..63]) {
+        Err(Error::Decode {
+            offset,
+            path,
+            source,
+        }) => {
+            assert_eq!(path, "FetchRequest.topics[0].partitions[1].fetch_offset");
+            assert_eq!(offset, 59);
+            assert!(matches!(*source, Error::OutOfBounds));
+        }
+        r => panic!("unexpected result {:?}", r.map(|_| ())),
+    }
+}
+
+#[test]
+fn uuid_is_16_bytes_big_endian() {
+    let v = uuid::Uuid::from_u128(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff);
+    let mut buf = Vec::new();
+    KafkaSerializer::new(&mut buf).emit_uuid(v).unwrap();
+    assert_eq!(
+        buf,
+        [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff
+        ]
+    );
+
+    let mut d = KafkaDeserializer::new(&buf);
+    assert_eq!(d.read_uuid().unwrap(), v);
+    assert_eq!(d.position(), 16);
+}
+
+#[test]
+fn nil_uuid_round_trip() {
+    let mut buf = Vec::new();
+    crate::TopicId::ZERO
+        .serialize(0, &mut KafkaFlexiSerializer::new(true, &mut buf))
+        .unwrap();
+    assert_eq!(buf, [0; 16]);
+
+    let id = crate::TopicId::deserialize(0, &mut KafkaFlexiDeserializer::new(true, &buf)).unwrap();
+    assert!(id.is_zero());
+}
+
+#[test]
+fn truncated_uuid() {
+    let mut d = KafkaDeserializer::new(&[0xff; 15]);
+    assert!(matches!(d.read_uuid(), Err(Error::OutOfBounds)));
+    assert_eq!(d.position(), 0);
+}
+
+#[test]
+fn huge_array_length() {
+    // Declared length is not trusted for reservation, reading fails at the
+    // first missing element instead of trying to allocate for 2^31 of them
+    for use_flexible in [false, true] {
+        let hdr: &[u8] = if use_flexible {
+            &[0xff, 0xff, 0xff, 0xff, 0x0f]
+        } else {
+            &[0x7f, 0xff, 0xff, 0xff]
+        };
+        let mut d = KafkaFlexiDeserializer::new(use_flexible, hdr);
+        assert!(matches!(
+            d.read_array::<FetchRequestTopic>(12),
+            Err(Error::Decode { .. })
+        ));
+        let mut d = KafkaFlexiDeserializer::new(use_flexible, hdr);
+        assert!(matches!(
+            d.read_nullable_array::<FetchRequestTopic>(12),
+            Err(Error::Decode { .. })
+        ));
+        let mut d = KafkaFlexiDeserializer::new(use_flexible, hdr);
+        assert!(matches!(
+            d.read_map(12, |t: &FetchRequestTopic| t.topic.clone()),
+            Err(Error::Decode { .. })
+        ));
+    }
+}
+
+// Body of DescribeUserScramCredentialsRequest v0 behind request header v2
+// (api key 50, version 0, correlation id 1, null client id, no tagged fields).
+// Compact lengths are unsigned varints of N+1 and 0 for null, as the generated
+// Java writers lay them out with writeUnsignedVarint(size + 1).
+fn describe_scram_request(body: &[u8]) -> Vec<u8> {
+    let mut buf = vec![0, 50, 0, 0, 0, 0, 0, 1, 0xff, 0xff, 0];
+    buf.extend_from_slice(body);
+    buf
+}
+
+#[test]
+fn compact_lengths_are_n_plus_one() {
+    let mut d = KafkaDeserializer::new(&[0x04, b'a', b'b', b'c', 0x03, 1, 2, 0x01, 0x03]);
+    assert_eq!(d.read_compact_string().unwrap(), "abc");
+    assert_eq!(d.read_compact_bytes().unwrap(), [1, 2]);
+    assert_eq!(d.read_compact_nullable_string().unwrap(), Some(""));
+    assert_eq!(d.read_compact_array_hdr().unwrap(), 2);
+
+    // 127 bytes take a 2 byte length
+    let mut buf = vec![0x80, 0x01];
+    buf.extend_from_slice(&[b'x'; 127]);
+    let mut d = KafkaDeserializer::new(&buf);
+    assert_eq!(d.read_compact_string().unwrap().len(), 127);
+
+    // users: [{name: "alice"}], tagged fields of the user and of the request
+    let buf = describe_scram_request(&[0x02, 0x06, b'a', b'l', b'i', b'c', b'e', 0x00, 0x00]);
+    let (_, r) = deserialize_request::<DescribeUserScramCredentialsRequest>(&buf).unwrap();
+    let users = r.users.unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].name, "alice");
+}
+
+#[test]
+fn compact_zero_length_is_null() {
+    let mut d = KafkaDeserializer::new(&[0x00]);
+    assert!(matches!(
+        d.read_compact_string(),
+        Err(Error::UnexpectedNull)
+    ));
+    let mut d = KafkaDeserializer::new(&[0x00]);
+    assert!(matches!(d.read_compact_bytes(), Err(Error::UnexpectedNull)));
+    let mut d = KafkaDeserializer::new(&[0x00]);
+    assert!(matches!(
+        d.read_compact_array_hdr(),
+        Err(Error::UnexpectedNull)
+    ));
+
+    // Null name of a user
+    let buf = describe_scram_request(&[0x02, 0x00, 0x00, 0x00]);
+    match deserialize_request::<DescribeUserScramCredentialsRequest>(&buf) {
+        Err(Error::Decode {
+            offset,
+            path,
+            source,
+        }) => {
+            assert_eq!(path, "DescribeUserScramCredentialsRequest.users[0].name");
+            // Right past the length of the name
+            assert_eq!(offset, 13);
+            assert!(matches!(*source, Error::UnexpectedNull));
+        }
+        r => panic!("unexpected result {:?}", r.map(|_| ())),
+    }
+}
+
+#[test]
+fn nullable_compact_arrays() {
+    let buf = describe_scram_request(&[0x00, 0x00]);
+    let (_, r) = deserialize_request::<DescribeUserScramCredentialsRequest>(&buf).unwrap();
+    assert!(r.users.is_none());
+
+    let buf = describe_scram_request(&[0x01, 0x00]);
+    let (_, r) = deserialize_request::<DescribeUserScramCredentialsRequest>(&buf).unwrap();
+    assert!(r.users.unwrap().is_empty());
+}
+
+#[test]
+fn nullable_array_null_and_empty() {
+    // Length -1 (compact 0) is null, 0 (compact 1) is an empty array
+    for (use_flexible, null, empty) in [
+        (
+            false,
+            &[0xff, 0xff, 0xff, 0xff][..],
+            &[0x00, 0x00, 0x00, 0x00][..],
+        ),
+        (true, &[0x00][..], &[0x01][..]),
+    ] {
+        let mut d = KafkaFlexiDeserializer::new(use_flexible, null);
+        assert!(d
+            .read_nullable_array::<FetchRequestTopic>(4)
+            .unwrap()
+            .is_none());
+        let mut d = KafkaFlexiDeserializer::new(use_flexible, empty);
+        assert_eq!(
+            d.read_nullable_array::<FetchRequestTopic>(4)
+                .unwrap()
+                .map(|v| v.len()),
+            Some(0)
+        );
+    }
+
+    // Both survive a round trip, before version 4 there is no such field
+    for ver in [4, 11, 12, 16] {
+        for aborted_transactions in [None, Some(Vec::new())] {
+            let p = FetchResponsePartition {
+                aborted_transactions,
+                ..Default::default()
+            };
+            let use_flexible = ver >= FetchResponse::FLEXIBLE_VERSION;
+            let mut buf = Vec::new();
+            p.serialize(ver, &mut KafkaFlexiSerializer::new(use_flexible, &mut buf))
+                .unwrap();
+            let mut d = KafkaFlexiDeserializer::new(use_flexible, &buf);
+            let read = FetchResponsePartition::deserialize(ver, &mut d).unwrap();
+            assert_eq!(
+                read.aborted_transactions.map(|v| v.len()),
+                p.aborted_transactions.map(|v| v.len()),
+                "version {}",
+                ver
+            );
+        }
+    }
+}
+
+#[test]
+fn null_non_nullable_array() {
+    // topics is the last field of version 4
+    let mut buf = Vec::new();
+    serialize_message(&FetchRequest::default(), 4, "c", &mut buf).unwrap();
+    let len = buf.len();
+    buf[len - 4..].copy_from_slice(&[0xff; 4]);
+    match deserialize_request::<FetchRequest>(&buf) {
+        Err(Error::Decode { path, source, .. }) => {
+            assert_eq!(path, "FetchRequest.topics");
+            assert!(matches!(*source, Error::UnexpectedNull));
+        }
+        r => panic!("unexpected result {:?}", r.map(|_| ())),
+    }
+
+    let mut d = KafkaFlexiDeserializer::new(true, &[0x00]);
+    assert!(matches!(
+        d.read_array::<FetchRequestTopic>(12),
+        Err(Error::UnexpectedNull)
+    ));
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn share_bytes_slices_buffer() {
+    let buf = ::bytes::Bytes::from(b"0123456789".to_vec());
+    let shared = share_bytes(&buf, &buf[2..5]);
+    assert_eq!(&shared[..], b"234");
+    assert_eq!(shared.as_ptr(), buf[2..].as_ptr());
+
+    // Slices of other buffers are copied
+    let other = b"234".to_vec();
+    let copied = share_bytes(&buf, &other);
+    assert_eq!(&copied[..], b"234");
+    assert!(!buf.as_ptr_range().contains(&copied.as_ptr()));
+    assert!(share_bytes(&buf, &[]).is_empty());
+}
@@ -8,20 +8,37 @@ pub(crate) fn decode_zig_zag_64(n: u64) -> i64 {
     ((n >> 1) as i64) ^ (-((n & 1) as i64))
 }
 
-pub(crate) fn decode_varint64(buf: &[u8]) -> Result<u64, Error> {
+pub(crate) fn decode_varint32(buf: &[u8]) -> Result<(u32, usize), Error> {
+    decode_varint(buf, 32).map(|(v, len)| (v as u32, len))
+}
+
+pub(crate) fn decode_varint64(buf: &[u8]) -> Result<(u64, usize), Error> {
+    decode_varint(buf, 64)
+}
+
+// Decodes varint of a `bits` wide integer from the start of buf and returns it
+// alongside the number of bytes it occupies. Errors are:
+//
+//   OutOfBounds: buf ends in the middle of varint, more bytes are needed
+//   IncorrectVarint: varint is longer than the integer allows or has bits which
+//     don't fit into it
+//
+// Like Kafka, varints which aren't minimally encoded are accepted.
+fn decode_varint(buf: &[u8], bits: u32) -> Result<(u64, usize), Error> {
+    let max_len = bits.div_ceil(7) as usize;
     let mut r: u64 = 0;
-    let mut i = 0;
 
-    for &b in buf {
-        if i == 10 {
+    for i in 0..max_len {
+        let b = *buf.get(i).ok_or(Error::OutOfBounds)?;
+        let shift = i as u32 * 7;
+        let v = (b & 0x7f) as u64;
+        if shift + 7 > bits && v >> (bits - shift) != 0 {
             return Err(Error::IncorrectVarint);
         }
-        // TODO: may overflow if i == 9
-        r = r | (((b & 0x7f) as u64) << (i * 7));
-        i += 1;
+        r |= v << shift;
         if b < 0x80 {
-            return Ok(r);
+            return Ok((r, i + 1));
         }
     }
-    return Err(Error::IncorrectVarint);
+    Err(Error::IncorrectVarint)
 }
@@ -0,0 +1,206 @@
+use std::fmt;
+
+// Error codes of responses. Retriable errors are transient, the request may
+// succeed if retried, possibly after refreshing metadata.
+//
+// Ref: https://github.com/apache/kafka/blob/3.9.0/clients/src/main/java/org/apache/kafka/common/protocol/Errors.java
+macro_rules! kafka_codes {
+    ($($code:literal => $name:ident, $retriable:literal, $description:literal;)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum KafkaCode {
+            $($name,)*
+            // Code unknown to this library, e.g. added by a newer broker
+            Unknown(i16),
+        }
+
+        impl KafkaCode {
+            pub fn from_code(code: i16) -> KafkaCode {
+                match code {
+                    $($code => KafkaCode::$name,)*
+                    code => KafkaCode::Unknown(code),
+                }
+            }
+
+            pub fn code(&self) -> i16 {
+                match self {
+                    $(KafkaCode::$name => $code,)*
+                    KafkaCode::Unknown(code) => *code,
+                }
+            }
+
+            // Unknown codes are not retriable
+            pub fn is_retriable(&self) -> bool {
+                match self {
+                    $(KafkaCode::$name => $retriable,)*
+                    KafkaCode::Unknown(_) => false,
+                }
+            }
+
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $(KafkaCode::$name => $description,)*
+                    KafkaCode::Unknown(_) => "Unknown error code.",
+                }
+            }
+        }
+    };
+}
+
+kafka_codes! {
+    -1 => UnknownServerError, false, "The server experienced an unexpected error when processing the request.";
+    0 => None, false, "";
+    1 => OffsetOutOfRange, false, "The requested offset is not within the range of offsets maintained by the server.";
+    2 => CorruptMessage, true, "This message has failed its CRC checksum, exceeds the valid size, has a null key for a compacted topic, or is otherwise corrupt.";
+    3 => UnknownTopicOrPartition, true, "This server does not host this topic-partition.";
+    4 => InvalidFetchSize, false, "The requested fetch size is invalid.";
+    5 => LeaderNotAvailable, true, "There is no leader for this topic-partition as we are in the middle of a leadership election.";
+    6 => NotLeaderOrFollower, true, "For requests intended only for the leader, this error indicates that the broker is not the current leader. For requests intended for any replica, this error indicates that the broker is not a replica of the topic partition.";
+    7 => RequestTimedOut, true, "The request timed out.";
+    8 => BrokerNotAvailable, false, "The broker is not available.";
+    9 => ReplicaNotAvailable, true, "The replica is not available for the requested topic-partition. Produce/Fetch requests and other requests intended only for the leader or follower return NOT_LEADER_OR_FOLLOWER if the broker is not a replica of the topic-partition.";
+    10 => MessageTooLarge, false, "The request included a message larger than the max message size the server will accept.";
+    11 => StaleControllerEpoch, false, "The controller moved to another broker.";
+    12 => OffsetMetadataTooLarge, false, "The metadata field of the offset request was too large.";
+    13 => NetworkException, true, "The server disconnected before a response was received.";
+    14 => CoordinatorLoadInProgress, true, "The coordinator is loading and hence can't process requests.";
+    15 => CoordinatorNotAvailable, true, "The coordinator is not available.";
+    16 => NotCoordinator, true, "This is not the correct coordinator.";
+    17 => InvalidTopicException, false, "The request attempted to perform an operation on an invalid topic.";
+    18 => RecordListTooLarge, false, "The request included message batch larger than the configured segment size on the server.";
+    19 => NotEnoughReplicas, true, "Messages are rejected since there are fewer in-sync replicas than required.";
+    20 => NotEnoughReplicasAfterAppend, true, "Messages are written to the log, but to fewer in-sync replicas than required.";
+    21 => InvalidRequiredAcks, false, "Produce request specified an invalid value for required acks.";
+    22 => IllegalGeneration, false, "Specified group generation id is not valid.";
+    23 => InconsistentGroupProtocol, false, "The group member's supported protocols are incompatible with those of existing members or first group member tried to join with empty protocol type or empty protocol list.";
+    24 => InvalidGroupId, false, "The configured groupId is invalid.";
+    25 => UnknownMemberId, false, "The coordinator is not aware of this member.";
+    26 => InvalidSessionTimeout, false, "The session timeout is not within the range allowed by the broker (as configured by group.min.session.timeout.ms and group.max.session.timeout.ms).";
+    27 => RebalanceInProgress, false, "The group is rebalancing, so a rejoin is needed.";
+    28 => InvalidCommitOffsetSize, false, "The committing offset data size is not valid.";
+    29 => TopicAuthorizationFailed, false, "Topic authorization failed.";
+    30 => GroupAuthorizationFailed, false, "Group authorization failed.";
+    31 => ClusterAuthorizationFailed, false, "Cluster authorization failed.";
+    32 => InvalidTimestamp, false, "The timestamp of the message is out of acceptable range.";
+    33 => UnsupportedSaslMechanism, false, "The broker does not support the requested SASL mechanism.";
+    34 => IllegalSaslState, false, "Request is not valid given the current SASL state.";
+    35 => UnsupportedVersion, false, "The version of API is not supported.";
+    36 => TopicAlreadyExists, false, "Topic with this name already exists.";
+    37 => InvalidPartitions, false, "Number of partitions is below 1.";
+    38 => InvalidReplicationFactor, false, "Replication factor is below 1 or larger than the number of available brokers.";
+    39 => InvalidReplicaAssignment, false, "Replica assignment is invalid.";
+    40 => InvalidConfig, false, "Configuration is invalid.";
+    41 => NotController, true, "This is not the correct controller for this cluster.";
+    42 => InvalidRequest, false, "This most likely occurs because of a request being malformed by the client library or the message was sent to an incompatible broker. See the broker logs for more details.";
+    43 => UnsupportedForMessageFormat, false, "The message format version on the broker does not support the request.";
+    44 => PolicyViolation, false, "Request parameters do not satisfy the configured policy.";
+    45 => OutOfOrderSequenceNumber, false, "The broker received an out of order sequence number.";
+    46 => DuplicateSequenceNumber, false, "The broker received a duplicate sequence number.";
+    47 => InvalidProducerEpoch, false, "Producer attempted to produce with an old epoch.";
+    48 => InvalidTxnState, false, "The producer attempted a transactional operation in an invalid state.";
+    49 => InvalidProducerIdMapping, false, "The producer attempted to use a producer id which is not currently assigned to its transactional id.";
+    50 => InvalidTransactionTimeout, false, "The transaction timeout is larger than the maximum value allowed by the broker (as configured by transaction.max.timeout.ms).";
+    51 => ConcurrentTransactions, false, "The producer attempted to update a transaction while another concurrent operation on the same transaction was ongoing.";
+    52 => TransactionCoordinatorFenced, false, "Indicates that the transaction coordinator sending a WriteTxnMarker is no longer the current coordinator for a given producer.";
+    53 => TransactionalIdAuthorizationFailed, false, "Transactional Id authorization failed.";
+    54 => SecurityDisabled, false, "Security features are disabled.";
+    55 => OperationNotAttempted, false, "The broker did not attempt to execute this operation. This may happen for batched RPCs where some operations in the batch failed, causing the broker to respond without trying the rest.";
+    56 => KafkaStorageError, true, "Disk error when trying to access log file on the disk.";
+    57 => LogDirNotFound, false, "The user-specified log directory is not found in the broker config.";
+    58 => SaslAuthenticationFailed, false, "SASL Authentication failed.";
+    59 => UnknownProducerId, false, "This exception is raised by the broker if it could not locate the producer metadata associated with the producerId in question. This could happen if, for instance, the producer's records were deleted because their retention time had elapsed. Once the last records of the producerId are removed, the producer's metadata is removed from the broker, and future appends by the producer will return this exception.";
+    60 => ReassignmentInProgress, false, "A partition reassignment is in progress.";
+    61 => DelegationTokenAuthDisabled, false, "Delegation Token feature is not enabled.";
+    62 => DelegationTokenNotFound, false, "Delegation Token is not found on server.";
+    63 => DelegationTokenOwnerMismatch, false, "Specified Principal is not valid Owner/Renewer.";
+    64 => DelegationTokenRequestNotAllowed, false, "Delegation Token requests are not allowed on PLAINTEXT/1-way SSL channels and on delegation token authenticated channels.";
+    65 => DelegationTokenAuthorizationFailed, false, "Delegation Token authorization failed.";
+    66 => DelegationTokenExpired, false, "Delegation Token is expired.";
+    67 => InvalidPrincipalType, false, "Supplied principalType is not supported.";
+    68 => NonEmptyGroup, false, "The group is not empty.";
+    69 => GroupIdNotFound, false, "The group id does not exist.";
+    70 => FetchSessionIdNotFound, true, "The fetch session ID was not found.";
+    71 => InvalidFetchSessionEpoch, true, "The fetch session epoch is invalid.";
+    72 => ListenerNotFound, true, "There is no listener on the leader broker that matches the listener on which metadata request was processed.";
+    73 => TopicDeletionDisabled, false, "Topic deletion is disabled.";
+    74 => FencedLeaderEpoch, true, "The leader epoch in the request is older than the epoch on the broker.";
+    75 => UnknownLeaderEpoch, true, "The leader epoch in the request is newer than the epoch on the broker.";
+    76 => UnsupportedCompressionType, false, "The requesting client does not support the compression type of given partition.";
+    77 => StaleBrokerEpoch, false, "Broker epoch has changed.";
+    78 => OffsetNotAvailable, true, "The leader high watermark has not caught up from a recent leader election so the offsets cannot be guaranteed to be monotonically increasing.";
+    79 => MemberIdRequired, false, "The group member needs to have a valid member id before actually entering a consumer group.";
+    80 => PreferredLeaderNotAvailable, true, "The preferred leader was not available.";
+    81 => GroupMaxSizeReached, false, "The consumer group has reached its max size.";
+    82 => FencedInstanceId, false, "The broker rejected this static consumer since another consumer with the same group.instance.id has registered with a different member.id.";
+    83 => EligibleLeadersNotAvailable, true, "Eligible topic partition leaders are not available.";
+    84 => ElectionNotNeeded, true, "Leader election not needed for topic partition.";
+    85 => NoReassignmentInProgress, false, "No partition reassignment is in progress.";
+    86 => GroupSubscribedToTopic, false, "Deleting offsets of a topic is forbidden while the consumer group is actively subscribed to it.";
+    87 => InvalidRecord, false, "This record has failed the validation on broker and hence will be rejected.";
+    88 => UnstableOffsetCommit, true, "There are unstable offsets that need to be cleared.";
+    89 => ThrottlingQuotaExceeded, true, "The throttling quota has been exceeded.";
+    90 => ProducerFenced, false, "There is a newer producer with the same transactionalId which fences the current one.";
+    91 => ResourceNotFound, false, "A request illegally referred to a resource that does not exist.";
+    92 => DuplicateResource, false, "A request illegally referred to the same resource twice.";
+    93 => UnacceptableCredential, false, "Requested credential would not meet criteria for acceptability.";
+    94 => InconsistentVoterSet, false, "Indicates that the either the sender or recipient of a voter-only request is not one of the expected voters.";
+    95 => InvalidUpdateVersion, false, "The given update version was invalid.";
+    96 => FeatureUpdateFailed, false, "Unable to update finalized features due to an unexpected server error.";
+    97 => PrincipalDeserializationFailure, false, "Request principal deserialization failed during forwarding. This indicates an internal error on the broker cluster security setup.";
+    98 => SnapshotNotFound, false, "Requested snapshot was not found.";
+    99 => PositionOutOfRange, false, "Requested position is not greater than or equal to zero, and less than the size of the snapshot.";
+    100 => UnknownTopicId, true, "This server does not host this topic ID.";
+    101 => DuplicateBrokerRegistration, false, "This broker ID is already in use.";
+    102 => BrokerIdNotRegistered, false, "The given broker ID was not registered.";
+    103 => InconsistentTopicId, true, "The log's topic ID did not match the topic ID in the request.";
+    104 => InconsistentClusterId, false, "The clusterId in the request does not match that found on the server.";
+    105 => TransactionalIdNotFound, false, "The transactionalId could not be found.";
+    106 => FetchSessionTopicIdError, true, "The fetch session encountered inconsistent topic ID usage.";
+    107 => IneligibleReplica, false, "The new ISR contains at least one ineligible replica.";
+    108 => NewLeaderElected, false, "The AlterPartition request successfully updated the partition state but the leader has changed.";
+    109 => OffsetMovedToTieredStorage, false, "The requested offset is moved to tiered storage.";
+    110 => FencedMemberEpoch, false, "The member epoch is fenced by the group coordinator. The member must abandon all its partitions and rejoin.";
+    111 => UnreleasedInstanceId, false, "The instance ID is still used by another member in the consumer group. That member must leave first.";
+    112 => UnsupportedAssignor, false, "The assignor or its version range is not supported by the consumer group.";
+    113 => StaleMemberEpoch, false, "The member epoch is stale. The member must retry after receiving its updated member epoch via the ConsumerGroupHeartbeat API.";
+    114 => MismatchedEndpointType, false, "The request was sent to an endpoint of the wrong type.";
+    115 => UnsupportedEndpointType, false, "This endpoint type is not supported yet.";
+    116 => UnknownControllerId, false, "This controller ID is not known.";
+    117 => UnknownSubscriptionId, false, "Client sent a push telemetry request with an invalid or outdated subscription ID.";
+    118 => TelemetryTooLarge, false, "Client sent a push telemetry request larger than the maximum size the broker will accept.";
+    119 => InvalidRegistration, false, "The controller has considered the broker registration to be invalid.";
+    120 => TransactionAbortable, false, "The server encountered an error with the transaction. The client can abort the transaction to continue using this transactional ID.";
+    121 => InvalidRecordState, false, "The record state is invalid. The acknowledgement of delivery could not be completed.";
+    122 => ShareSessionNotFound, true, "The share session was not found.";
+    123 => InvalidShareSessionEpoch, true, "The share session epoch is invalid.";
+    124 => FencedStateEpoch, false, "The share coordinator rejected the request because the share-group state epoch did not match.";
+    125 => InvalidVoterKey, false, "The voter key doesn't match the receiving replica's key.";
+    126 => DuplicateVoter, false, "The voter is already part of the set of voters.";
+    127 => VoterNotFound, false, "The voter is not part of the set of voters.";
+}
+
+impl KafkaCode {
+    // Ok for None, so that error codes of responses can be checked with `?`
+    pub fn check(code: i16) -> Result<(), KafkaCode> {
+        match KafkaCode::from_code(code) {
+            KafkaCode::None => Ok(()),
+            e => Err(e),
+        }
+    }
+}
+
+impl From<i16> for KafkaCode {
+    fn from(code: i16) -> Self {
+        KafkaCode::from_code(code)
+    }
+}
+
+impl fmt::Display for KafkaCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KafkaCode::Unknown(code) => write!(f, "Unknown error code {}", code),
+            e => write!(f, "{:?} ({}): {}", e, e.code(), e.description()),
+        }
+    }
+}
+
+impl std::error::Error for KafkaCode {}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io;
+use thiserror::Error;
+
+mod code;
+#[cfg(test)]
+mod tests;
+
+pub use code::*;
+
+/// The result of a serialization or deserialization operation.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("IO")]
+    Io(#[from] io::Error),
+
+    #[error("Bounds check")]
+    BoundsCheck(#[from] std::num::TryFromIntError),
+
+    // Serialization errors below
+    #[error("Field {field} is not supported by version {version}")]
+    UnsupportedFieldForVersion { field: &'static str, version: i16 },
+
+    // Deserialization errors below
+    #[error("Unexpected end of buffer")]
+    OutOfBounds,
+
+    #[error("Incorrect varint")]
+    IncorrectVarint,
+
+    #[error("Null value of non-nullable field")]
+    UnexpectedNull,
+
+    #[error("UTF8 decoding error")]
+    UTF8Error(#[from] std::str::Utf8Error),
+
+    #[error("Unsupported version {0}")]
+    UnsupportedVersion(i16),
+
+    #[error("Unknown api key {0}")]
+    UnknownApiKey(i16),
+
+    #[error("Unsupported record batch magic {0}")]
+    UnsupportedMagic(i8),
+
+    #[error("Unsupported compression {0}")]
+    UnsupportedCompression(i16),
+
+    #[error("Record batch CRC mismatch")]
+    CorruptRecordBatch,
+
+    #[error("Invalid record")]
+    InvalidRecord,
+
+    #[error("Unknown control record type {0}")]
+    UnknownControlRecordType(i16),
+
+    #[error("Malformed snapshot")]
+    MalformedSnapshot,
+
+    #[error("Malformed checkpoint file")]
+    MalformedCheckpoint,
+
+    // Error of the value at `offset` of the buffer being decoded, `path` is the
+    // field holding it, e.g. JoinGroupRequest.protocols[2].metadata
+    #[error("{source} at offset {offset} in {path}")]
+    Decode {
+        offset: usize,
+        path: String,
+        source: Box<Error>,
+    },
+
+    // Error code returned by a broker
+    #[error("{0}")]
+    Kafka(#[from] KafkaCode),
+}
+
+// Part of the path of Error::Decode
+pub enum PathSegment<'a> {
+    Message(&'a str),
+    Field(&'a str),
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Message(name) => f.write_str(name),
+            PathSegment::Field(name) => write!(f, ".{}", name),
+            PathSegment::Index(i) => write!(f, "[{}]", i),
+        }
+    }
+}
+
+impl Error {
+    // Prepends the segment to the path of a decode error. Other errors become
+    // decode errors of the value at `offset`.
+    pub fn in_path(self, offset: usize, segment: PathSegment) -> Error {
+        match self {
+            Error::Decode {
+                offset,
+                mut path,
+                source,
+            } => {
+                path.insert_str(0, &segment.to_string());
+                Error::Decode {
+                    offset,
+                    path,
+                    source,
+                }
+            }
+            e => Error::Decode {
+                offset,
+                path: segment.to_string(),
+                source: Box::new(e),
+            },
+        }
+    }
+
+    // Error the decoding failed with, without the path
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::Decode { source, .. } => source.root_cause(),
+            e => e,
+        }
+    }
+}
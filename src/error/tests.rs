@@ -0,0 +1,87 @@
+use super::*;
+
+#[test]
+fn every_kafka_code_is_known() {
+    // Table has no gaps from -1 up to the last code
+    for code in -1..=127 {
+        let e = KafkaCode::from_code(code);
+        assert!(!matches!(e, KafkaCode::Unknown(_)), "{}", code);
+        assert_eq!(e.code(), code);
+        assert_eq!(KafkaCode::from(code), e);
+    }
+}
+
+#[test]
+fn known_kafka_codes() {
+    assert_eq!(KafkaCode::from_code(-1), KafkaCode::UnknownServerError);
+    assert_eq!(KafkaCode::from_code(0), KafkaCode::None);
+    assert_eq!(KafkaCode::from_code(3), KafkaCode::UnknownTopicOrPartition);
+    assert_eq!(KafkaCode::from_code(87), KafkaCode::InvalidRecord);
+    assert_eq!(KafkaCode::from_code(127), KafkaCode::VoterNotFound);
+    assert_eq!(KafkaCode::NotCoordinator.code(), 16);
+
+    assert_eq!(
+        KafkaCode::RequestTimedOut.to_string(),
+        "RequestTimedOut (7): The request timed out."
+    );
+    assert!(KafkaCode::check(0).is_ok());
+    assert!(matches!(
+        KafkaCode::check(6),
+        Err(KafkaCode::NotLeaderOrFollower)
+    ));
+}
+
+#[test]
+fn unknown_kafka_codes() {
+    for code in [-2, 128, i16::MIN, i16::MAX] {
+        let e = KafkaCode::from_code(code);
+        assert_eq!(e, KafkaCode::Unknown(code));
+        assert_eq!(e.code(), code);
+        assert!(!e.is_retriable());
+        assert_eq!(e.description(), "Unknown error code.");
+        assert_eq!(e.to_string(), format!("Unknown error code {}", code));
+        assert!(KafkaCode::check(code).is_err());
+    }
+}
+
+#[test]
+fn retriable_kafka_codes() {
+    for e in [
+        KafkaCode::CorruptMessage,
+        KafkaCode::UnknownTopicOrPartition,
+        KafkaCode::NotLeaderOrFollower,
+        KafkaCode::RequestTimedOut,
+        KafkaCode::NetworkException,
+        KafkaCode::CoordinatorLoadInProgress,
+        KafkaCode::NotCoordinator,
+        KafkaCode::NotEnoughReplicas,
+        KafkaCode::NotController,
+        KafkaCode::FencedLeaderEpoch,
+        KafkaCode::UnknownTopicId,
+    ] {
+        assert!(e.is_retriable(), "{:?}", e);
+    }
+    for e in [
+        KafkaCode::UnknownServerError,
+        KafkaCode::None,
+        KafkaCode::OffsetOutOfRange,
+        KafkaCode::BrokerNotAvailable,
+        KafkaCode::TopicAuthorizationFailed,
+        KafkaCode::UnsupportedVersion,
+        KafkaCode::InvalidRecord,
+        KafkaCode::ProducerFenced,
+        KafkaCode::FencedMemberEpoch,
+    ] {
+        assert!(!e.is_retriable(), "{:?}", e);
+    }
+}
+
+#[test]
+fn kafka_code_is_error() {
+    let e: Error = KafkaCode::NotCoordinator.into();
+    assert!(matches!(e, Error::Kafka(KafkaCode::NotCoordinator)));
+    assert_eq!(
+        e.to_string(),
+        "NotCoordinator (16): This is not the correct coordinator."
+    );
+}
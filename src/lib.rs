@@ -1,6 +1,11 @@
+pub mod assignor;
 pub mod de;
 pub mod error;
+pub mod log;
+#[macro_use]
 pub mod messages;
+pub mod metadata;
+pub mod records;
 pub mod ser;
 
 // TODO: what  is entityType? It looks like newtype to me
@@ -18,6 +23,26 @@ pub type TransactionalId = String;
 pub type ProducerId = i64;
 pub type GroupId = String;
 pub type TopicName = String;
+
+// Unique topic id assigned by the controller (KIP-516). Zero id stands for
+// unknown, e.g. in requests of versions predating topic ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct TopicId(pub uuid::Uuid);
+
+impl TopicId {
+    pub const ZERO: TopicId = TopicId(uuid::Uuid::nil());
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_nil()
+    }
+}
+
+impl From<uuid::Uuid> for TopicId {
+    fn from(v: uuid::Uuid) -> Self {
+        TopicId(v)
+    }
+}
+
 pub type BrokerId = i32;
 
 type Bytes = Vec<u8>;
@@ -28,6 +53,15 @@ pub trait KafkaMessage {
     const MIN_API_VERSION: i16;
     const MAX_API_VERSION: i16;
     const FLEXIBLE_VERSION: i16;
+
+    // Ref: https://github.com/apache/kafka/blob/2.5.0/generator/src/main/java/org/apache/kafka/message/ApiMessageTypeGenerator.java#L252-L318
+    fn request_header_version(ver: i16) -> i16 {
+        if ver >= Self::FLEXIBLE_VERSION {
+            2
+        } else {
+            1
+        }
+    }
 }
 
 pub trait KafkaRequest {
@@ -0,0 +1,76 @@
+use crate::error::{Error, Result};
+
+// leader-epoch-checkpoint file of a partition directory. Plain text, where
+// every entry maps leader epoch to the first offset written in it:
+//
+//   0             <- version
+//   2             <- number of entries
+//   0 0           <- epoch start_offset
+//   3 1500
+//
+// Ref: https://github.com/apache/kafka/blob/3.7.0/storage/src/main/java/org/apache/kafka/storage/internals/checkpoint/LeaderEpochCheckpointFile.java
+pub const LEADER_EPOCH_CHECKPOINT_FILE: &str = "leader-epoch-checkpoint";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochEntry {
+    pub epoch: i32,
+    pub start_offset: i64,
+}
+
+pub struct LeaderEpochCheckpoint {
+    pub entries: Vec<EpochEntry>,
+}
+
+impl LeaderEpochCheckpoint {
+    pub const VERSION: i32 = 0;
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut lines = s.lines();
+        let mut next_line = || lines.next().ok_or(Error::MalformedCheckpoint);
+
+        let version: i32 = next_line()?
+            .trim()
+            .parse()
+            .map_err(|_| Error::MalformedCheckpoint)?;
+        if version != Self::VERSION {
+            return Err(Error::MalformedCheckpoint);
+        }
+        let count: usize = next_line()?
+            .trim()
+            .parse()
+            .map_err(|_| Error::MalformedCheckpoint)?;
+
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let mut fields = next_line()?.split_whitespace();
+            let mut next_field = || fields.next().ok_or(Error::MalformedCheckpoint);
+            entries.push(EpochEntry {
+                epoch: next_field()?
+                    .parse()
+                    .map_err(|_| Error::MalformedCheckpoint)?,
+                start_offset: next_field()?
+                    .parse()
+                    .map_err(|_| Error::MalformedCheckpoint)?,
+            });
+        }
+        Ok(LeaderEpochCheckpoint { entries })
+    }
+
+    // Epoch of the leader which wrote the offset, None if the offset precedes
+    // the first known epoch
+    pub fn epoch_for_offset(&self, offset: i64) -> Option<i32> {
+        let i = self.entries.partition_point(|e| e.start_offset <= offset);
+        i.checked_sub(1).map(|i| self.entries[i].epoch)
+    }
+}
+
+impl std::fmt::Display for LeaderEpochCheckpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "{}", Self::VERSION)?;
+        writeln!(f, "{}", self.entries.len())?;
+        for e in &self.entries {
+            writeln!(f, "{} {}", e.epoch, e.start_offset)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::de::{KafkaDeserializer, KafkaProtoDecoder};
+use crate::error::Result;
+
+// Sparse indexes of a log segment. Offsets are stored relative to the base
+// offset of the segment. Index of the active segment is preallocated and padded
+// with zeros, so decoding stops at the first zeroed entry past the first one.
+//
+// Ref: https://github.com/apache/kafka/blob/3.7.0/storage/src/main/java/org/apache/kafka/storage/internals/log/OffsetIndex.java
+
+// Entry of .index file:
+//
+//   relativeOffset => INT32
+//   position => INT32, byte position of the batch in .log file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetPosition {
+    pub offset: i64,
+    pub position: u32,
+}
+
+pub struct OffsetIndex {
+    pub entries: Vec<OffsetPosition>,
+}
+
+impl OffsetIndex {
+    const ENTRY_SIZE: usize = 8;
+
+    pub fn from_bytes(base_offset: i64, buf: &[u8]) -> Result<Self> {
+        let mut d = KafkaDeserializer::new(buf);
+        let mut entries = Vec::with_capacity(buf.len() / Self::ENTRY_SIZE);
+        for i in 0..buf.len() / Self::ENTRY_SIZE {
+            let relative_offset = d.read_int32()?;
+            let position = d.read_uint32()?;
+            if i > 0 && relative_offset == 0 && position == 0 {
+                break;
+            }
+            entries.push(OffsetPosition {
+                offset: base_offset + relative_offset as i64,
+                position,
+            });
+        }
+        Ok(OffsetIndex { entries })
+    }
+
+    // Largest entry with offset not greater than the target one
+    pub fn lookup(&self, offset: i64) -> Option<OffsetPosition> {
+        let i = self.entries.partition_point(|e| e.offset <= offset);
+        i.checked_sub(1).map(|i| self.entries[i])
+    }
+}
+
+// Entry of .timeindex file:
+//
+//   timestamp => INT64
+//   relativeOffset => INT32, offset of the first record with a larger timestamp
+//
+// Ref: https://github.com/apache/kafka/blob/3.7.0/storage/src/main/java/org/apache/kafka/storage/internals/log/TimeIndex.java
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampOffset {
+    pub timestamp: i64,
+    pub offset: i64,
+}
+
+pub struct TimeIndex {
+    pub entries: Vec<TimestampOffset>,
+}
+
+impl TimeIndex {
+    const ENTRY_SIZE: usize = 12;
+
+    pub fn from_bytes(base_offset: i64, buf: &[u8]) -> Result<Self> {
+        let mut d = KafkaDeserializer::new(buf);
+        let mut entries = Vec::with_capacity(buf.len() / Self::ENTRY_SIZE);
+        for i in 0..buf.len() / Self::ENTRY_SIZE {
+            let timestamp = d.read_int64()?;
+            let relative_offset = d.read_int32()?;
+            if i > 0 && timestamp == 0 && relative_offset == 0 {
+                break;
+            }
+            entries.push(TimestampOffset {
+                timestamp,
+                offset: base_offset + relative_offset as i64,
+            });
+        }
+        Ok(TimeIndex { entries })
+    }
+
+    // Largest entry with timestamp not greater than the target one
+    pub fn lookup(&self, timestamp: i64) -> Option<TimestampOffset> {
+        let i = self.entries.partition_point(|e| e.timestamp <= timestamp);
+        i.checked_sub(1).map(|i| self.entries[i])
+    }
+
+    pub fn max_timestamp(&self) -> Option<i64> {
+        self.entries.last().map(|e| e.timestamp)
+    }
+}
@@ -0,0 +1,175 @@
+// Reader of a partition directory of a broker, e.g. /var/lib/kafka/foo-0. Log
+// is split into segments, each one named after the first offset it holds:
+//
+//   00000000000000000000.log        <- record batches
+//   00000000000000000000.index      <- offset -> position in .log
+//   00000000000000000000.timeindex  <- timestamp -> offset
+//   leader-epoch-checkpoint
+//
+// Files are read into memory as a whole, this is meant for offline inspection
+// rather than serving reads.
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::records::{peek_batch, RecordBatches};
+
+mod checkpoint;
+mod index;
+#[cfg(test)]
+mod tests;
+
+pub use checkpoint::*;
+pub use index::*;
+
+pub const LOG_FILE_SUFFIX: &str = ".log";
+pub const INDEX_FILE_SUFFIX: &str = ".index";
+pub const TIME_INDEX_FILE_SUFFIX: &str = ".timeindex";
+
+// Offsets are zero padded, so that file names sort by offset
+pub fn segment_file_name(base_offset: i64, suffix: &str) -> String {
+    format!("{:020}{}", base_offset, suffix)
+}
+
+pub struct LogSegment {
+    pub base_offset: i64,
+    pub log: Vec<u8>,
+    pub index: OffsetIndex,
+    pub time_index: TimeIndex,
+}
+
+impl LogSegment {
+    // Missing index files are treated as empty, they are rebuilt by the broker
+    // on startup anyway
+    pub fn open(dir: &Path, base_offset: i64) -> Result<Self> {
+        let read_optional =
+            |suffix| match std::fs::read(dir.join(segment_file_name(base_offset, suffix))) {
+                Ok(buf) => Ok(buf),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+                Err(e) => Err(e),
+            };
+        Ok(LogSegment {
+            base_offset,
+            log: std::fs::read(dir.join(segment_file_name(base_offset, LOG_FILE_SUFFIX)))?,
+            index: OffsetIndex::from_bytes(base_offset, &read_optional(INDEX_FILE_SUFFIX)?)?,
+            time_index: TimeIndex::from_bytes(
+                base_offset,
+                &read_optional(TIME_INDEX_FILE_SUFFIX)?,
+            )?,
+        })
+    }
+
+    pub fn batches(&self) -> RecordBatches<'_> {
+        RecordBatches::new(&self.log)
+    }
+
+    // Batches starting from the one which holds the offset
+    pub fn seek_offset(&self, offset: i64) -> RecordBatches<'_> {
+        let start = self.index.lookup(offset).map_or(0, |e| e.position as usize);
+        self.seek_from(start, |last_offset, _| last_offset >= offset)
+    }
+
+    // Batches starting from the first one with max timestamp not less than the
+    // target one
+    pub fn seek_timestamp(&self, timestamp: i64) -> RecordBatches<'_> {
+        let start = self
+            .time_index
+            .lookup(timestamp)
+            .and_then(|e| self.index.lookup(e.offset))
+            .map_or(0, |e| e.position as usize);
+        self.seek_from(start, |_, max_timestamp| max_timestamp >= timestamp)
+    }
+
+    // Largest timestamp in the segment, taken from the time index if possible
+    pub fn max_timestamp(&self) -> Option<i64> {
+        let start = self
+            .time_index
+            .entries
+            .last()
+            .and_then(|e| self.index.lookup(e.offset))
+            .map_or(0, |e| e.position as usize);
+        let mut buf = self.log.get(start..).unwrap_or_default();
+        let mut max = self.time_index.max_timestamp();
+        while let Some((_, max_timestamp, size)) = peek_batch(buf) {
+            max = max.max(Some(max_timestamp));
+            buf = &buf[size..];
+        }
+        max
+    }
+
+    // Skips batches by their headers starting from the byte position, until
+    // the predicate on (last offset, max timestamp) holds
+    fn seek_from<F>(&self, start: usize, f: F) -> RecordBatches<'_>
+    where
+        F: Fn(i64, i64) -> bool,
+    {
+        let mut buf = self.log.get(start..).unwrap_or_default();
+        while let Some((last_offset, max_timestamp, size)) = peek_batch(buf) {
+            if f(last_offset, max_timestamp) {
+                break;
+            }
+            buf = &buf[size..];
+        }
+        RecordBatches::new(buf)
+    }
+}
+
+pub struct PartitionDir {
+    pub path: PathBuf,
+    // Base offsets of segments, ascending
+    pub segments: Vec<i64>,
+    pub leader_epochs: Option<LeaderEpochCheckpoint>,
+}
+
+impl PartitionDir {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut segments = Vec::new();
+        for entry in std::fs::read_dir(&path)? {
+            let name = entry?.file_name();
+            let base_offset = name
+                .to_str()
+                .and_then(|n| n.strip_suffix(LOG_FILE_SUFFIX))
+                .and_then(|n| n.parse().ok());
+            if let Some(base_offset) = base_offset {
+                segments.push(base_offset);
+            }
+        }
+        segments.sort_unstable();
+
+        let leader_epochs = match std::fs::read_to_string(path.join(LEADER_EPOCH_CHECKPOINT_FILE)) {
+            Ok(s) => Some(LeaderEpochCheckpoint::parse(&s)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(PartitionDir {
+            path,
+            segments,
+            leader_epochs,
+        })
+    }
+
+    pub fn segment(&self, base_offset: i64) -> Result<LogSegment> {
+        LogSegment::open(&self.path, base_offset)
+    }
+
+    // Segment which holds the offset, None if the offset precedes the log start
+    pub fn segment_for_offset(&self, offset: i64) -> Result<Option<LogSegment>> {
+        let i = self.segments.partition_point(|base| *base <= offset);
+        match i.checked_sub(1) {
+            Some(i) => self.segment(self.segments[i]).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // First segment with records not older than the timestamp
+    pub fn segment_for_timestamp(&self, timestamp: i64) -> Result<Option<LogSegment>> {
+        for base_offset in &self.segments {
+            let segment = self.segment(*base_offset)?;
+            if segment.max_timestamp().is_some_and(|t| t >= timestamp) {
+                return Ok(Some(segment));
+            }
+        }
+        Ok(None)
+    }
+}
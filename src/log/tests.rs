@@ -0,0 +1,275 @@
+use std::path::Path;
+
+use super::*;
+use crate::error::Error;
+use crate::records::{Record, RecordBatch};
+
+fn index_bytes(entries: &[(i32, u32)], padding: usize) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for (relative_offset, position) in entries {
+        buf.extend_from_slice(&relative_offset.to_be_bytes());
+        buf.extend_from_slice(&position.to_be_bytes());
+    }
+    buf.resize(buf.len() + padding * 8, 0);
+    buf
+}
+
+fn time_index_bytes(entries: &[(i64, i32)], padding: usize) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for (timestamp, relative_offset) in entries {
+        buf.extend_from_slice(&timestamp.to_be_bytes());
+        buf.extend_from_slice(&relative_offset.to_be_bytes());
+    }
+    buf.resize(buf.len() + padding * 12, 0);
+    buf
+}
+
+#[test]
+fn offset_index_from_bytes() {
+    // First entry may well be zeroed, zeroed ones past it are preallocated tail
+    let buf = index_bytes(&[(0, 0), (3, 4096), (7, 8192)], 4);
+    let index = OffsetIndex::from_bytes(100, &buf).unwrap();
+    let entry = |offset, position| OffsetPosition { offset, position };
+    assert_eq!(
+        index.entries,
+        vec![entry(100, 0), entry(103, 4096), entry(107, 8192)]
+    );
+
+    assert_eq!(index.lookup(99), None);
+    assert_eq!(index.lookup(100), Some(entry(100, 0)));
+    assert_eq!(index.lookup(106), Some(entry(103, 4096)));
+    assert_eq!(index.lookup(1000), Some(entry(107, 8192)));
+
+    // Fully preallocated index of an empty segment, trailing partial entry
+    let index = OffsetIndex::from_bytes(100, &index_bytes(&[], 3)).unwrap();
+    assert_eq!(index.entries, vec![entry(100, 0)]);
+    let index = OffsetIndex::from_bytes(100, &buf[..buf.len() - 4]).unwrap();
+    assert_eq!(index.entries.len(), 3);
+    assert!(OffsetIndex::from_bytes(100, &[])
+        .unwrap()
+        .entries
+        .is_empty());
+}
+
+#[test]
+fn time_index_from_bytes() {
+    let buf = time_index_bytes(&[(1000, 0), (1500, 3), (2000, 7)], 2);
+    let index = TimeIndex::from_bytes(100, &buf).unwrap();
+    let entry = |timestamp, offset| TimestampOffset { timestamp, offset };
+    assert_eq!(
+        index.entries,
+        vec![entry(1000, 100), entry(1500, 103), entry(2000, 107)]
+    );
+    assert_eq!(index.max_timestamp(), Some(2000));
+
+    assert_eq!(index.lookup(999), None);
+    assert_eq!(index.lookup(1499), Some(entry(1000, 100)));
+    assert_eq!(index.lookup(1500), Some(entry(1500, 103)));
+
+    let index = TimeIndex::from_bytes(100, &buf[..buf.len() - 1]).unwrap();
+    assert_eq!(index.entries.len(), 3);
+    let index = TimeIndex::from_bytes(100, &[]).unwrap();
+    assert_eq!(index.max_timestamp(), None);
+}
+
+#[test]
+fn leader_epoch_checkpoint_round_trip() {
+    let s = "0\n3\n0 0\n3 1500\n4 1700\n";
+    let checkpoint = LeaderEpochCheckpoint::parse(s).unwrap();
+    assert_eq!(
+        checkpoint.entries,
+        vec![
+            EpochEntry {
+                epoch: 0,
+                start_offset: 0
+            },
+            EpochEntry {
+                epoch: 3,
+                start_offset: 1500
+            },
+            EpochEntry {
+                epoch: 4,
+                start_offset: 1700
+            },
+        ]
+    );
+    assert_eq!(checkpoint.to_string(), s);
+    assert_eq!(checkpoint.epoch_for_offset(-1), None);
+    assert_eq!(checkpoint.epoch_for_offset(1499), Some(0));
+    assert_eq!(checkpoint.epoch_for_offset(1700), Some(4));
+
+    let empty = LeaderEpochCheckpoint::parse("0\n0\n").unwrap();
+    assert!(empty.entries.is_empty());
+    assert_eq!(empty.to_string(), "0\n0\n");
+
+    for malformed in ["", "1\n0\n", "0\n2\n0 0\n", "0\n1\n0\n", "0\n1\n0 x\n"] {
+        assert!(
+            matches!(
+                LeaderEpochCheckpoint::parse(malformed),
+                Err(Error::MalformedCheckpoint)
+            ),
+            "{:?}",
+            malformed
+        );
+    }
+}
+
+// Batch of `count` records at `base_offset`, 10ms apart starting at `timestamp`
+fn batch(base_offset: i64, count: i32, timestamp: i64) -> RecordBatch {
+    RecordBatch {
+        base_offset,
+        partition_leader_epoch: 0,
+        attributes: 0,
+        last_offset_delta: count - 1,
+        base_timestamp: timestamp,
+        max_timestamp: timestamp + (count - 1) as i64 * 10,
+        producer_id: -1,
+        producer_epoch: -1,
+        base_sequence: -1,
+        records: (0..count)
+            .map(|i| Record {
+                attributes: 0,
+                timestamp_delta: i as i64 * 10,
+                offset_delta: i,
+                key: None,
+                value: Some(b"v".to_vec()),
+                headers: Vec::new(),
+            })
+            .collect(),
+    }
+}
+
+// Gzip flag set in attributes (byte 22) and CRC (bytes 17..21) updated
+fn compressed(b: &RecordBatch) -> Vec<u8> {
+    let mut buf = b.to_bytes().unwrap();
+    buf[22] |= 1;
+    let crc = crate::records::crc32c::crc32c(&buf[21..]);
+    buf[17..21].copy_from_slice(&crc.to_be_bytes());
+    buf
+}
+
+fn write_file(dir: &Path, base_offset: i64, suffix: &str, buf: &[u8]) {
+    std::fs::write(dir.join(segment_file_name(base_offset, suffix)), buf).unwrap();
+}
+
+// Segment 0 holds offsets 0..8 in batches starting at 0, 3, 5 (compressed)
+// and 6, indexed at 3 and 6. Segment 8 has the log file only.
+fn write_partition(dir: &Path) {
+    let mut log = Vec::new();
+    let mut positions = Vec::new();
+    for (b, compress) in [
+        (batch(0, 3, 1000), false),
+        (batch(3, 2, 2000), false),
+        (batch(5, 1, 3000), true),
+        (batch(6, 2, 4000), false),
+    ] {
+        positions.push(log.len() as u32);
+        if compress {
+            log.extend_from_slice(&compressed(&b));
+        } else {
+            b.write(&mut log).unwrap();
+        }
+    }
+    write_file(dir, 0, LOG_FILE_SUFFIX, &log);
+    write_file(
+        dir,
+        0,
+        INDEX_FILE_SUFFIX,
+        &index_bytes(&[(3, positions[1]), (6, positions[3])], 10),
+    );
+    write_file(
+        dir,
+        0,
+        TIME_INDEX_FILE_SUFFIX,
+        &time_index_bytes(&[(2010, 5), (4010, 8)], 10),
+    );
+
+    write_file(
+        dir,
+        8,
+        LOG_FILE_SUFFIX,
+        &batch(8, 1, 5000).to_bytes().unwrap(),
+    );
+    std::fs::write(dir.join(LEADER_EPOCH_CHECKPOINT_FILE), "0\n1\n0 0\n").unwrap();
+}
+
+fn base_offsets(batches: RecordBatches) -> Vec<Option<i64>> {
+    batches.map(|b| b.ok().map(|b| b.base_offset)).collect()
+}
+
+#[test]
+fn partition_dir() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_partition(tmp.path());
+    std::fs::write(tmp.path().join("partition.metadata"), "").unwrap();
+
+    let dir = PartitionDir::open(tmp.path()).unwrap();
+    assert_eq!(dir.segments, vec![0, 8]);
+    assert_eq!(dir.leader_epochs.as_ref().unwrap().entries.len(), 1);
+
+    let segment = dir.segment(0).unwrap();
+    assert_eq!(segment.index.entries.len(), 2);
+    // Compressed batch is reported and stepped over
+    assert_eq!(
+        base_offsets(segment.batches()),
+        vec![Some(0), Some(3), None, Some(6)]
+    );
+
+    let segment = dir.segment(8).unwrap();
+    assert!(segment.index.entries.is_empty());
+    assert_eq!(segment.max_timestamp(), Some(5000));
+}
+
+#[test]
+fn seek_offset() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_partition(tmp.path());
+    let dir = PartitionDir::open(tmp.path()).unwrap();
+
+    let segment = dir.segment_for_offset(4).unwrap().unwrap();
+    assert_eq!(segment.base_offset, 0);
+    // Offset within a batch starts from that batch
+    assert_eq!(
+        base_offsets(segment.seek_offset(4)),
+        vec![Some(3), None, Some(6)]
+    );
+    assert_eq!(base_offsets(segment.seek_offset(0)).len(), 4);
+    assert_eq!(base_offsets(segment.seek_offset(5)), vec![None, Some(6)]);
+    assert_eq!(base_offsets(segment.seek_offset(7)), vec![Some(6)]);
+    assert!(base_offsets(segment.seek_offset(8)).is_empty());
+
+    assert_eq!(dir.segment_for_offset(8).unwrap().unwrap().base_offset, 8);
+    assert_eq!(dir.segment_for_offset(100).unwrap().unwrap().base_offset, 8);
+    assert!(dir.segment_for_offset(-1).unwrap().is_none());
+}
+
+#[test]
+fn seek_timestamp() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_partition(tmp.path());
+    let dir = PartitionDir::open(tmp.path()).unwrap();
+
+    let segment = dir.segment_for_timestamp(2015).unwrap().unwrap();
+    assert_eq!(segment.base_offset, 0);
+    assert_eq!(segment.max_timestamp(), Some(4010));
+    // First batch with max timestamp not less than the target one
+    assert_eq!(
+        base_offsets(segment.seek_timestamp(1500)),
+        vec![Some(3), None, Some(6)]
+    );
+    assert_eq!(
+        base_offsets(segment.seek_timestamp(2015)),
+        vec![None, Some(6)]
+    );
+    assert_eq!(base_offsets(segment.seek_timestamp(0)).len(), 4);
+    assert!(base_offsets(segment.seek_timestamp(4011)).is_empty());
+
+    assert_eq!(
+        dir.segment_for_timestamp(4011)
+            .unwrap()
+            .unwrap()
+            .base_offset,
+        8
+    );
+    assert!(dir.segment_for_timestamp(5001).unwrap().is_none());
+}
@@ -0,0 +1,114 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, ProducerId};
+
+pub struct AllocateProducerIdsRequest {
+    // The ID of the requesting broker
+    pub broker_id: BrokerId,
+    // The epoch of the requesting broker, default=-1
+    pub broker_epoch: i64,
+}
+
+impl Default for AllocateProducerIdsRequest {
+    fn default() -> Self {
+        AllocateProducerIdsRequest {
+            broker_id: 0,
+            broker_epoch: -1,
+        }
+    }
+}
+
+builder! {
+    AllocateProducerIdsRequest => AllocateProducerIdsRequestBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for AllocateProducerIdsRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AllocateProducerIdsRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = AllocateProducerIdsRequest {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AllocateProducerIdsRequest {
+    const API_KEY: i16 = 67;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for AllocateProducerIdsRequest {
+    type Response = AllocateProducerIdsResponse;
+}
+
+#[derive(Default)]
+pub struct AllocateProducerIdsResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    // The first producer ID in this range, inclusive
+    pub producer_id_start: ProducerId,
+    // The number of producer IDs in this range
+    pub producer_id_len: i32,
+}
+
+builder! {
+    AllocateProducerIdsResponse => AllocateProducerIdsResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        producer_id_start: ProducerId,
+        producer_id_len: i32,
+    }
+}
+
+impl KafkaProtoEncodable for AllocateProducerIdsResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int64(self.producer_id_start)?;
+        s.emit_int32(self.producer_id_len)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AllocateProducerIdsResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = AllocateProducerIdsResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            producer_id_start: d.read_field("producer_id_start", |d| d.read_int64())?,
+            producer_id_len: d.read_field("producer_id_len", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AllocateProducerIdsResponse {
+    const API_KEY: i16 = 67;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,206 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::messages::ClientQuotaEntity;
+use crate::{KafkaMessage, KafkaRequest};
+
+#[derive(Default)]
+pub struct AlterClientQuotasRequest {
+    // The quota configuration entries to alter.
+    pub entries: Vec<AlterClientQuotasRequestEntry>,
+    // Whether the alteration should be validated, but not performed.
+    pub validate_only: bool,
+}
+
+builder! {
+    AlterClientQuotasRequest => AlterClientQuotasRequestBuilder {
+        entries: Vec<AlterClientQuotasRequestEntry>,
+        validate_only: bool,
+    }
+}
+
+impl KafkaProtoEncodable for AlterClientQuotasRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.entries.iter())?;
+        s.emit_bool(self.validate_only)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterClientQuotasRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterClientQuotasRequest {
+            entries: d.read_field("entries", |d| d.read_array(ver))?,
+            validate_only: d.read_field("validate_only", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterClientQuotasRequestEntry {
+    // The quota entity to alter.
+    pub entity: Vec<ClientQuotaEntity>,
+    // An individual quota configuration entry to alter.
+    pub ops: Vec<AlterClientQuotasRequestOp>,
+}
+
+builder! {
+    AlterClientQuotasRequestEntry => AlterClientQuotasRequestEntryBuilder {
+        entity: Vec<ClientQuotaEntity>,
+        ops: Vec<AlterClientQuotasRequestOp>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterClientQuotasRequestEntry {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.entity.iter())?;
+        s.emit_array(ver, self.ops.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterClientQuotasRequestEntry {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterClientQuotasRequestEntry {
+            entity: d.read_field("entity", |d| d.read_array(ver))?,
+            ops: d.read_field("ops", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterClientQuotasRequestOp {
+    // The quota configuration key.
+    pub key: String,
+    // The value to set, otherwise ignored if the value is to be removed.
+    pub value: f64,
+    // Whether the quota configuration value should be removed, otherwise set.
+    pub remove: bool,
+}
+
+builder! {
+    AlterClientQuotasRequestOp => AlterClientQuotasRequestOpBuilder {
+        key: String,
+        value: f64,
+        remove: bool,
+    }
+}
+
+impl KafkaProtoEncodable for AlterClientQuotasRequestOp {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.key)?;
+        s.emit_float64(self.value)?;
+        s.emit_bool(self.remove)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterClientQuotasRequestOp {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = AlterClientQuotasRequestOp {
+            key: d.read_field("key", |d| d.read_string())?.to_string(),
+            value: d.read_field("value", |d| d.read_float64())?,
+            remove: d.read_field("remove", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AlterClientQuotasRequest {
+    const API_KEY: i16 = 49;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 1;
+}
+
+impl KafkaRequest for AlterClientQuotasRequest {
+    type Response = AlterClientQuotasResponse;
+}
+
+#[derive(Default)]
+pub struct AlterClientQuotasResponse {
+    pub throttle_time_ms: i32,
+    pub entries: Vec<AlterClientQuotasResponseEntry>,
+}
+
+builder! {
+    AlterClientQuotasResponse => AlterClientQuotasResponseBuilder {
+        throttle_time_ms: i32,
+        entries: Vec<AlterClientQuotasResponseEntry>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterClientQuotasResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_array(ver, self.entries.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterClientQuotasResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterClientQuotasResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            entries: d.read_field("entries", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterClientQuotasResponseEntry {
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    // The quota entity this result is for.
+    pub entity: Vec<ClientQuotaEntity>,
+}
+
+builder! {
+    AlterClientQuotasResponseEntry => AlterClientQuotasResponseEntryBuilder {
+        error_code: i16,
+        error_message: Option<String>,
+        entity: Vec<ClientQuotaEntity>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterClientQuotasResponseEntry {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_array(ver, self.entity.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterClientQuotasResponseEntry {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterClientQuotasResponseEntry {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+            entity: d.read_field("entity", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AlterClientQuotasResponse {
+    const API_KEY: i16 = 49;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 1;
+}
@@ -0,0 +1,403 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicId, TopicName};
+
+// AlterPartition was called AlterIsr before version 2
+pub struct AlterPartitionRequest {
+    // The ID of the requesting broker
+    pub broker_id: BrokerId,
+    // The epoch of the requesting broker, default=-1
+    pub broker_epoch: i64,
+    pub topics: Vec<AlterPartitionRequestTopic>,
+}
+
+impl Default for AlterPartitionRequest {
+    fn default() -> Self {
+        AlterPartitionRequest {
+            broker_id: 0,
+            broker_epoch: -1,
+            topics: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    AlterPartitionRequest => AlterPartitionRequestBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+        topics: Vec<AlterPartitionRequestTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterPartitionRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterPartitionRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterPartitionRequest {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterPartitionRequestTopic {
+    // versions: 0-1, ignorable
+    pub topic_name: TopicName,
+    // versions: 2+, ignorable
+    pub topic_id: TopicId,
+    pub partitions: Vec<AlterPartitionRequestPartition>,
+}
+
+builder! {
+    AlterPartitionRequestTopic => AlterPartitionRequestTopicBuilder {
+        topic_name: TopicName,
+        topic_id: TopicId,
+        partitions: Vec<AlterPartitionRequestPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterPartitionRequestTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 1 {
+            s.emit_string(&self.topic_name)?;
+        }
+        if ver >= 2 {
+            s.emit_uuid(self.topic_id.0)?;
+        }
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterPartitionRequestTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterPartitionRequestTopic {
+            topic_name: if ver <= 1 {
+                d.read_field("topic_name", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            topic_id: if ver >= 2 {
+                TopicId(d.read_field("topic_id", |d| d.read_uuid())?)
+            } else {
+                TopicId::ZERO
+            },
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterPartitionRequestPartition {
+    pub partition_index: i32,
+    // The leader epoch of this partition
+    pub leader_epoch: i32,
+
+    // versions: 0-2
+    // The ISR for this partition. Deprecated since version 3.
+    pub new_isr: Vec<BrokerId>,
+
+    // versions: 3+
+    pub new_isr_with_epochs: Vec<AlterPartitionRequestBrokerState>,
+
+    // versions: 1+, default=0
+    // 1 if the partition is recovering from an unclean leader election; 0 otherwise.
+    pub leader_recovery_state: i8,
+
+    // The expected epoch of the partition which is being updated.
+    pub partition_epoch: i32,
+}
+
+builder! {
+    AlterPartitionRequestPartition => AlterPartitionRequestPartitionBuilder {
+        partition_index: i32,
+        leader_epoch: i32,
+        new_isr: Vec<BrokerId>,
+        new_isr_with_epochs: Vec<AlterPartitionRequestBrokerState>,
+        leader_recovery_state: i8,
+        partition_epoch: i32,
+    }
+}
+
+impl KafkaProtoEncodable for AlterPartitionRequestPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int32(self.leader_epoch)?;
+        if ver <= 2 {
+            s.emit_array(ver, self.new_isr.iter())?;
+        } else {
+            s.check_absent_field("new_isr", ver, self.new_isr.is_empty())?;
+        }
+        if ver >= 3 {
+            s.emit_array(ver, self.new_isr_with_epochs.iter())?;
+        } else {
+            s.check_absent_field(
+                "new_isr_with_epochs",
+                ver,
+                self.new_isr_with_epochs.is_empty(),
+            )?;
+        }
+        if ver >= 1 {
+            s.emit_int8(self.leader_recovery_state)?;
+        } else {
+            s.check_absent_field(
+                "leader_recovery_state",
+                ver,
+                self.leader_recovery_state == 0,
+            )?;
+        }
+        s.emit_int32(self.partition_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterPartitionRequestPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterPartitionRequestPartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            new_isr: if ver <= 2 {
+                d.read_field("new_isr", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            new_isr_with_epochs: if ver >= 3 {
+                d.read_field("new_isr_with_epochs", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            leader_recovery_state: if ver >= 1 {
+                d.read_field("leader_recovery_state", |d| d.read_int8())?
+            } else {
+                0
+            },
+            partition_epoch: d.read_field("partition_epoch", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterPartitionRequestBrokerState {
+    pub broker_id: BrokerId,
+    // The epoch of the broker. It will be -1 if the epoch check is not supported.
+    pub broker_epoch: i64,
+}
+
+builder! {
+    AlterPartitionRequestBrokerState => AlterPartitionRequestBrokerStateBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for AlterPartitionRequestBrokerState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterPartitionRequestBrokerState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = AlterPartitionRequestBrokerState {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AlterPartitionRequest {
+    const API_KEY: i16 = 56;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 3;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for AlterPartitionRequest {
+    type Response = AlterPartitionResponse;
+}
+
+#[derive(Default)]
+pub struct AlterPartitionResponse {
+    pub throttle_time_ms: i32,
+    // The top level response error code
+    pub error_code: i16,
+    pub topics: Vec<AlterPartitionResponseTopic>,
+}
+
+builder! {
+    AlterPartitionResponse => AlterPartitionResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        topics: Vec<AlterPartitionResponseTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterPartitionResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterPartitionResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterPartitionResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterPartitionResponseTopic {
+    // versions: 0-1, ignorable
+    pub topic_name: TopicName,
+    // versions: 2+, ignorable
+    pub topic_id: TopicId,
+    pub partitions: Vec<AlterPartitionResponsePartition>,
+}
+
+builder! {
+    AlterPartitionResponseTopic => AlterPartitionResponseTopicBuilder {
+        topic_name: TopicName,
+        topic_id: TopicId,
+        partitions: Vec<AlterPartitionResponsePartition>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterPartitionResponseTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 1 {
+            s.emit_string(&self.topic_name)?;
+        }
+        if ver >= 2 {
+            s.emit_uuid(self.topic_id.0)?;
+        }
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterPartitionResponseTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterPartitionResponseTopic {
+            topic_name: if ver <= 1 {
+                d.read_field("topic_name", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            topic_id: if ver >= 2 {
+                TopicId(d.read_field("topic_id", |d| d.read_uuid())?)
+            } else {
+                TopicId::ZERO
+            },
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterPartitionResponsePartition {
+    pub partition_index: i32,
+    pub error_code: i16,
+    // The broker ID of the leader.
+    pub leader_id: BrokerId,
+    // The leader epoch.
+    pub leader_epoch: i32,
+    // The in-sync replica IDs.
+    pub isr: Vec<BrokerId>,
+
+    // versions: 1+, ignorable, default=0
+    // 1 if the partition is recovering from an unclean leader election; 0 otherwise.
+    pub leader_recovery_state: i8,
+
+    // The current epoch for the partition for KRaft controllers. The current ZK
+    // version for the legacy controllers.
+    pub partition_epoch: i32,
+}
+
+builder! {
+    AlterPartitionResponsePartition => AlterPartitionResponsePartitionBuilder {
+        partition_index: i32,
+        error_code: i16,
+        leader_id: BrokerId,
+        leader_epoch: i32,
+        isr: Vec<BrokerId>,
+        leader_recovery_state: i8,
+        partition_epoch: i32,
+    }
+}
+
+impl KafkaProtoEncodable for AlterPartitionResponsePartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_array(ver, self.isr.iter())?;
+        if ver >= 1 {
+            s.emit_int8(self.leader_recovery_state)?;
+        }
+        s.emit_int32(self.partition_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterPartitionResponsePartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterPartitionResponsePartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            isr: d.read_field("isr", |d| d.read_array(ver))?,
+            leader_recovery_state: if ver >= 1 {
+                d.read_field("leader_recovery_state", |d| d.read_int8())?
+            } else {
+                0
+            },
+            partition_epoch: d.read_field("partition_epoch", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AlterPartitionResponse {
+    const API_KEY: i16 = 56;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 3;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
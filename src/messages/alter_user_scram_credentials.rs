@@ -0,0 +1,220 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{Bytes, KafkaMessage, KafkaRequest};
+
+#[derive(Default)]
+pub struct AlterUserScramCredentialsRequest {
+    // The SCRAM credentials to remove.
+    pub deletions: Vec<ScramCredentialDeletion>,
+    // The SCRAM credentials to update/insert.
+    pub upsertions: Vec<ScramCredentialUpsertion>,
+}
+
+builder! {
+    AlterUserScramCredentialsRequest => AlterUserScramCredentialsRequestBuilder {
+        deletions: Vec<ScramCredentialDeletion>,
+        upsertions: Vec<ScramCredentialUpsertion>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterUserScramCredentialsRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.deletions.iter())?;
+        s.emit_array(ver, self.upsertions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterUserScramCredentialsRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterUserScramCredentialsRequest {
+            deletions: d.read_field("deletions", |d| d.read_array(ver))?,
+            upsertions: d.read_field("upsertions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ScramCredentialDeletion {
+    pub name: String,
+    // The SCRAM mechanism, one of SCRAM_* constants.
+    pub mechanism: i8,
+}
+
+builder! {
+    ScramCredentialDeletion => ScramCredentialDeletionBuilder {
+        name: String,
+        mechanism: i8,
+    }
+}
+
+impl KafkaProtoEncodable for ScramCredentialDeletion {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_int8(self.mechanism)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ScramCredentialDeletion {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = ScramCredentialDeletion {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            mechanism: d.read_field("mechanism", |d| d.read_int8())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ScramCredentialUpsertion {
+    pub name: String,
+    // The SCRAM mechanism, one of SCRAM_* constants.
+    pub mechanism: i8,
+    // The number of iterations.
+    pub iterations: i32,
+    // A random salt generated by the client.
+    pub salt: Bytes,
+    // The salted password.
+    pub salted_password: Bytes,
+}
+
+builder! {
+    ScramCredentialUpsertion => ScramCredentialUpsertionBuilder {
+        name: String,
+        mechanism: i8,
+        iterations: i32,
+        salt: Bytes,
+        salted_password: Bytes,
+    }
+}
+
+impl KafkaProtoEncodable for ScramCredentialUpsertion {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_int8(self.mechanism)?;
+        s.emit_int32(self.iterations)?;
+        s.emit_bytes(&self.salt)?;
+        s.emit_bytes(&self.salted_password)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ScramCredentialUpsertion {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = ScramCredentialUpsertion {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            mechanism: d.read_field("mechanism", |d| d.read_int8())?,
+            iterations: d.read_field("iterations", |d| d.read_int32())?,
+            salt: d.read_field("salt", |d| d.read_bytes())?.to_vec(),
+            salted_password: d
+                .read_field("salted_password", |d| d.read_bytes())?
+                .to_vec(),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AlterUserScramCredentialsRequest {
+    const API_KEY: i16 = 51;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for AlterUserScramCredentialsRequest {
+    type Response = AlterUserScramCredentialsResponse;
+}
+
+#[derive(Default)]
+pub struct AlterUserScramCredentialsResponse {
+    pub throttle_time_ms: i32,
+    pub results: Vec<AlterUserScramCredentialsResult>,
+}
+
+builder! {
+    AlterUserScramCredentialsResponse => AlterUserScramCredentialsResponseBuilder {
+        throttle_time_ms: i32,
+        results: Vec<AlterUserScramCredentialsResult>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterUserScramCredentialsResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_array(ver, self.results.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterUserScramCredentialsResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = AlterUserScramCredentialsResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            results: d.read_field("results", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct AlterUserScramCredentialsResult {
+    pub user: String,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+}
+
+builder! {
+    AlterUserScramCredentialsResult => AlterUserScramCredentialsResultBuilder {
+        user: String,
+        error_code: i16,
+        error_message: Option<String>,
+    }
+}
+
+impl KafkaProtoEncodable for AlterUserScramCredentialsResult {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.user)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AlterUserScramCredentialsResult {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = AlterUserScramCredentialsResult {
+            user: d.read_field("user", |d| d.read_string())?.to_string(),
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AlterUserScramCredentialsResponse {
+    const API_KEY: i16 = 51;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
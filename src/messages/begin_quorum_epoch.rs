@@ -0,0 +1,241 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicName};
+
+#[derive(Default)]
+pub struct BeginQuorumEpochRequest {
+    pub cluster_id: Option<String>,
+    pub topics: Vec<BeginQuorumEpochRequestTopic>,
+}
+
+builder! {
+    BeginQuorumEpochRequest => BeginQuorumEpochRequestBuilder {
+        cluster_id: Option<String>,
+        topics: Vec<BeginQuorumEpochRequestTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for BeginQuorumEpochRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_nullable_string(self.cluster_id.as_deref())?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BeginQuorumEpochRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = BeginQuorumEpochRequest {
+            cluster_id: d
+                .read_field("cluster_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct BeginQuorumEpochRequestTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<BeginQuorumEpochRequestPartition>,
+}
+
+builder! {
+    BeginQuorumEpochRequestTopic => BeginQuorumEpochRequestTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<BeginQuorumEpochRequestPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for BeginQuorumEpochRequestTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BeginQuorumEpochRequestTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = BeginQuorumEpochRequestTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct BeginQuorumEpochRequestPartition {
+    pub partition_index: i32,
+    // The ID of the newly elected leader.
+    pub leader_id: BrokerId,
+    // The epoch of the newly elected leader.
+    pub leader_epoch: i32,
+}
+
+builder! {
+    BeginQuorumEpochRequestPartition => BeginQuorumEpochRequestPartitionBuilder {
+        partition_index: i32,
+        leader_id: BrokerId,
+        leader_epoch: i32,
+    }
+}
+
+impl KafkaProtoEncodable for BeginQuorumEpochRequestPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BeginQuorumEpochRequestPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = BeginQuorumEpochRequestPartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for BeginQuorumEpochRequest {
+    const API_KEY: i16 = 53;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+}
+
+impl KafkaRequest for BeginQuorumEpochRequest {
+    type Response = BeginQuorumEpochResponse;
+}
+
+#[derive(Default)]
+pub struct BeginQuorumEpochResponse {
+    // The top level error code.
+    pub error_code: i16,
+    pub topics: Vec<BeginQuorumEpochResponseTopic>,
+}
+
+builder! {
+    BeginQuorumEpochResponse => BeginQuorumEpochResponseBuilder {
+        error_code: i16,
+        topics: Vec<BeginQuorumEpochResponseTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for BeginQuorumEpochResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BeginQuorumEpochResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = BeginQuorumEpochResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct BeginQuorumEpochResponseTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<BeginQuorumEpochResponsePartition>,
+}
+
+builder! {
+    BeginQuorumEpochResponseTopic => BeginQuorumEpochResponseTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<BeginQuorumEpochResponsePartition>,
+    }
+}
+
+impl KafkaProtoEncodable for BeginQuorumEpochResponseTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BeginQuorumEpochResponseTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = BeginQuorumEpochResponseTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct BeginQuorumEpochResponsePartition {
+    pub partition_index: i32,
+    pub error_code: i16,
+    // The ID of the current leader or -1 if the leader is unknown.
+    pub leader_id: BrokerId,
+    // The latest known leader epoch.
+    pub leader_epoch: i32,
+}
+
+builder! {
+    BeginQuorumEpochResponsePartition => BeginQuorumEpochResponsePartitionBuilder {
+        partition_index: i32,
+        error_code: i16,
+        leader_id: BrokerId,
+        leader_epoch: i32,
+    }
+}
+
+impl KafkaProtoEncodable for BeginQuorumEpochResponsePartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BeginQuorumEpochResponsePartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = BeginQuorumEpochResponsePartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for BeginQuorumEpochResponse {
+    const API_KEY: i16 = 53;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+}
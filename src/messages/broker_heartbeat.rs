@@ -0,0 +1,167 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest};
+
+pub struct BrokerHeartbeatRequest {
+    pub broker_id: BrokerId,
+    // The broker epoch, default=-1
+    pub broker_epoch: i64,
+    // The highest metadata offset which the broker has reached.
+    pub current_metadata_offset: i64,
+    // True if the broker wants to be fenced, false otherwise.
+    pub want_fence: bool,
+    // True if the broker wants to be shut down, false otherwise.
+    pub want_shut_down: bool,
+
+    // versions: 1+, tag: 0
+    // Log directories that failed and went offline.
+    pub offline_log_dirs: Vec<uuid::Uuid>,
+}
+
+impl Default for BrokerHeartbeatRequest {
+    fn default() -> Self {
+        BrokerHeartbeatRequest {
+            broker_id: 0,
+            broker_epoch: -1,
+            current_metadata_offset: 0,
+            want_fence: false,
+            want_shut_down: false,
+            offline_log_dirs: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    BrokerHeartbeatRequest => BrokerHeartbeatRequestBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+        current_metadata_offset: i64,
+        want_fence: bool,
+        want_shut_down: bool,
+        offline_log_dirs: Vec<uuid::Uuid>,
+    }
+}
+
+impl KafkaProtoEncodable for BrokerHeartbeatRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_int64(self.current_metadata_offset)?;
+        s.emit_bool(self.want_fence)?;
+        s.emit_bool(self.want_shut_down)?;
+
+        if ver < 1 {
+            s.check_absent_field("offline_log_dirs", ver, self.offline_log_dirs.is_empty())?;
+        }
+
+        let emit_offline_log_dirs = !self.offline_log_dirs.is_empty();
+        let mut r = s.emit_tagged_fields_hdr(emit_offline_log_dirs as usize)?;
+        if emit_offline_log_dirs {
+            r = s.emit_tagged_field(0, ver, &self.offline_log_dirs)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for BrokerHeartbeatRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = BrokerHeartbeatRequest {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+            current_metadata_offset: d.read_field("current_metadata_offset", |d| d.read_int64())?,
+            want_fence: d.read_field("want_fence", |d| d.read_bool())?,
+            want_shut_down: d.read_field("want_shut_down", |d| d.read_bool())?,
+            offline_log_dirs: Vec::new(),
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 if ver >= 1 => {
+                r.offline_log_dirs = d.read_field("offline_log_dirs", |d| d.read_array(ver))?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for BrokerHeartbeatRequest {
+    const API_KEY: i16 = 63;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for BrokerHeartbeatRequest {
+    type Response = BrokerHeartbeatResponse;
+}
+
+pub struct BrokerHeartbeatResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    // True if the broker has approximately caught up with the latest metadata, default=false
+    pub is_caught_up: bool,
+    // True if the broker is fenced, default=true
+    pub is_fenced: bool,
+    // True if the broker should proceed with its shutdown.
+    pub should_shut_down: bool,
+}
+
+impl Default for BrokerHeartbeatResponse {
+    fn default() -> Self {
+        BrokerHeartbeatResponse {
+            throttle_time_ms: 0,
+            error_code: 0,
+            is_caught_up: false,
+            is_fenced: true,
+            should_shut_down: false,
+        }
+    }
+}
+
+builder! {
+    BrokerHeartbeatResponse => BrokerHeartbeatResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        is_caught_up: bool,
+        is_fenced: bool,
+        should_shut_down: bool,
+    }
+}
+
+impl KafkaProtoEncodable for BrokerHeartbeatResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_bool(self.is_caught_up)?;
+        s.emit_bool(self.is_fenced)?;
+        s.emit_bool(self.should_shut_down)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BrokerHeartbeatResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = BrokerHeartbeatResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            is_caught_up: d.read_field("is_caught_up", |d| d.read_bool())?,
+            is_fenced: d.read_field("is_fenced", |d| d.read_bool())?,
+            should_shut_down: d.read_field("should_shut_down", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for BrokerHeartbeatResponse {
+    const API_KEY: i16 = 63;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,264 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+use linked_hash_map::LinkedHashMap;
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest};
+
+pub struct BrokerRegistrationRequest {
+    pub broker_id: BrokerId,
+    pub cluster_id: String,
+    // The incarnation id of the broker process.
+    pub incarnation_id: uuid::Uuid,
+
+    // key: name
+    pub listeners: LinkedHashMap<String, BrokerRegistrationListener>,
+    // key: name
+    pub features: LinkedHashMap<String, BrokerRegistrationFeature>,
+
+    pub rack: Option<String>,
+
+    // versions: 1+, default=false
+    // If the required configurations for ZK migration are present, this value is set to true.
+    pub is_migrating_zk_broker: bool,
+
+    // versions: 2+
+    // Log directories configured in this broker which are available.
+    pub log_dirs: Vec<uuid::Uuid>,
+
+    // versions: 3+, ignorable, default=-1
+    // The epoch before a clean shutdown.
+    pub previous_broker_epoch: i64,
+}
+
+impl Default for BrokerRegistrationRequest {
+    fn default() -> Self {
+        BrokerRegistrationRequest {
+            broker_id: 0,
+            cluster_id: String::new(),
+            incarnation_id: uuid::Uuid::nil(),
+            listeners: LinkedHashMap::new(),
+            features: LinkedHashMap::new(),
+            rack: None,
+            is_migrating_zk_broker: false,
+            log_dirs: Vec::new(),
+            previous_broker_epoch: -1,
+        }
+    }
+}
+
+builder! {
+    BrokerRegistrationRequest => BrokerRegistrationRequestBuilder {
+        broker_id: BrokerId,
+        cluster_id: String,
+        incarnation_id: uuid::Uuid,
+        listeners: LinkedHashMap<String, BrokerRegistrationListener>,
+        features: LinkedHashMap<String, BrokerRegistrationFeature>,
+        rack: Option<String>,
+        is_migrating_zk_broker: bool,
+        log_dirs: Vec<uuid::Uuid>,
+        previous_broker_epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for BrokerRegistrationRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_string(&self.cluster_id)?;
+        s.emit_uuid(self.incarnation_id)?;
+        s.emit_array(ver, self.listeners.values())?;
+        s.emit_array(ver, self.features.values())?;
+        s.emit_nullable_string(self.rack.as_deref())?;
+        if ver >= 1 {
+            s.emit_bool(self.is_migrating_zk_broker)?;
+        } else {
+            s.check_absent_field("is_migrating_zk_broker", ver, !self.is_migrating_zk_broker)?;
+        }
+        if ver >= 2 {
+            s.emit_array(ver, self.log_dirs.iter())?;
+        } else {
+            s.check_absent_field("log_dirs", ver, self.log_dirs.is_empty())?;
+        }
+        if ver >= 3 {
+            s.emit_int64(self.previous_broker_epoch)?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BrokerRegistrationRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = BrokerRegistrationRequest {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            cluster_id: d.read_field("cluster_id", |d| d.read_string())?.to_string(),
+            incarnation_id: d.read_field("incarnation_id", |d| d.read_uuid())?,
+            listeners: d.read_field("listeners", |d| {
+                d.read_map(ver, |l: &BrokerRegistrationListener| l.name.clone())
+            })?,
+            features: d.read_field("features", |d| {
+                d.read_map(ver, |f: &BrokerRegistrationFeature| f.name.clone())
+            })?,
+            rack: d
+                .read_field("rack", |d| d.read_nullable_string())?
+                .map(Into::into),
+            is_migrating_zk_broker: if ver >= 1 {
+                d.read_field("is_migrating_zk_broker", |d| d.read_bool())?
+            } else {
+                false
+            },
+            log_dirs: if ver >= 2 {
+                d.read_field("log_dirs", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            previous_broker_epoch: if ver >= 3 {
+                d.read_field("previous_broker_epoch", |d| d.read_int64())?
+            } else {
+                -1
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct BrokerRegistrationListener {
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub security_protocol: i16,
+}
+
+builder! {
+    BrokerRegistrationListener => BrokerRegistrationListenerBuilder {
+        name: String,
+        host: String,
+        port: u16,
+        security_protocol: i16,
+    }
+}
+
+impl KafkaProtoEncodable for BrokerRegistrationListener {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_string(&self.host)?;
+        s.emit_uint16(self.port)?;
+        s.emit_int16(self.security_protocol)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BrokerRegistrationListener {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = BrokerRegistrationListener {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            host: d.read_field("host", |d| d.read_string())?.to_string(),
+            port: d.read_field("port", |d| d.read_uint16())?,
+            security_protocol: d.read_field("security_protocol", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct BrokerRegistrationFeature {
+    pub name: String,
+    pub min_supported_version: i16,
+    pub max_supported_version: i16,
+}
+
+builder! {
+    BrokerRegistrationFeature => BrokerRegistrationFeatureBuilder {
+        name: String,
+        min_supported_version: i16,
+        max_supported_version: i16,
+    }
+}
+
+impl KafkaProtoEncodable for BrokerRegistrationFeature {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_int16(self.min_supported_version)?;
+        s.emit_int16(self.max_supported_version)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BrokerRegistrationFeature {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = BrokerRegistrationFeature {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            min_supported_version: d.read_field("min_supported_version", |d| d.read_int16())?,
+            max_supported_version: d.read_field("max_supported_version", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for BrokerRegistrationRequest {
+    const API_KEY: i16 = 62;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 3;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for BrokerRegistrationRequest {
+    type Response = BrokerRegistrationResponse;
+}
+
+#[derive(Default)]
+pub struct BrokerRegistrationResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    // The broker's assigned epoch, or -1 if none was assigned.
+    pub broker_epoch: i64,
+}
+
+builder! {
+    BrokerRegistrationResponse => BrokerRegistrationResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        broker_epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for BrokerRegistrationResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for BrokerRegistrationResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = BrokerRegistrationResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for BrokerRegistrationResponse {
+    const API_KEY: i16 = 62;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 3;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,281 @@
+use crate::de::{deserialize_versioned, KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{serialize_versioned, KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::messages::JoinGroupRequestProtocol;
+use crate::Bytes;
+
+// JoinGroupRequest.protocol_type used by Kafka Connect workers. Metadata of each
+// protocol is a ConnectProtocolWorkerState and SyncGroup assignments are
+// ConnectProtocolAssignment, both prefixed with their version. Version is
+// implied by protocol name.
+pub const CONNECT_PROTOCOL_TYPE: &str = "connect";
+
+// Eager rebalance protocol, version 0
+pub const CONNECT_PROTOCOL_EAGER: &str = "default";
+// Incremental cooperative rebalance protocol, version 1
+pub const CONNECT_PROTOCOL_COMPATIBLE: &str = "compatible";
+// Incremental cooperative rebalance protocol with session keys, version 2
+pub const CONNECT_PROTOCOL_SESSIONED: &str = "sessioned";
+
+// Task id denoting connector instance itself in ConnectProtocolConnectorAssignment
+pub const CONNECTOR_TASK: i32 = -1;
+
+// ConnectProtocolAssignment error codes
+pub const CONNECT_ASSIGNMENT_NO_ERROR: i16 = 0;
+// Leader's config offset is behind offsets of some workers
+pub const CONNECT_ASSIGNMENT_CONFIG_MISMATCH: i16 = 1;
+
+pub fn connect_protocol_version(name: &str) -> Option<i16> {
+    match name {
+        CONNECT_PROTOCOL_EAGER => Some(0),
+        CONNECT_PROTOCOL_COMPATIBLE => Some(1),
+        CONNECT_PROTOCOL_SESSIONED => Some(2),
+        _ => None,
+    }
+}
+
+pub fn connect_protocol_name(ver: i16) -> Option<&'static str> {
+    match ver {
+        0 => Some(CONNECT_PROTOCOL_EAGER),
+        1 => Some(CONNECT_PROTOCOL_COMPATIBLE),
+        2 => Some(CONNECT_PROTOCOL_SESSIONED),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+pub struct ConnectProtocolWorkerState {
+    // REST URL of the worker
+    pub url: String,
+    // Latest offset of the config topic the worker has read up to
+    pub config_offset: i64,
+
+    // versions: 1+
+    // Versioned ConnectProtocolAssignment the worker currently runs, null if none
+    pub allocation: Option<Bytes>,
+}
+
+builder! {
+    ConnectProtocolWorkerState => ConnectProtocolWorkerStateBuilder {
+        url: String,
+        config_offset: i64,
+        allocation: Option<Bytes>,
+    }
+}
+
+impl ConnectProtocolWorkerState {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 2;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+
+    pub fn allocation(&self) -> crate::error::Result<Option<ConnectProtocolAssignment>> {
+        self.allocation
+            .as_deref()
+            .map(ConnectProtocolAssignment::from_bytes)
+            .transpose()
+    }
+}
+
+impl KafkaProtoEncodable for ConnectProtocolWorkerState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.url)?;
+        s.emit_int64(self.config_offset)?;
+        if ver >= 1 {
+            s.emit_nullable_bytes(self.allocation.as_deref())?;
+        } else {
+            s.check_absent_field("allocation", ver, self.allocation.is_none())?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConnectProtocolWorkerState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConnectProtocolWorkerState {
+            url: d.read_field("url", |d| d.read_string())?.to_string(),
+            config_offset: d.read_field("config_offset", |d| d.read_int64())?,
+            allocation: if ver >= 1 {
+                d.read_field("allocation", |d| d.read_nullable_bytes())?
+                    .map(<[u8]>::to_vec)
+            } else {
+                None
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConnectProtocolAssignment {
+    pub error: i16,
+    // Member id of the leader
+    pub leader: String,
+    // REST URL of the leader
+    pub leader_url: String,
+    pub config_offset: i64,
+
+    // nullableVersions: 1+
+    // Connectors and tasks assigned to the worker
+    pub assignment: Option<Vec<ConnectProtocolConnectorAssignment>>,
+
+    // versions: 1+
+    // nullableVersions: 1+
+    // Connectors and tasks the worker has to stop
+    pub revoked: Option<Vec<ConnectProtocolConnectorAssignment>>,
+
+    // versions: 1+, default=0
+    // Delay in milliseconds before the next rebalance, when the leader awaits
+    // departed workers to return
+    pub scheduled_delay: i32,
+}
+
+builder! {
+    ConnectProtocolAssignment => ConnectProtocolAssignmentBuilder {
+        error: i16,
+        leader: String,
+        leader_url: String,
+        config_offset: i64,
+        assignment: Option<Vec<ConnectProtocolConnectorAssignment>>,
+        revoked: Option<Vec<ConnectProtocolConnectorAssignment>>,
+        scheduled_delay: i32,
+    }
+}
+
+impl ConnectProtocolAssignment {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 2;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for ConnectProtocolAssignment {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error)?;
+        s.emit_string(&self.leader)?;
+        s.emit_string(&self.leader_url)?;
+        s.emit_int64(self.config_offset)?;
+        if ver >= 1 {
+            s.emit_nullable_array(ver, self.assignment.as_ref().map(|v| v.iter()))?;
+            s.emit_nullable_array(ver, self.revoked.as_ref().map(|v| v.iter()))?;
+            s.emit_int32(self.scheduled_delay)?;
+        } else {
+            s.emit_array(ver, self.assignment.as_deref().unwrap_or_default().iter())?;
+            s.check_absent_field("revoked", ver, self.revoked.is_none())?;
+            s.check_absent_field("scheduled_delay", ver, self.scheduled_delay == 0)?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConnectProtocolAssignment {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConnectProtocolAssignment {
+            error: d.read_field("error", |d| d.read_int16())?,
+            leader: d.read_field("leader", |d| d.read_string())?.to_string(),
+            leader_url: d.read_field("leader_url", |d| d.read_string())?.to_string(),
+            config_offset: d.read_field("config_offset", |d| d.read_int64())?,
+            assignment: if ver >= 1 {
+                d.read_field("assignment", |d| d.read_nullable_array(ver))?
+            } else {
+                Some(d.read_field("assignment", |d| d.read_array(ver))?)
+            },
+            revoked: if ver >= 1 {
+                d.read_field("revoked", |d| d.read_nullable_array(ver))?
+            } else {
+                None
+            },
+            scheduled_delay: if ver >= 1 {
+                d.read_field("scheduled_delay", |d| d.read_int32())?
+            } else {
+                0
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConnectProtocolConnectorAssignment {
+    pub connector: String,
+    // Task ids, CONNECTOR_TASK stands for the connector itself
+    pub tasks: Vec<i32>,
+}
+
+builder! {
+    ConnectProtocolConnectorAssignment => ConnectProtocolConnectorAssignmentBuilder {
+        connector: String,
+        tasks: Vec<i32>,
+    }
+}
+
+impl KafkaProtoEncodable for ConnectProtocolConnectorAssignment {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.connector)?;
+        s.emit_array(ver, self.tasks.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConnectProtocolConnectorAssignment {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConnectProtocolConnectorAssignment {
+            connector: d.read_field("connector", |d| d.read_string())?.to_string(),
+            tasks: d.read_field("tasks", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl JoinGroupRequestProtocol<'_> {
+    // Protocol entry of a JoinGroupRequest with protocol_type "connect", name of
+    // the protocol is implied by `ver`
+    pub fn from_worker_state(
+        state: &ConnectProtocolWorkerState,
+        ver: i16,
+    ) -> crate::error::Result<JoinGroupRequestProtocol<'static>> {
+        let name =
+            connect_protocol_name(ver).ok_or(crate::error::Error::UnsupportedVersion(ver))?;
+        Ok(JoinGroupRequestProtocol {
+            name: name.to_string().into(),
+            metadata: state.to_bytes(ver)?.into(),
+        })
+    }
+
+    pub fn worker_state(&self) -> crate::error::Result<ConnectProtocolWorkerState> {
+        ConnectProtocolWorkerState::from_bytes(&self.metadata)
+    }
+}
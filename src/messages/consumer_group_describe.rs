@@ -0,0 +1,317 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{GroupId, KafkaMessage, KafkaRequest, TopicId, TopicName};
+
+#[derive(Default)]
+pub struct ConsumerGroupDescribeRequest {
+    // The ids of the groups to describe
+    pub group_ids: Vec<GroupId>,
+    // Whether to include authorized operations.
+    pub include_authorized_operations: bool,
+}
+
+builder! {
+    ConsumerGroupDescribeRequest => ConsumerGroupDescribeRequestBuilder {
+        group_ids: Vec<GroupId>,
+        include_authorized_operations: bool,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupDescribeRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.group_ids.iter())?;
+        s.emit_bool(self.include_authorized_operations)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupDescribeRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupDescribeRequest {
+            group_ids: d.read_field("group_ids", |d| d.read_array(ver))?,
+            include_authorized_operations: d
+                .read_field("include_authorized_operations", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ConsumerGroupDescribeRequest {
+    const API_KEY: i16 = 69;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for ConsumerGroupDescribeRequest {
+    type Response = ConsumerGroupDescribeResponse;
+}
+
+#[derive(Default)]
+pub struct ConsumerGroupDescribeResponse {
+    pub throttle_time_ms: i32,
+    pub groups: Vec<ConsumerGroupDescribeGroup>,
+}
+
+builder! {
+    ConsumerGroupDescribeResponse => ConsumerGroupDescribeResponseBuilder {
+        throttle_time_ms: i32,
+        groups: Vec<ConsumerGroupDescribeGroup>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupDescribeResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_array(ver, self.groups.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupDescribeResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupDescribeResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            groups: d.read_field("groups", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+pub struct ConsumerGroupDescribeGroup {
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub group_id: GroupId,
+    // The group state string, or the empty string.
+    pub group_state: String,
+    pub group_epoch: i32,
+    pub assignment_epoch: i32,
+    // The selected assignor.
+    pub assignor_name: String,
+    pub members: Vec<ConsumerGroupDescribeMember>,
+    // 32-bit bitfield to represent authorized operations for this group,
+    // default=-2147483648
+    pub authorized_operations: i32,
+}
+
+impl Default for ConsumerGroupDescribeGroup {
+    fn default() -> Self {
+        ConsumerGroupDescribeGroup {
+            error_code: 0,
+            error_message: None,
+            group_id: String::new(),
+            group_state: String::new(),
+            group_epoch: 0,
+            assignment_epoch: 0,
+            assignor_name: String::new(),
+            members: Vec::new(),
+            authorized_operations: -2147483648,
+        }
+    }
+}
+
+builder! {
+    ConsumerGroupDescribeGroup => ConsumerGroupDescribeGroupBuilder {
+        error_code: i16,
+        error_message: Option<String>,
+        group_id: GroupId,
+        group_state: String,
+        group_epoch: i32,
+        assignment_epoch: i32,
+        assignor_name: String,
+        members: Vec<ConsumerGroupDescribeMember>,
+        authorized_operations: i32,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupDescribeGroup {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_string(&self.group_id)?;
+        s.emit_string(&self.group_state)?;
+        s.emit_int32(self.group_epoch)?;
+        s.emit_int32(self.assignment_epoch)?;
+        s.emit_string(&self.assignor_name)?;
+        s.emit_array(ver, self.members.iter())?;
+        s.emit_int32(self.authorized_operations)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupDescribeGroup {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupDescribeGroup {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+            group_id: d.read_field("group_id", |d| d.read_string())?.to_string(),
+            group_state: d
+                .read_field("group_state", |d| d.read_string())?
+                .to_string(),
+            group_epoch: d.read_field("group_epoch", |d| d.read_int32())?,
+            assignment_epoch: d.read_field("assignment_epoch", |d| d.read_int32())?,
+            assignor_name: d
+                .read_field("assignor_name", |d| d.read_string())?
+                .to_string(),
+            members: d.read_field("members", |d| d.read_array(ver))?,
+            authorized_operations: d.read_field("authorized_operations", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConsumerGroupDescribeMember {
+    pub member_id: String,
+    pub instance_id: Option<String>,
+    pub rack_id: Option<String>,
+    pub member_epoch: i32,
+    pub client_id: String,
+    pub client_host: String,
+    pub subscribed_topic_names: Vec<TopicName>,
+    // the subscribed topic regex otherwise or null of not provided.
+    pub subscribed_topic_regex: Option<String>,
+    // The current assignment.
+    pub assignment: ConsumerGroupDescribeAssignment,
+    // The target assignment.
+    pub target_assignment: ConsumerGroupDescribeAssignment,
+}
+
+builder! {
+    ConsumerGroupDescribeMember => ConsumerGroupDescribeMemberBuilder {
+        member_id: String,
+        instance_id: Option<String>,
+        rack_id: Option<String>,
+        member_epoch: i32,
+        client_id: String,
+        client_host: String,
+        subscribed_topic_names: Vec<TopicName>,
+        subscribed_topic_regex: Option<String>,
+        assignment: ConsumerGroupDescribeAssignment,
+        target_assignment: ConsumerGroupDescribeAssignment,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupDescribeMember {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.member_id)?;
+        s.emit_nullable_string(self.instance_id.as_deref())?;
+        s.emit_nullable_string(self.rack_id.as_deref())?;
+        s.emit_int32(self.member_epoch)?;
+        s.emit_string(&self.client_id)?;
+        s.emit_string(&self.client_host)?;
+        s.emit_array(ver, self.subscribed_topic_names.iter())?;
+        s.emit_nullable_string(self.subscribed_topic_regex.as_deref())?;
+        self.assignment.serialize(ver, s)?;
+        self.target_assignment.serialize(ver, s)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupDescribeMember {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupDescribeMember {
+            member_id: d.read_field("member_id", |d| d.read_string())?.to_string(),
+            instance_id: d
+                .read_field("instance_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            rack_id: d
+                .read_field("rack_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            member_epoch: d.read_field("member_epoch", |d| d.read_int32())?,
+            client_id: d.read_field("client_id", |d| d.read_string())?.to_string(),
+            client_host: d
+                .read_field("client_host", |d| d.read_string())?
+                .to_string(),
+            subscribed_topic_names: d
+                .read_field("subscribed_topic_names", |d| d.read_array(ver))?,
+            subscribed_topic_regex: d
+                .read_field("subscribed_topic_regex", |d| d.read_nullable_string())?
+                .map(Into::into),
+            assignment: ConsumerGroupDescribeAssignment::deserialize(ver, d)?,
+            target_assignment: ConsumerGroupDescribeAssignment::deserialize(ver, d)?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConsumerGroupDescribeAssignment {
+    // The assigned topic-partitions to the member.
+    pub topic_partitions: Vec<ConsumerGroupDescribeTopicPartitions>,
+}
+
+builder! {
+    ConsumerGroupDescribeAssignment => ConsumerGroupDescribeAssignmentBuilder {
+        topic_partitions: Vec<ConsumerGroupDescribeTopicPartitions>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupDescribeAssignment {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.topic_partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupDescribeAssignment {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupDescribeAssignment {
+            topic_partitions: d.read_field("topic_partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConsumerGroupDescribeTopicPartitions {
+    pub topic_id: TopicId,
+    pub topic_name: TopicName,
+    pub partitions: Vec<i32>,
+}
+
+builder! {
+    ConsumerGroupDescribeTopicPartitions => ConsumerGroupDescribeTopicPartitionsBuilder {
+        topic_id: TopicId,
+        topic_name: TopicName,
+        partitions: Vec<i32>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupDescribeTopicPartitions {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(self.topic_id.0)?;
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupDescribeTopicPartitions {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupDescribeTopicPartitions {
+            topic_id: TopicId(d.read_field("topic_id", |d| d.read_uuid())?),
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ConsumerGroupDescribeResponse {
+    const API_KEY: i16 = 69;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,257 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{GroupId, KafkaMessage, KafkaRequest, TopicId, TopicName};
+
+// Heartbeat of the consumer group protocol introduced by KIP-848. It replaces
+// JoinGroup, SyncGroup, Heartbeat and LeaveGroup of the classic protocol.
+pub struct ConsumerGroupHeartbeatRequest {
+    pub group_id: GroupId,
+    // The member id generated by the consumer. The member id must be kept
+    // during the entire lifetime of the consumer process.
+    pub member_id: String,
+    // The current member epoch; 0 to join the group; -1 to leave the group;
+    // -2 to indicate that the static member will rejoin.
+    pub member_epoch: i32,
+    // null if not provided or if it didn't change since the last heartbeat.
+    pub instance_id: Option<String>,
+    // null if not provided or if it didn't change since the last heartbeat.
+    pub rack_id: Option<String>,
+    // -1 if it didn't change since the last heartbeat; the maximum time in
+    // milliseconds that the coordinator will wait on the member to revoke its
+    // partitions otherwise. default=-1
+    pub rebalance_timeout_ms: i32,
+
+    // nullableVersions: 0+, null if it didn't change since the last heartbeat
+    pub subscribed_topic_names: Option<Vec<TopicName>>,
+
+    // The server-side assignor to use, or null if not used or if it didn't
+    // change since the last heartbeat.
+    pub server_assignor: Option<String>,
+
+    // nullableVersions: 0+, null if it didn't change since the last heartbeat
+    // The partitions owned by the member.
+    pub topic_partitions: Option<Vec<ConsumerGroupHeartbeatTopicPartitions>>,
+}
+
+impl Default for ConsumerGroupHeartbeatRequest {
+    fn default() -> Self {
+        ConsumerGroupHeartbeatRequest {
+            group_id: String::new(),
+            member_id: String::new(),
+            member_epoch: 0,
+            instance_id: None,
+            rack_id: None,
+            rebalance_timeout_ms: -1,
+            subscribed_topic_names: None,
+            server_assignor: None,
+            topic_partitions: None,
+        }
+    }
+}
+
+builder! {
+    ConsumerGroupHeartbeatRequest => ConsumerGroupHeartbeatRequestBuilder {
+        group_id: GroupId,
+        member_id: String,
+        member_epoch: i32,
+        instance_id: Option<String>,
+        rack_id: Option<String>,
+        rebalance_timeout_ms: i32,
+        subscribed_topic_names: Option<Vec<TopicName>>,
+        server_assignor: Option<String>,
+        topic_partitions: Option<Vec<ConsumerGroupHeartbeatTopicPartitions>>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupHeartbeatRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.group_id)?;
+        s.emit_string(&self.member_id)?;
+        s.emit_int32(self.member_epoch)?;
+        s.emit_nullable_string(self.instance_id.as_deref())?;
+        s.emit_nullable_string(self.rack_id.as_deref())?;
+        s.emit_int32(self.rebalance_timeout_ms)?;
+        s.emit_nullable_array(ver, self.subscribed_topic_names.as_ref().map(|v| v.iter()))?;
+        s.emit_nullable_string(self.server_assignor.as_deref())?;
+        s.emit_nullable_array(ver, self.topic_partitions.as_ref().map(|v| v.iter()))?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupHeartbeatRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupHeartbeatRequest {
+            group_id: d.read_field("group_id", |d| d.read_string())?.to_string(),
+            member_id: d.read_field("member_id", |d| d.read_string())?.to_string(),
+            member_epoch: d.read_field("member_epoch", |d| d.read_int32())?,
+            instance_id: d
+                .read_field("instance_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            rack_id: d
+                .read_field("rack_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            rebalance_timeout_ms: d.read_field("rebalance_timeout_ms", |d| d.read_int32())?,
+            subscribed_topic_names: d
+                .read_field("subscribed_topic_names", |d| d.read_nullable_array(ver))?,
+            server_assignor: d
+                .read_field("server_assignor", |d| d.read_nullable_string())?
+                .map(Into::into),
+            topic_partitions: d.read_field("topic_partitions", |d| d.read_nullable_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConsumerGroupHeartbeatTopicPartitions {
+    pub topic_id: TopicId,
+    pub partitions: Vec<i32>,
+}
+
+builder! {
+    ConsumerGroupHeartbeatTopicPartitions => ConsumerGroupHeartbeatTopicPartitionsBuilder {
+        topic_id: TopicId,
+        partitions: Vec<i32>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupHeartbeatTopicPartitions {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(self.topic_id.0)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupHeartbeatTopicPartitions {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupHeartbeatTopicPartitions {
+            topic_id: TopicId(d.read_field("topic_id", |d| d.read_uuid())?),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ConsumerGroupHeartbeatRequest {
+    const API_KEY: i16 = 68;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for ConsumerGroupHeartbeatRequest {
+    type Response = ConsumerGroupHeartbeatResponse;
+}
+
+#[derive(Default)]
+pub struct ConsumerGroupHeartbeatResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    // The member id generated by the coordinator. Only provided when the member
+    // joins with MemberEpoch == 0.
+    pub member_id: Option<String>,
+    pub member_epoch: i32,
+    // The heartbeat interval in milliseconds.
+    pub heartbeat_interval_ms: i32,
+    // null if not provided; the assignment otherwise.
+    pub assignment: Option<ConsumerGroupHeartbeatAssignment>,
+}
+
+builder! {
+    ConsumerGroupHeartbeatResponse => ConsumerGroupHeartbeatResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        error_message: Option<String>,
+        member_id: Option<String>,
+        member_epoch: i32,
+        heartbeat_interval_ms: i32,
+        assignment: Option<ConsumerGroupHeartbeatAssignment>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupHeartbeatResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_nullable_string(self.member_id.as_deref())?;
+        s.emit_int32(self.member_epoch)?;
+        s.emit_int32(self.heartbeat_interval_ms)?;
+        // Nullable struct is prefixed with -1 when null and 1 otherwise
+        match &self.assignment {
+            None => s.emit_int8(-1)?,
+            Some(assignment) => {
+                s.emit_int8(1)?;
+                assignment.serialize(ver, s)?
+            }
+        };
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupHeartbeatResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupHeartbeatResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+            member_id: d
+                .read_field("member_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            member_epoch: d.read_field("member_epoch", |d| d.read_int32())?,
+            heartbeat_interval_ms: d.read_field("heartbeat_interval_ms", |d| d.read_int32())?,
+            assignment: if d.read_field("assignment", |d| d.read_int8())? < 0 {
+                None
+            } else {
+                Some(ConsumerGroupHeartbeatAssignment::deserialize(ver, d)?)
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConsumerGroupHeartbeatAssignment {
+    // The partitions assigned to the member that can be used immediately.
+    pub topic_partitions: Vec<ConsumerGroupHeartbeatTopicPartitions>,
+}
+
+builder! {
+    ConsumerGroupHeartbeatAssignment => ConsumerGroupHeartbeatAssignmentBuilder {
+        topic_partitions: Vec<ConsumerGroupHeartbeatTopicPartitions>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerGroupHeartbeatAssignment {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.topic_partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerGroupHeartbeatAssignment {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerGroupHeartbeatAssignment {
+            topic_partitions: d.read_field("topic_partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ConsumerGroupHeartbeatResponse {
+    const API_KEY: i16 = 68;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
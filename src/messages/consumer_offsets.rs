@@ -0,0 +1,460 @@
+use crate::de::{
+    deserialize_versioned, KafkaDeserializer, KafkaFlexibleDecoder, KafkaProtoDecodable,
+    KafkaProtoDecoder,
+};
+use crate::ser::{serialize_versioned, KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::messages::{ConsumerProtocolAssignment, ConsumerProtocolSubscription};
+use crate::{Bytes, GroupId, TopicName};
+
+// Record of the __consumer_offsets topic. Both key and value are prefixed with
+// their version, version of the key determines type of the record. Null value
+// is a tombstone: offset was deleted or group is gone.
+pub enum ConsumerOffsetsRecord {
+    OffsetCommit(OffsetCommitKey, Option<OffsetCommitValue>),
+    GroupMetadata(GroupMetadataKey, Option<GroupMetadataValue>),
+    // Record types this crate doesn't know about, e.g. ones written by the
+    // consumer group protocol (KIP-848) coordinator, with their key version
+    Unknown(i16),
+}
+
+impl ConsumerOffsetsRecord {
+    pub fn from_bytes(key: &[u8], value: Option<&[u8]>) -> crate::error::Result<Self> {
+        let key_ver = KafkaDeserializer::new(key).read_int16()?;
+        let r = match key_ver {
+            0..=1 => ConsumerOffsetsRecord::OffsetCommit(
+                OffsetCommitKey::from_bytes(key)?,
+                value.map(OffsetCommitValue::from_bytes).transpose()?,
+            ),
+            2 => ConsumerOffsetsRecord::GroupMetadata(
+                GroupMetadataKey::from_bytes(key)?,
+                value.map(GroupMetadataValue::from_bytes).transpose()?,
+            ),
+            v => ConsumerOffsetsRecord::Unknown(v),
+        };
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct OffsetCommitKey {
+    pub group: GroupId,
+    pub topic: TopicName,
+    pub partition: i32,
+}
+
+builder! {
+    OffsetCommitKey => OffsetCommitKeyBuilder {
+        group: GroupId,
+        topic: TopicName,
+        partition: i32,
+    }
+}
+
+impl OffsetCommitKey {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 1;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for OffsetCommitKey {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.group)?;
+        s.emit_string(&self.topic)?;
+        s.emit_int32(self.partition)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for OffsetCommitKey {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = OffsetCommitKey {
+            group: d.read_field("group", |d| d.read_string())?.to_string(),
+            topic: d.read_field("topic", |d| d.read_string())?.to_string(),
+            partition: d.read_field("partition", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+pub struct OffsetCommitValue {
+    pub offset: i64,
+
+    // versions: 3+, default=-1
+    pub leader_epoch: i32,
+
+    pub metadata: String,
+    pub commit_timestamp: i64,
+
+    // versions: 1, default=-1
+    pub expire_timestamp: i64,
+}
+
+impl Default for OffsetCommitValue {
+    fn default() -> Self {
+        OffsetCommitValue {
+            offset: 0,
+            leader_epoch: -1,
+            metadata: String::new(),
+            commit_timestamp: 0,
+            expire_timestamp: -1,
+        }
+    }
+}
+
+builder! {
+    OffsetCommitValue => OffsetCommitValueBuilder {
+        offset: i64,
+        leader_epoch: i32,
+        metadata: String,
+        commit_timestamp: i64,
+        expire_timestamp: i64,
+    }
+}
+
+impl OffsetCommitValue {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 4;
+    pub const FLEXIBLE_VERSION: i16 = 4;
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for OffsetCommitValue {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int64(self.offset)?;
+        if ver >= 3 {
+            s.emit_int32(self.leader_epoch)?;
+        } else {
+            s.check_absent_field("leader_epoch", ver, self.leader_epoch == -1)?;
+        }
+        s.emit_string(&self.metadata)?;
+        s.emit_int64(self.commit_timestamp)?;
+        if ver == 1 {
+            s.emit_int64(self.expire_timestamp)?;
+        } else {
+            s.check_absent_field("expire_timestamp", ver, self.expire_timestamp == -1)?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for OffsetCommitValue {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = OffsetCommitValue {
+            offset: d.read_field("offset", |d| d.read_int64())?,
+            leader_epoch: if ver >= 3 {
+                d.read_field("leader_epoch", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            metadata: d.read_field("metadata", |d| d.read_string())?.to_string(),
+            commit_timestamp: d.read_field("commit_timestamp", |d| d.read_int64())?,
+            expire_timestamp: if ver == 1 {
+                d.read_field("expire_timestamp", |d| d.read_int64())?
+            } else {
+                -1
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct GroupMetadataKey {
+    pub group: GroupId,
+}
+
+builder! {
+    GroupMetadataKey => GroupMetadataKeyBuilder {
+        group: GroupId,
+    }
+}
+
+impl GroupMetadataKey {
+    pub const MIN_VERSION: i16 = 2;
+    pub const MAX_VERSION: i16 = 2;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for GroupMetadataKey {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.group)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for GroupMetadataKey {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = GroupMetadataKey {
+            group: d.read_field("group", |d| d.read_string())?.to_string(),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+pub struct GroupMetadataValue {
+    pub protocol_type: String,
+    pub generation: i32,
+    pub protocol: Option<String>,
+    pub leader: Option<String>,
+
+    // versions: 2+, default=-1
+    pub current_state_timestamp: i64,
+
+    pub members: Vec<GroupMetadataMember>,
+}
+
+impl Default for GroupMetadataValue {
+    fn default() -> Self {
+        GroupMetadataValue {
+            protocol_type: String::new(),
+            generation: 0,
+            protocol: None,
+            leader: None,
+            current_state_timestamp: -1,
+            members: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    GroupMetadataValue => GroupMetadataValueBuilder {
+        protocol_type: String,
+        generation: i32,
+        protocol: Option<String>,
+        leader: Option<String>,
+        current_state_timestamp: i64,
+        members: Vec<GroupMetadataMember>,
+    }
+}
+
+impl GroupMetadataValue {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 4;
+    pub const FLEXIBLE_VERSION: i16 = 4;
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for GroupMetadataValue {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.protocol_type)?;
+        s.emit_int32(self.generation)?;
+        s.emit_nullable_string(self.protocol.as_deref())?;
+        s.emit_nullable_string(self.leader.as_deref())?;
+        if ver >= 2 {
+            s.emit_int64(self.current_state_timestamp)?;
+        } else {
+            s.check_absent_field(
+                "current_state_timestamp",
+                ver,
+                self.current_state_timestamp == -1,
+            )?;
+        }
+        s.emit_array(ver, self.members.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for GroupMetadataValue {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = GroupMetadataValue {
+            protocol_type: d
+                .read_field("protocol_type", |d| d.read_string())?
+                .to_string(),
+            generation: d.read_field("generation", |d| d.read_int32())?,
+            protocol: d
+                .read_field("protocol", |d| d.read_nullable_string())?
+                .map(Into::into),
+            leader: d
+                .read_field("leader", |d| d.read_nullable_string())?
+                .map(Into::into),
+            current_state_timestamp: if ver >= 2 {
+                d.read_field("current_state_timestamp", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            members: d.read_field("members", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+pub struct GroupMetadataMember {
+    pub member_id: String,
+
+    // versions: 3+, default=null
+    pub group_instance_id: Option<String>,
+
+    pub client_id: String,
+    pub client_host: String,
+
+    // versions: 1+, default=-1
+    pub rebalance_timeout: i32,
+
+    pub session_timeout: i32,
+    // Metadata of the group protocol chosen by the coordinator
+    pub subscription: Bytes,
+    pub assignment: Bytes,
+}
+
+impl Default for GroupMetadataMember {
+    fn default() -> Self {
+        GroupMetadataMember {
+            member_id: String::new(),
+            group_instance_id: None,
+            client_id: String::new(),
+            client_host: String::new(),
+            rebalance_timeout: -1,
+            session_timeout: 0,
+            subscription: Vec::new(),
+            assignment: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    GroupMetadataMember => GroupMetadataMemberBuilder {
+        member_id: String,
+        group_instance_id: Option<String>,
+        client_id: String,
+        client_host: String,
+        rebalance_timeout: i32,
+        session_timeout: i32,
+        subscription: Bytes,
+        assignment: Bytes,
+    }
+}
+
+impl GroupMetadataMember {
+    // Valid for groups with protocol_type "consumer" only
+    pub fn consumer_subscription(&self) -> crate::error::Result<ConsumerProtocolSubscription> {
+        ConsumerProtocolSubscription::from_bytes(&self.subscription)
+    }
+
+    // Valid for groups with protocol_type "consumer" only
+    pub fn consumer_assignment(&self) -> crate::error::Result<ConsumerProtocolAssignment> {
+        ConsumerProtocolAssignment::from_bytes(&self.assignment)
+    }
+}
+
+impl KafkaProtoEncodable for GroupMetadataMember {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.member_id)?;
+        if ver >= 3 {
+            s.emit_nullable_string(self.group_instance_id.as_deref())?;
+        } else {
+            s.check_absent_field("group_instance_id", ver, self.group_instance_id.is_none())?;
+        }
+        s.emit_string(&self.client_id)?;
+        s.emit_string(&self.client_host)?;
+        if ver >= 1 {
+            s.emit_int32(self.rebalance_timeout)?;
+        } else {
+            s.check_absent_field("rebalance_timeout", ver, self.rebalance_timeout == -1)?;
+        }
+        s.emit_int32(self.session_timeout)?;
+        s.emit_bytes(&self.subscription)?;
+        s.emit_bytes(&self.assignment)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for GroupMetadataMember {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = GroupMetadataMember {
+            member_id: d.read_field("member_id", |d| d.read_string())?.to_string(),
+            group_instance_id: if ver >= 3 {
+                d.read_field("group_instance_id", |d| d.read_nullable_string())?
+                    .map(Into::into)
+            } else {
+                None
+            },
+            client_id: d.read_field("client_id", |d| d.read_string())?.to_string(),
+            client_host: d
+                .read_field("client_host", |d| d.read_string())?
+                .to_string(),
+            rebalance_timeout: if ver >= 1 {
+                d.read_field("rebalance_timeout", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            session_timeout: d.read_field("session_timeout", |d| d.read_int32())?,
+            subscription: d.read_field("subscription", |d| d.read_bytes())?.to_vec(),
+            assignment: d.read_field("assignment", |d| d.read_bytes())?.to_vec(),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
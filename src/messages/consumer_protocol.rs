@@ -0,0 +1,234 @@
+use crate::de::{deserialize_versioned, KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{serialize_versioned, KafkaFlexibleEncoder, KafkaProtoEncodable};
+use linked_hash_map::LinkedHashMap;
+
+use crate::messages::JoinGroupRequestProtocol;
+use crate::{Bytes, TopicName};
+
+// JoinGroupRequest.protocol_type used by consumers. Metadata of each protocol is
+// a ConsumerProtocolSubscription and SyncGroup assignments are
+// ConsumerProtocolAssignment, both prefixed with their version.
+pub const CONSUMER_PROTOCOL_TYPE: &str = "consumer";
+
+pub struct ConsumerProtocolSubscription {
+    pub topics: Vec<TopicName>,
+    pub user_data: Option<Bytes>,
+
+    // versions: 1+, ignorable
+    // key: topic
+    pub owned_partitions: LinkedHashMap<TopicName, ConsumerProtocolTopicPartition>,
+
+    // versions: 2+, ignorable, default=-1
+    pub generation_id: i32,
+
+    // versions: 3+, ignorable, default=null
+    pub rack_id: Option<String>,
+}
+
+impl Default for ConsumerProtocolSubscription {
+    fn default() -> Self {
+        ConsumerProtocolSubscription {
+            topics: Vec::new(),
+            user_data: None,
+            owned_partitions: LinkedHashMap::new(),
+            generation_id: -1,
+            rack_id: None,
+        }
+    }
+}
+
+builder! {
+    ConsumerProtocolSubscription => ConsumerProtocolSubscriptionBuilder {
+        topics: Vec<TopicName>,
+        user_data: Option<Bytes>,
+        owned_partitions: LinkedHashMap<TopicName, ConsumerProtocolTopicPartition>,
+        generation_id: i32,
+        rack_id: Option<String>,
+    }
+}
+
+impl ConsumerProtocolSubscription {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 3;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerProtocolSubscription {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_nullable_bytes(self.user_data.as_deref())?;
+        if ver >= 1 {
+            s.emit_array(ver, self.owned_partitions.values())?;
+        }
+        if ver >= 2 {
+            s.emit_int32(self.generation_id)?;
+        }
+        if ver >= 3 {
+            s.emit_nullable_string(self.rack_id.as_deref())?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerProtocolSubscription {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerProtocolSubscription {
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+            user_data: d
+                .read_field("user_data", |d| d.read_nullable_bytes())?
+                .map(<[u8]>::to_vec),
+            owned_partitions: if ver >= 1 {
+                read_topic_partitions("owned_partitions", ver, d)?
+            } else {
+                LinkedHashMap::new()
+            },
+            generation_id: if ver >= 2 {
+                d.read_field("generation_id", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            rack_id: if ver >= 3 {
+                d.read_field("rack_id", |d| d.read_nullable_string())?
+                    .map(Into::into)
+            } else {
+                None
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ConsumerProtocolTopicPartition {
+    pub topic: TopicName,
+    pub partitions: Vec<i32>,
+}
+
+builder! {
+    ConsumerProtocolTopicPartition => ConsumerProtocolTopicPartitionBuilder {
+        topic: TopicName,
+        partitions: Vec<i32>,
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerProtocolTopicPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerProtocolTopicPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerProtocolTopicPartition {
+            topic: d.read_field("topic", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+fn read_topic_partitions<'a, D: KafkaFlexibleDecoder<'a>>(
+    name: &str,
+    ver: i16,
+    d: &mut D,
+) -> Result<LinkedHashMap<TopicName, ConsumerProtocolTopicPartition>, D::Error> {
+    d.read_field(name, |d| {
+        d.read_map(ver, |t: &ConsumerProtocolTopicPartition| t.topic.clone())
+    })
+}
+
+#[derive(Default)]
+pub struct ConsumerProtocolAssignment {
+    // key: topic
+    pub assigned_partitions: LinkedHashMap<TopicName, ConsumerProtocolTopicPartition>,
+    pub user_data: Option<Bytes>,
+}
+
+builder! {
+    ConsumerProtocolAssignment => ConsumerProtocolAssignmentBuilder {
+        assigned_partitions: LinkedHashMap<TopicName, ConsumerProtocolTopicPartition>,
+        user_data: Option<Bytes>,
+    }
+}
+
+impl ConsumerProtocolAssignment {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 3;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for ConsumerProtocolAssignment {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.assigned_partitions.values())?;
+        s.emit_nullable_bytes(self.user_data.as_deref())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConsumerProtocolAssignment {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ConsumerProtocolAssignment {
+            assigned_partitions: read_topic_partitions("assigned_partitions", ver, d)?,
+            user_data: d
+                .read_field("user_data", |d| d.read_nullable_bytes())?
+                .map(<[u8]>::to_vec),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl JoinGroupRequestProtocol<'_> {
+    // Protocol entry of a JoinGroupRequest with protocol_type "consumer", `name`
+    // is the assignor name, e.g. "range" or "cooperative-sticky"
+    pub fn from_subscription(
+        name: &str,
+        subscription: &ConsumerProtocolSubscription,
+        ver: i16,
+    ) -> crate::error::Result<JoinGroupRequestProtocol<'static>> {
+        Ok(JoinGroupRequestProtocol {
+            name: name.to_string().into(),
+            metadata: subscription.to_bytes(ver)?.into(),
+        })
+    }
+
+    pub fn subscription(&self) -> crate::error::Result<ConsumerProtocolSubscription> {
+        ConsumerProtocolSubscription::from_bytes(&self.metadata)
+    }
+}
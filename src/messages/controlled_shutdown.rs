@@ -0,0 +1,158 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+use linked_hash_map::LinkedHashMap;
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicName};
+
+pub struct ControlledShutdownRequest {
+    // The id of the broker for which controlled shutdown has been requested.
+    pub broker_id: BrokerId,
+
+    // versions: 2+, ignorable, default=-1
+    pub broker_epoch: i64,
+}
+
+impl Default for ControlledShutdownRequest {
+    fn default() -> Self {
+        ControlledShutdownRequest {
+            broker_id: 0,
+            broker_epoch: -1,
+        }
+    }
+}
+
+builder! {
+    ControlledShutdownRequest => ControlledShutdownRequestBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for ControlledShutdownRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        if ver >= 2 {
+            s.emit_int64(self.broker_epoch)?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ControlledShutdownRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ControlledShutdownRequest {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: if ver >= 2 {
+                d.read_field("broker_epoch", |d| d.read_int64())?
+            } else {
+                -1
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ControlledShutdownRequest {
+    const API_KEY: i16 = 7;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 3;
+    const FLEXIBLE_VERSION: i16 = 3;
+
+    // Version 0 of ControlledShutdownRequest is the only user of RequestHeader v0,
+    // which has no ClientId
+    fn request_header_version(ver: i16) -> i16 {
+        match ver {
+            0 => 0,
+            v if v >= Self::FLEXIBLE_VERSION => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl KafkaRequest for ControlledShutdownRequest {
+    type Response = ControlledShutdownResponse;
+}
+
+#[derive(Default)]
+pub struct ControlledShutdownResponse {
+    pub error_code: i16,
+
+    // key: (topic_name, partition_index)
+    // The partitions that the broker still leads.
+    pub remaining_partitions: LinkedHashMap<(TopicName, i32), ControlledShutdownRemainingPartition>,
+}
+
+builder! {
+    ControlledShutdownResponse => ControlledShutdownResponseBuilder {
+        error_code: i16,
+        remaining_partitions: LinkedHashMap<(TopicName, i32), ControlledShutdownRemainingPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for ControlledShutdownResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.remaining_partitions.values())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ControlledShutdownResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ControlledShutdownResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            remaining_partitions: d.read_field("remaining_partitions", |d| {
+                d.read_map(ver, |p: &ControlledShutdownRemainingPartition| {
+                    (p.topic_name.clone(), p.partition_index)
+                })
+            })?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ControlledShutdownRemainingPartition {
+    pub topic_name: TopicName,
+    pub partition_index: i32,
+}
+
+builder! {
+    ControlledShutdownRemainingPartition => ControlledShutdownRemainingPartitionBuilder {
+        topic_name: TopicName,
+        partition_index: i32,
+    }
+}
+
+impl KafkaProtoEncodable for ControlledShutdownRemainingPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_int32(self.partition_index)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ControlledShutdownRemainingPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = ControlledShutdownRemainingPartition {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ControlledShutdownResponse {
+    const API_KEY: i16 = 7;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 3;
+    const FLEXIBLE_VERSION: i16 = 3;
+}
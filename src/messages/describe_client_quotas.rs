@@ -0,0 +1,266 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{KafkaMessage, KafkaRequest};
+
+#[derive(Default)]
+pub struct DescribeClientQuotasRequest {
+    // Filter components to apply to quota entities.
+    pub components: Vec<DescribeClientQuotasRequestComponent>,
+    // Whether the match is strict, i.e. should exclude entities with unspecified entity types.
+    pub strict: bool,
+}
+
+builder! {
+    DescribeClientQuotasRequest => DescribeClientQuotasRequestBuilder {
+        components: Vec<DescribeClientQuotasRequestComponent>,
+        strict: bool,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeClientQuotasRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.components.iter())?;
+        s.emit_bool(self.strict)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClientQuotasRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeClientQuotasRequest {
+            components: d.read_field("components", |d| d.read_array(ver))?,
+            strict: d.read_field("strict", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeClientQuotasRequestComponent {
+    // The entity type that the filter component applies to.
+    pub entity_type: String,
+    // How to match the entity, one of MATCH_TYPE_* constants.
+    pub match_type: i8,
+    // The string to match against, or null if unused for the match type.
+    pub match_: Option<String>,
+}
+
+builder! {
+    DescribeClientQuotasRequestComponent => DescribeClientQuotasRequestComponentBuilder {
+        entity_type: String,
+        match_type: i8,
+        match_: Option<String>,
+    }
+}
+
+// Match types, as used by `DescribeClientQuotasRequestComponent::match_type`
+pub const MATCH_TYPE_EXACT: i8 = 0; // match the entity name exactly
+pub const MATCH_TYPE_DEFAULT: i8 = 1; // match the default entity name
+pub const MATCH_TYPE_SPECIFIED: i8 = 2; // match any specified entity name
+
+impl KafkaProtoEncodable for DescribeClientQuotasRequestComponent {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.entity_type)?;
+        s.emit_int8(self.match_type)?;
+        s.emit_nullable_string(self.match_.as_deref())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClientQuotasRequestComponent {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = DescribeClientQuotasRequestComponent {
+            entity_type: d
+                .read_field("entity_type", |d| d.read_string())?
+                .to_string(),
+            match_type: d.read_field("match_type", |d| d.read_int8())?,
+            match_: d
+                .read_field("match_", |d| d.read_nullable_string())?
+                .map(Into::into),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for DescribeClientQuotasRequest {
+    const API_KEY: i16 = 48;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 1;
+}
+
+impl KafkaRequest for DescribeClientQuotasRequest {
+    type Response = DescribeClientQuotasResponse;
+}
+
+#[derive(Default)]
+pub struct DescribeClientQuotasResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+
+    // nullableVersions: 0+
+    pub entries: Option<Vec<DescribeClientQuotasResponseEntry>>,
+}
+
+builder! {
+    DescribeClientQuotasResponse => DescribeClientQuotasResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        error_message: Option<String>,
+        entries: Option<Vec<DescribeClientQuotasResponseEntry>>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeClientQuotasResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_nullable_array(ver, self.entries.as_ref().map(|v| v.iter()))?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClientQuotasResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeClientQuotasResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+            entries: d.read_field("entries", |d| d.read_nullable_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeClientQuotasResponseEntry {
+    // The quota entity description.
+    pub entity: Vec<ClientQuotaEntity>,
+    // The quota values for the entity.
+    pub values: Vec<DescribeClientQuotasResponseValue>,
+}
+
+builder! {
+    DescribeClientQuotasResponseEntry => DescribeClientQuotasResponseEntryBuilder {
+        entity: Vec<ClientQuotaEntity>,
+        values: Vec<DescribeClientQuotasResponseValue>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeClientQuotasResponseEntry {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.entity.iter())?;
+        s.emit_array(ver, self.values.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClientQuotasResponseEntry {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeClientQuotasResponseEntry {
+            entity: d.read_field("entity", |d| d.read_array(ver))?,
+            values: d.read_field("values", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// Quota entity component, shared by DescribeClientQuotas and AlterClientQuotas
+#[derive(Default)]
+pub struct ClientQuotaEntity {
+    // The entity type, e.g. "user", "client-id" or "ip".
+    pub entity_type: String,
+    // The entity name, or null if the default.
+    pub entity_name: Option<String>,
+}
+
+builder! {
+    ClientQuotaEntity => ClientQuotaEntityBuilder {
+        entity_type: String,
+        entity_name: Option<String>,
+    }
+}
+
+impl KafkaProtoEncodable for ClientQuotaEntity {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.entity_type)?;
+        s.emit_nullable_string(self.entity_name.as_deref())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ClientQuotaEntity {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = ClientQuotaEntity {
+            entity_type: d
+                .read_field("entity_type", |d| d.read_string())?
+                .to_string(),
+            entity_name: d
+                .read_field("entity_name", |d| d.read_nullable_string())?
+                .map(Into::into),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeClientQuotasResponseValue {
+    // The quota configuration key.
+    pub key: String,
+    // The quota configuration value.
+    pub value: f64,
+}
+
+builder! {
+    DescribeClientQuotasResponseValue => DescribeClientQuotasResponseValueBuilder {
+        key: String,
+        value: f64,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeClientQuotasResponseValue {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.key)?;
+        s.emit_float64(self.value)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClientQuotasResponseValue {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = DescribeClientQuotasResponseValue {
+            key: d.read_field("key", |d| d.read_string())?.to_string(),
+            value: d.read_field("value", |d| d.read_float64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for DescribeClientQuotasResponse {
+    const API_KEY: i16 = 48;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 1;
+}
@@ -0,0 +1,215 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+use linked_hash_map::LinkedHashMap;
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest};
+
+pub struct DescribeClusterRequest {
+    // Whether to include cluster authorized operations.
+    pub include_cluster_authorized_operations: bool,
+
+    // versions: 1+, default=1
+    // The endpoint type to describe. 1=brokers, 2=controllers.
+    pub endpoint_type: i8,
+}
+
+impl Default for DescribeClusterRequest {
+    fn default() -> Self {
+        DescribeClusterRequest {
+            include_cluster_authorized_operations: false,
+            endpoint_type: 1,
+        }
+    }
+}
+
+builder! {
+    DescribeClusterRequest => DescribeClusterRequestBuilder {
+        include_cluster_authorized_operations: bool,
+        endpoint_type: i8,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeClusterRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_bool(self.include_cluster_authorized_operations)?;
+        if ver >= 1 {
+            s.emit_int8(self.endpoint_type)?;
+        } else {
+            s.check_absent_field("endpoint_type", ver, self.endpoint_type == 1)?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClusterRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeClusterRequest {
+            include_cluster_authorized_operations: d
+                .read_field("include_cluster_authorized_operations", |d| d.read_bool())?,
+            endpoint_type: if ver >= 1 {
+                d.read_field("endpoint_type", |d| d.read_int8())?
+            } else {
+                1
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for DescribeClusterRequest {
+    const API_KEY: i16 = 60;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for DescribeClusterRequest {
+    type Response = DescribeClusterResponse;
+}
+
+pub struct DescribeClusterResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+
+    // versions: 1+, default=1
+    pub endpoint_type: i8,
+
+    pub cluster_id: String,
+    // The ID of the controller broker, default=-1
+    pub controller_id: BrokerId,
+
+    // key: broker_id
+    pub brokers: LinkedHashMap<BrokerId, DescribeClusterBroker>,
+
+    // 32-bit bitfield to represent authorized operations for this cluster,
+    // default=-2147483648
+    pub cluster_authorized_operations: i32,
+}
+
+impl Default for DescribeClusterResponse {
+    fn default() -> Self {
+        DescribeClusterResponse {
+            throttle_time_ms: 0,
+            error_code: 0,
+            error_message: None,
+            endpoint_type: 1,
+            cluster_id: String::new(),
+            controller_id: -1,
+            brokers: LinkedHashMap::new(),
+            cluster_authorized_operations: -2147483648,
+        }
+    }
+}
+
+builder! {
+    DescribeClusterResponse => DescribeClusterResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        error_message: Option<String>,
+        endpoint_type: i8,
+        cluster_id: String,
+        controller_id: BrokerId,
+        brokers: LinkedHashMap<BrokerId, DescribeClusterBroker>,
+        cluster_authorized_operations: i32,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeClusterResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        if ver >= 1 {
+            s.emit_int8(self.endpoint_type)?;
+        } else {
+            s.check_absent_field("endpoint_type", ver, self.endpoint_type == 1)?;
+        }
+        s.emit_string(&self.cluster_id)?;
+        s.emit_int32(self.controller_id)?;
+        s.emit_array(ver, self.brokers.values())?;
+        s.emit_int32(self.cluster_authorized_operations)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClusterResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeClusterResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+            endpoint_type: if ver >= 1 {
+                d.read_field("endpoint_type", |d| d.read_int8())?
+            } else {
+                1
+            },
+            cluster_id: d.read_field("cluster_id", |d| d.read_string())?.to_string(),
+            controller_id: d.read_field("controller_id", |d| d.read_int32())?,
+            brokers: d.read_field("brokers", |d| {
+                d.read_map(ver, |b: &DescribeClusterBroker| b.broker_id)
+            })?,
+            cluster_authorized_operations: d
+                .read_field("cluster_authorized_operations", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeClusterBroker {
+    pub broker_id: BrokerId,
+    pub host: String,
+    pub port: i32,
+    pub rack: Option<String>,
+}
+
+builder! {
+    DescribeClusterBroker => DescribeClusterBrokerBuilder {
+        broker_id: BrokerId,
+        host: String,
+        port: i32,
+        rack: Option<String>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeClusterBroker {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_string(&self.host)?;
+        s.emit_int32(self.port)?;
+        s.emit_nullable_string(self.rack.as_deref())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeClusterBroker {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = DescribeClusterBroker {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            host: d.read_field("host", |d| d.read_string())?.to_string(),
+            port: d.read_field("port", |d| d.read_int32())?,
+            rack: d
+                .read_field("rack", |d| d.read_nullable_string())?
+                .map(Into::into),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for DescribeClusterResponse {
+    const API_KEY: i16 = 60;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,303 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicName};
+
+#[derive(Default)]
+pub struct DescribeQuorumRequest {
+    pub topics: Vec<DescribeQuorumRequestTopic>,
+}
+
+builder! {
+    DescribeQuorumRequest => DescribeQuorumRequestBuilder {
+        topics: Vec<DescribeQuorumRequestTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeQuorumRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeQuorumRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeQuorumRequest {
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeQuorumRequestTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<DescribeQuorumRequestPartition>,
+}
+
+builder! {
+    DescribeQuorumRequestTopic => DescribeQuorumRequestTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<DescribeQuorumRequestPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeQuorumRequestTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeQuorumRequestTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeQuorumRequestTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeQuorumRequestPartition {
+    pub partition_index: i32,
+}
+
+builder! {
+    DescribeQuorumRequestPartition => DescribeQuorumRequestPartitionBuilder {
+        partition_index: i32,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeQuorumRequestPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeQuorumRequestPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = DescribeQuorumRequestPartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for DescribeQuorumRequest {
+    const API_KEY: i16 = 55;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for DescribeQuorumRequest {
+    type Response = DescribeQuorumResponse;
+}
+
+#[derive(Default)]
+pub struct DescribeQuorumResponse {
+    // The top level error code.
+    pub error_code: i16,
+    pub topics: Vec<DescribeQuorumResponseTopic>,
+}
+
+builder! {
+    DescribeQuorumResponse => DescribeQuorumResponseBuilder {
+        error_code: i16,
+        topics: Vec<DescribeQuorumResponseTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeQuorumResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeQuorumResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeQuorumResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeQuorumResponseTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<DescribeQuorumResponsePartition>,
+}
+
+builder! {
+    DescribeQuorumResponseTopic => DescribeQuorumResponseTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<DescribeQuorumResponsePartition>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeQuorumResponseTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeQuorumResponseTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeQuorumResponseTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeQuorumResponsePartition {
+    pub partition_index: i32,
+    pub error_code: i16,
+    // The ID of the current leader or -1 if the leader is unknown.
+    pub leader_id: BrokerId,
+    // The latest known leader epoch.
+    pub leader_epoch: i32,
+    pub high_watermark: i64,
+    pub current_voters: Vec<QuorumReplicaState>,
+    pub observers: Vec<QuorumReplicaState>,
+}
+
+builder! {
+    DescribeQuorumResponsePartition => DescribeQuorumResponsePartitionBuilder {
+        partition_index: i32,
+        error_code: i16,
+        leader_id: BrokerId,
+        leader_epoch: i32,
+        high_watermark: i64,
+        current_voters: Vec<QuorumReplicaState>,
+        observers: Vec<QuorumReplicaState>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeQuorumResponsePartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_int64(self.high_watermark)?;
+        s.emit_array(ver, self.current_voters.iter())?;
+        s.emit_array(ver, self.observers.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeQuorumResponsePartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeQuorumResponsePartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            high_watermark: d.read_field("high_watermark", |d| d.read_int64())?,
+            current_voters: d.read_field("current_voters", |d| d.read_array(ver))?,
+            observers: d.read_field("observers", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// ReplicaState common struct of DescribeQuorumResponse
+pub struct QuorumReplicaState {
+    pub replica_id: BrokerId,
+    // The last known log end offset of the follower or -1 if it is unknown.
+    pub log_end_offset: i64,
+
+    // versions: 1+, ignorable, default=-1
+    // The last known leader wall clock time time when a follower fetched from the leader.
+    pub last_fetch_timestamp: i64,
+
+    // versions: 1+, ignorable, default=-1
+    // The leader wall clock append time of the offset for which the follower made the most
+    // recent fetch request.
+    pub last_caught_up_timestamp: i64,
+}
+
+impl Default for QuorumReplicaState {
+    fn default() -> Self {
+        QuorumReplicaState {
+            replica_id: 0,
+            log_end_offset: 0,
+            last_fetch_timestamp: -1,
+            last_caught_up_timestamp: -1,
+        }
+    }
+}
+
+builder! {
+    QuorumReplicaState => QuorumReplicaStateBuilder {
+        replica_id: BrokerId,
+        log_end_offset: i64,
+        last_fetch_timestamp: i64,
+        last_caught_up_timestamp: i64,
+    }
+}
+
+impl KafkaProtoEncodable for QuorumReplicaState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.replica_id)?;
+        s.emit_int64(self.log_end_offset)?;
+        if ver >= 1 {
+            s.emit_int64(self.last_fetch_timestamp)?;
+            s.emit_int64(self.last_caught_up_timestamp)?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for QuorumReplicaState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = QuorumReplicaState {
+            replica_id: d.read_field("replica_id", |d| d.read_int32())?,
+            log_end_offset: d.read_field("log_end_offset", |d| d.read_int64())?,
+            last_fetch_timestamp: if ver >= 1 {
+                d.read_field("last_fetch_timestamp", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            last_caught_up_timestamp: if ver >= 1 {
+                d.read_field("last_caught_up_timestamp", |d| d.read_int64())?
+            } else {
+                -1
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for DescribeQuorumResponse {
+    const API_KEY: i16 = 55;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 1;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,211 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{KafkaMessage, KafkaRequest};
+
+#[derive(Default)]
+pub struct DescribeUserScramCredentialsRequest {
+    // nullableVersions: 0+, null means all users
+    pub users: Option<Vec<DescribeUserScramCredentialsRequestUser>>,
+}
+
+builder! {
+    DescribeUserScramCredentialsRequest => DescribeUserScramCredentialsRequestBuilder {
+        users: Option<Vec<DescribeUserScramCredentialsRequestUser>>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeUserScramCredentialsRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_nullable_array(ver, self.users.as_ref().map(|v| v.iter()))?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeUserScramCredentialsRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeUserScramCredentialsRequest {
+            users: d.read_field("users", |d| d.read_nullable_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeUserScramCredentialsRequestUser {
+    pub name: String,
+}
+
+builder! {
+    DescribeUserScramCredentialsRequestUser => DescribeUserScramCredentialsRequestUserBuilder {
+        name: String,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeUserScramCredentialsRequestUser {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeUserScramCredentialsRequestUser {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = DescribeUserScramCredentialsRequestUser {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for DescribeUserScramCredentialsRequest {
+    const API_KEY: i16 = 50;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for DescribeUserScramCredentialsRequest {
+    type Response = DescribeUserScramCredentialsResponse;
+}
+
+#[derive(Default)]
+pub struct DescribeUserScramCredentialsResponse {
+    pub throttle_time_ms: i32,
+    // The message-level error code, 0 except for user authorization or infrastructure issues.
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub results: Vec<DescribeUserScramCredentialsResult>,
+}
+
+builder! {
+    DescribeUserScramCredentialsResponse => DescribeUserScramCredentialsResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        error_message: Option<String>,
+        results: Vec<DescribeUserScramCredentialsResult>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeUserScramCredentialsResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_array(ver, self.results.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeUserScramCredentialsResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeUserScramCredentialsResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+            results: d.read_field("results", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct DescribeUserScramCredentialsResult {
+    pub user: String,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub credential_infos: Vec<ScramCredentialInfo>,
+}
+
+builder! {
+    DescribeUserScramCredentialsResult => DescribeUserScramCredentialsResultBuilder {
+        user: String,
+        error_code: i16,
+        error_message: Option<String>,
+        credential_infos: Vec<ScramCredentialInfo>,
+    }
+}
+
+impl KafkaProtoEncodable for DescribeUserScramCredentialsResult {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.user)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_array(ver, self.credential_infos.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for DescribeUserScramCredentialsResult {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = DescribeUserScramCredentialsResult {
+            user: d.read_field("user", |d| d.read_string())?.to_string(),
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+            credential_infos: d.read_field("credential_infos", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct ScramCredentialInfo {
+    // The SCRAM mechanism, one of SCRAM_* constants.
+    pub mechanism: i8,
+    // The number of iterations used in the SCRAM credential.
+    pub iterations: i32,
+}
+
+builder! {
+    ScramCredentialInfo => ScramCredentialInfoBuilder {
+        mechanism: i8,
+        iterations: i32,
+    }
+}
+
+impl KafkaProtoEncodable for ScramCredentialInfo {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int8(self.mechanism)?;
+        s.emit_int32(self.iterations)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ScramCredentialInfo {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = ScramCredentialInfo {
+            mechanism: d.read_field("mechanism", |d| d.read_int8())?,
+            iterations: d.read_field("iterations", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// SCRAM mechanisms, as used by `mechanism` fields of SCRAM credential messages
+pub const SCRAM_UNKNOWN: i8 = 0;
+pub const SCRAM_SHA_256: i8 = 1;
+pub const SCRAM_SHA_512: i8 = 2;
+
+impl KafkaMessage for DescribeUserScramCredentialsResponse {
+    const API_KEY: i16 = 50;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,243 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicName};
+
+#[derive(Default)]
+pub struct EndQuorumEpochRequest {
+    pub cluster_id: Option<String>,
+    pub topics: Vec<EndQuorumEpochRequestTopic>,
+}
+
+builder! {
+    EndQuorumEpochRequest => EndQuorumEpochRequestBuilder {
+        cluster_id: Option<String>,
+        topics: Vec<EndQuorumEpochRequestTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for EndQuorumEpochRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_nullable_string(self.cluster_id.as_deref())?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for EndQuorumEpochRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = EndQuorumEpochRequest {
+            cluster_id: d
+                .read_field("cluster_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct EndQuorumEpochRequestTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<EndQuorumEpochRequestPartition>,
+}
+
+builder! {
+    EndQuorumEpochRequestTopic => EndQuorumEpochRequestTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<EndQuorumEpochRequestPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for EndQuorumEpochRequestTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for EndQuorumEpochRequestTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = EndQuorumEpochRequestTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct EndQuorumEpochRequestPartition {
+    pub partition_index: i32,
+    // The current leader ID that is resigning.
+    pub leader_id: BrokerId,
+    // The current epoch.
+    pub leader_epoch: i32,
+    // A sorted list of preferred successors to start the election.
+    pub preferred_successors: Vec<BrokerId>,
+}
+
+builder! {
+    EndQuorumEpochRequestPartition => EndQuorumEpochRequestPartitionBuilder {
+        partition_index: i32,
+        leader_id: BrokerId,
+        leader_epoch: i32,
+        preferred_successors: Vec<BrokerId>,
+    }
+}
+
+impl KafkaProtoEncodable for EndQuorumEpochRequestPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_array(ver, self.preferred_successors.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for EndQuorumEpochRequestPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = EndQuorumEpochRequestPartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            preferred_successors: d.read_field("preferred_successors", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for EndQuorumEpochRequest {
+    const API_KEY: i16 = 54;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+}
+
+impl KafkaRequest for EndQuorumEpochRequest {
+    type Response = EndQuorumEpochResponse;
+}
+
+#[derive(Default)]
+pub struct EndQuorumEpochResponse {
+    // The top level error code.
+    pub error_code: i16,
+    pub topics: Vec<EndQuorumEpochResponseTopic>,
+}
+
+builder! {
+    EndQuorumEpochResponse => EndQuorumEpochResponseBuilder {
+        error_code: i16,
+        topics: Vec<EndQuorumEpochResponseTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for EndQuorumEpochResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for EndQuorumEpochResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = EndQuorumEpochResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct EndQuorumEpochResponseTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<EndQuorumEpochResponsePartition>,
+}
+
+builder! {
+    EndQuorumEpochResponseTopic => EndQuorumEpochResponseTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<EndQuorumEpochResponsePartition>,
+    }
+}
+
+impl KafkaProtoEncodable for EndQuorumEpochResponseTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for EndQuorumEpochResponseTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = EndQuorumEpochResponseTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct EndQuorumEpochResponsePartition {
+    pub partition_index: i32,
+    pub error_code: i16,
+    // The ID of the current leader or -1 if the leader is unknown.
+    pub leader_id: BrokerId,
+    // The latest known leader epoch.
+    pub leader_epoch: i32,
+}
+
+builder! {
+    EndQuorumEpochResponsePartition => EndQuorumEpochResponsePartitionBuilder {
+        partition_index: i32,
+        error_code: i16,
+        leader_id: BrokerId,
+        leader_epoch: i32,
+    }
+}
+
+impl KafkaProtoEncodable for EndQuorumEpochResponsePartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for EndQuorumEpochResponsePartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = EndQuorumEpochResponsePartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for EndQuorumEpochResponse {
+    const API_KEY: i16 = 54;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+}
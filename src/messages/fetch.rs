@@ -0,0 +1,793 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::messages::{LeaderIdAndEpoch, SnapshotId};
+use crate::{BrokerId, KafkaMessage, KafkaRequest, ProducerId, TopicId, TopicName};
+
+pub struct FetchRequest {
+    // versions: 12+, tag: 0, ignorable, default=null
+    pub cluster_id: Option<String>,
+
+    // versions: 0-14, default=-1
+    // The broker ID of the follower, or -1 if this request is from a consumer.
+    pub replica_id: BrokerId,
+
+    // versions: 15+, tag: 1
+    pub replica_state: Option<FetchRequestReplicaState>,
+
+    pub max_wait_ms: i32,
+    pub min_bytes: i32,
+
+    // versions: 3+, ignorable, default=0x7fffffff
+    pub max_bytes: i32,
+
+    // versions: 4+, ignorable, default=0
+    // 0 for READ_UNCOMMITTED, 1 for READ_COMMITTED.
+    pub isolation_level: i8,
+
+    // versions: 7+, ignorable, default=0
+    pub session_id: i32,
+    // versions: 7+, ignorable, default=-1
+    pub session_epoch: i32,
+
+    pub topics: Vec<FetchRequestTopic>,
+
+    // versions: 7+, ignorable
+    // In an incremental fetch request, the partitions to remove.
+    pub forgotten_topics_data: Vec<FetchRequestForgottenTopic>,
+
+    // versions: 11+, ignorable, default=""
+    // Rack ID of the consumer making this request.
+    pub rack_id: String,
+}
+
+impl Default for FetchRequest {
+    fn default() -> Self {
+        FetchRequest {
+            cluster_id: None,
+            replica_id: -1,
+            replica_state: None,
+            max_wait_ms: 0,
+            min_bytes: 0,
+            max_bytes: 0x7fffffff,
+            isolation_level: 0,
+            session_id: 0,
+            session_epoch: -1,
+            topics: Vec::new(),
+            forgotten_topics_data: Vec::new(),
+            rack_id: String::new(),
+        }
+    }
+}
+
+builder! {
+    FetchRequest => FetchRequestBuilder {
+        cluster_id: Option<String>,
+        replica_id: BrokerId,
+        replica_state: Option<FetchRequestReplicaState>,
+        max_wait_ms: i32,
+        min_bytes: i32,
+        max_bytes: i32,
+        isolation_level: i8,
+        session_id: i32,
+        session_epoch: i32,
+        topics: Vec<FetchRequestTopic>,
+        forgotten_topics_data: Vec<FetchRequestForgottenTopic>,
+        rack_id: String,
+    }
+}
+
+impl KafkaProtoEncodable for FetchRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 14 {
+            s.emit_int32(self.replica_id)?;
+        } else {
+            s.check_absent_field("replica_id", ver, self.replica_id == -1)?;
+        }
+        s.emit_int32(self.max_wait_ms)?;
+        s.emit_int32(self.min_bytes)?;
+        if ver >= 3 {
+            s.emit_int32(self.max_bytes)?;
+        }
+        if ver >= 4 {
+            s.emit_int8(self.isolation_level)?;
+        }
+        if ver >= 7 {
+            s.emit_int32(self.session_id)?;
+            s.emit_int32(self.session_epoch)?;
+        }
+        s.emit_array(ver, self.topics.iter())?;
+        if ver >= 7 {
+            s.emit_array(ver, self.forgotten_topics_data.iter())?;
+        }
+        if ver >= 11 {
+            s.emit_string(&self.rack_id)?;
+        }
+
+        if ver < 15 {
+            s.check_absent_field("replica_state", ver, self.replica_state.is_none())?;
+        }
+        let mut r = s.emit_tagged_fields_hdr(
+            self.cluster_id.is_some() as usize + self.replica_state.is_some() as usize,
+        )?;
+        if self.cluster_id.is_some() {
+            r = s.emit_tagged_field(0, ver, &self.cluster_id)?;
+        }
+        if let Some(replica_state) = &self.replica_state {
+            r = s.emit_tagged_field(1, ver, replica_state)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for FetchRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = FetchRequest {
+            cluster_id: None,
+            replica_id: if ver <= 14 {
+                d.read_field("replica_id", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            replica_state: None,
+            max_wait_ms: d.read_field("max_wait_ms", |d| d.read_int32())?,
+            min_bytes: d.read_field("min_bytes", |d| d.read_int32())?,
+            max_bytes: if ver >= 3 {
+                d.read_field("max_bytes", |d| d.read_int32())?
+            } else {
+                0x7fffffff
+            },
+            isolation_level: if ver >= 4 {
+                d.read_field("isolation_level", |d| d.read_int8())?
+            } else {
+                0
+            },
+            session_id: if ver >= 7 {
+                d.read_field("session_id", |d| d.read_int32())?
+            } else {
+                0
+            },
+            session_epoch: if ver >= 7 {
+                d.read_field("session_epoch", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+            forgotten_topics_data: if ver >= 7 {
+                d.read_field("forgotten_topics_data", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            rack_id: if ver >= 11 {
+                d.read_field("rack_id", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.cluster_id = d
+                    .read_field("cluster_id", |d| d.read_nullable_string())?
+                    .map(Into::into);
+                Ok(true)
+            }
+            1 if ver >= 15 => {
+                r.replica_state = Some(FetchRequestReplicaState::deserialize(ver, d)?);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+pub struct FetchRequestReplicaState {
+    // default=-1
+    // The replica ID of the follower, or -1 if this request is from a consumer.
+    pub replica_id: BrokerId,
+    // default=-1
+    // The epoch of this follower, or -1 if not available.
+    pub replica_epoch: i64,
+}
+
+impl Default for FetchRequestReplicaState {
+    fn default() -> Self {
+        FetchRequestReplicaState {
+            replica_id: -1,
+            replica_epoch: -1,
+        }
+    }
+}
+
+builder! {
+    FetchRequestReplicaState => FetchRequestReplicaStateBuilder {
+        replica_id: BrokerId,
+        replica_epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for FetchRequestReplicaState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.replica_id)?;
+        s.emit_int64(self.replica_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchRequestReplicaState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = FetchRequestReplicaState {
+            replica_id: d.read_field("replica_id", |d| d.read_int32())?,
+            replica_epoch: d.read_field("replica_epoch", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchRequestTopic {
+    // versions: 0-12, ignorable
+    pub topic: TopicName,
+    // versions: 13+, ignorable
+    pub topic_id: TopicId,
+    pub partitions: Vec<FetchRequestPartition>,
+}
+
+builder! {
+    FetchRequestTopic => FetchRequestTopicBuilder {
+        topic: TopicName,
+        topic_id: TopicId,
+        partitions: Vec<FetchRequestPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchRequestTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 12 {
+            s.emit_string(&self.topic)?;
+        }
+        if ver >= 13 {
+            s.emit_uuid(self.topic_id.0)?;
+        }
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchRequestTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchRequestTopic {
+            topic: if ver <= 12 {
+                d.read_field("topic", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            topic_id: if ver >= 13 {
+                TopicId(d.read_field("topic_id", |d| d.read_uuid())?)
+            } else {
+                TopicId::ZERO
+            },
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+pub struct FetchRequestPartition {
+    pub partition: i32,
+
+    // versions: 9+, ignorable, default=-1
+    pub current_leader_epoch: i32,
+
+    pub fetch_offset: i64,
+
+    // versions: 12+, ignorable, default=-1
+    // The epoch of the last fetched record or -1 if there is none.
+    pub last_fetched_epoch: i32,
+
+    // versions: 5+, ignorable, default=-1
+    // The earliest available offset of the follower replica.
+    pub log_start_offset: i64,
+
+    pub partition_max_bytes: i32,
+}
+
+impl Default for FetchRequestPartition {
+    fn default() -> Self {
+        FetchRequestPartition {
+            partition: 0,
+            current_leader_epoch: -1,
+            fetch_offset: 0,
+            last_fetched_epoch: -1,
+            log_start_offset: -1,
+            partition_max_bytes: 0,
+        }
+    }
+}
+
+builder! {
+    FetchRequestPartition => FetchRequestPartitionBuilder {
+        partition: i32,
+        current_leader_epoch: i32,
+        fetch_offset: i64,
+        last_fetched_epoch: i32,
+        log_start_offset: i64,
+        partition_max_bytes: i32,
+    }
+}
+
+impl KafkaProtoEncodable for FetchRequestPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition)?;
+        if ver >= 9 {
+            s.emit_int32(self.current_leader_epoch)?;
+        }
+        s.emit_int64(self.fetch_offset)?;
+        if ver >= 12 {
+            s.emit_int32(self.last_fetched_epoch)?;
+        }
+        if ver >= 5 {
+            s.emit_int64(self.log_start_offset)?;
+        }
+        s.emit_int32(self.partition_max_bytes)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchRequestPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchRequestPartition {
+            partition: d.read_field("partition", |d| d.read_int32())?,
+            current_leader_epoch: if ver >= 9 {
+                d.read_field("current_leader_epoch", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            fetch_offset: d.read_field("fetch_offset", |d| d.read_int64())?,
+            last_fetched_epoch: if ver >= 12 {
+                d.read_field("last_fetched_epoch", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            log_start_offset: if ver >= 5 {
+                d.read_field("log_start_offset", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            partition_max_bytes: d.read_field("partition_max_bytes", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchRequestForgottenTopic {
+    // versions: 7-12, ignorable
+    pub topic: TopicName,
+    // versions: 13+, ignorable
+    pub topic_id: TopicId,
+    pub partitions: Vec<i32>,
+}
+
+builder! {
+    FetchRequestForgottenTopic => FetchRequestForgottenTopicBuilder {
+        topic: TopicName,
+        topic_id: TopicId,
+        partitions: Vec<i32>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchRequestForgottenTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 12 {
+            s.emit_string(&self.topic)?;
+        }
+        if ver >= 13 {
+            s.emit_uuid(self.topic_id.0)?;
+        }
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchRequestForgottenTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchRequestForgottenTopic {
+            topic: if ver <= 12 {
+                d.read_field("topic", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            topic_id: if ver >= 13 {
+                TopicId(d.read_field("topic_id", |d| d.read_uuid())?)
+            } else {
+                TopicId::ZERO
+            },
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for FetchRequest {
+    const API_KEY: i16 = 1;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 15;
+    const FLEXIBLE_VERSION: i16 = 12;
+}
+
+impl KafkaRequest for FetchRequest {
+    type Response = FetchResponse;
+}
+
+#[derive(Default)]
+pub struct FetchResponse {
+    // versions: 1+, ignorable
+    pub throttle_time_ms: i32,
+    // versions: 7+, ignorable
+    pub error_code: i16,
+    // versions: 7+, ignorable, default=0
+    pub session_id: i32,
+    pub responses: Vec<FetchResponseTopic>,
+}
+
+builder! {
+    FetchResponse => FetchResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        session_id: i32,
+        responses: Vec<FetchResponseTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver >= 1 {
+            s.emit_int32(self.throttle_time_ms)?;
+        }
+        if ver >= 7 {
+            s.emit_int16(self.error_code)?;
+            s.emit_int32(self.session_id)?;
+        }
+        s.emit_array(ver, self.responses.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchResponse {
+            throttle_time_ms: if ver >= 1 {
+                d.read_field("throttle_time_ms", |d| d.read_int32())?
+            } else {
+                0
+            },
+            error_code: if ver >= 7 {
+                d.read_field("error_code", |d| d.read_int16())?
+            } else {
+                0
+            },
+            session_id: if ver >= 7 {
+                d.read_field("session_id", |d| d.read_int32())?
+            } else {
+                0
+            },
+            responses: d.read_field("responses", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchResponseTopic {
+    // versions: 0-12, ignorable
+    pub topic: TopicName,
+    // versions: 13+, ignorable
+    pub topic_id: TopicId,
+    pub partitions: Vec<FetchResponsePartition>,
+}
+
+builder! {
+    FetchResponseTopic => FetchResponseTopicBuilder {
+        topic: TopicName,
+        topic_id: TopicId,
+        partitions: Vec<FetchResponsePartition>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchResponseTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 12 {
+            s.emit_string(&self.topic)?;
+        }
+        if ver >= 13 {
+            s.emit_uuid(self.topic_id.0)?;
+        }
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchResponseTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchResponseTopic {
+            topic: if ver <= 12 {
+                d.read_field("topic", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            topic_id: if ver >= 13 {
+                TopicId(d.read_field("topic_id", |d| d.read_uuid())?)
+            } else {
+                TopicId::ZERO
+            },
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+pub struct FetchResponsePartition {
+    pub partition_index: i32,
+    pub error_code: i16,
+    pub high_watermark: i64,
+
+    // versions: 4+, ignorable, default=-1
+    pub last_stable_offset: i64,
+    // versions: 5+, ignorable, default=-1
+    pub log_start_offset: i64,
+
+    // versions: 12+, tag: 0
+    // In case divergence is detected based on the `last_fetched_epoch` and
+    // `fetch_offset` in the request, this field indicates the largest epoch and
+    // its end offset such that subsequent records are known to diverge.
+    pub diverging_epoch: Option<FetchResponseEpochEndOffset>,
+    // versions: 12+, tag: 1
+    pub current_leader: Option<LeaderIdAndEpoch>,
+    // versions: 12+, tag: 2
+    // In the case of fetching an offset less than the log start offset, this is
+    // the end offset and epoch that should be used in the FetchSnapshot request.
+    pub snapshot_id: Option<SnapshotId>,
+
+    // versions: 4+, ignorable
+    // nullableVersions: 4+
+    pub aborted_transactions: Option<Vec<FetchResponseAbortedTransaction>>,
+
+    // versions: 11+, ignorable, default=-1
+    pub preferred_read_replica: BrokerId,
+
+    // Shares the buffer the response was decoded from with the bytes feature,
+    // see deserialize_response_shared
+    #[cfg(not(feature = "bytes"))]
+    pub records: Option<crate::Bytes>,
+    #[cfg(feature = "bytes")]
+    pub records: Option<bytes::Bytes>,
+}
+
+impl Default for FetchResponsePartition {
+    fn default() -> Self {
+        FetchResponsePartition {
+            partition_index: 0,
+            error_code: 0,
+            high_watermark: 0,
+            last_stable_offset: -1,
+            log_start_offset: -1,
+            diverging_epoch: None,
+            current_leader: None,
+            snapshot_id: None,
+            aborted_transactions: None,
+            preferred_read_replica: -1,
+            records: None,
+        }
+    }
+}
+
+builder! {
+    FetchResponsePartition => FetchResponsePartitionBuilder {
+        partition_index: i32,
+        error_code: i16,
+        high_watermark: i64,
+        last_stable_offset: i64,
+        log_start_offset: i64,
+        diverging_epoch: Option<FetchResponseEpochEndOffset>,
+        current_leader: Option<LeaderIdAndEpoch>,
+        snapshot_id: Option<SnapshotId>,
+        aborted_transactions: Option<Vec<FetchResponseAbortedTransaction>>,
+        preferred_read_replica: BrokerId,
+        #[cfg(not(feature = "bytes"))]
+        records: Option<crate::Bytes>,
+        #[cfg(feature = "bytes")]
+        records: Option<bytes::Bytes>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchResponsePartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int64(self.high_watermark)?;
+        if ver >= 4 {
+            s.emit_int64(self.last_stable_offset)?;
+        }
+        if ver >= 5 {
+            s.emit_int64(self.log_start_offset)?;
+        }
+        if ver >= 4 {
+            s.emit_nullable_array(ver, self.aborted_transactions.as_ref().map(|v| v.iter()))?;
+        }
+        if ver >= 11 {
+            s.emit_int32(self.preferred_read_replica)?;
+        }
+        s.emit_nullable_bytes(self.records.as_deref())?;
+
+        let mut r = s.emit_tagged_fields_hdr(
+            self.diverging_epoch.is_some() as usize
+                + self.current_leader.is_some() as usize
+                + self.snapshot_id.is_some() as usize,
+        )?;
+        if let Some(diverging_epoch) = &self.diverging_epoch {
+            r = s.emit_tagged_field(0, ver, diverging_epoch)?;
+        }
+        if let Some(current_leader) = &self.current_leader {
+            r = s.emit_tagged_field(1, ver, current_leader)?;
+        }
+        if let Some(snapshot_id) = &self.snapshot_id {
+            r = s.emit_tagged_field(2, ver, snapshot_id)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for FetchResponsePartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = FetchResponsePartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            high_watermark: d.read_field("high_watermark", |d| d.read_int64())?,
+            last_stable_offset: if ver >= 4 {
+                d.read_field("last_stable_offset", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            log_start_offset: if ver >= 5 {
+                d.read_field("log_start_offset", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            diverging_epoch: None,
+            current_leader: None,
+            snapshot_id: None,
+            aborted_transactions: if ver >= 4 {
+                d.read_field("aborted_transactions", |d| d.read_nullable_array(ver))?
+            } else {
+                None
+            },
+            preferred_read_replica: if ver >= 11 {
+                d.read_field("preferred_read_replica", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            #[cfg(not(feature = "bytes"))]
+            records: d
+                .read_field("records", |d| d.read_nullable_bytes())?
+                .map(|b| b.to_vec()),
+            #[cfg(feature = "bytes")]
+            records: d.read_field("records", |d| d.read_shared_nullable_bytes())?,
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.diverging_epoch = Some(FetchResponseEpochEndOffset::deserialize(ver, d)?);
+                Ok(true)
+            }
+            1 => {
+                r.current_leader = Some(LeaderIdAndEpoch::deserialize(ver, d)?);
+                Ok(true)
+            }
+            2 => {
+                r.snapshot_id = Some(SnapshotId::deserialize(ver, d)?);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+pub struct FetchResponseEpochEndOffset {
+    // default=-1
+    pub epoch: i32,
+    // default=-1
+    pub end_offset: i64,
+}
+
+impl Default for FetchResponseEpochEndOffset {
+    fn default() -> Self {
+        FetchResponseEpochEndOffset {
+            epoch: -1,
+            end_offset: -1,
+        }
+    }
+}
+
+builder! {
+    FetchResponseEpochEndOffset => FetchResponseEpochEndOffsetBuilder {
+        epoch: i32,
+        end_offset: i64,
+    }
+}
+
+impl KafkaProtoEncodable for FetchResponseEpochEndOffset {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.epoch)?;
+        s.emit_int64(self.end_offset)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchResponseEpochEndOffset {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = FetchResponseEpochEndOffset {
+            epoch: d.read_field("epoch", |d| d.read_int32())?,
+            end_offset: d.read_field("end_offset", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchResponseAbortedTransaction {
+    pub producer_id: ProducerId,
+    // The first offset in the aborted transaction.
+    pub first_offset: i64,
+}
+
+builder! {
+    FetchResponseAbortedTransaction => FetchResponseAbortedTransactionBuilder {
+        producer_id: ProducerId,
+        first_offset: i64,
+    }
+}
+
+impl KafkaProtoEncodable for FetchResponseAbortedTransaction {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int64(self.producer_id)?;
+        s.emit_int64(self.first_offset)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchResponseAbortedTransaction {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = FetchResponseAbortedTransaction {
+            producer_id: d.read_field("producer_id", |d| d.read_int64())?,
+            first_offset: d.read_field("first_offset", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for FetchResponse {
+    const API_KEY: i16 = 1;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 15;
+    const FLEXIBLE_VERSION: i16 = 12;
+}
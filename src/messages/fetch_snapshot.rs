@@ -0,0 +1,399 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, Bytes, KafkaMessage, KafkaRequest, TopicName};
+
+pub struct FetchSnapshotRequest {
+    // tag: 0, default=null
+    pub cluster_id: Option<String>,
+    // The broker ID of the follower, default=-1
+    pub replica_id: BrokerId,
+    // The maximum bytes to fetch from all of the snapshots, default=0x7fffffff
+    pub max_bytes: i32,
+    pub topics: Vec<FetchSnapshotRequestTopic>,
+}
+
+impl Default for FetchSnapshotRequest {
+    fn default() -> Self {
+        FetchSnapshotRequest {
+            cluster_id: None,
+            replica_id: -1,
+            max_bytes: 0x7fffffff,
+            topics: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    FetchSnapshotRequest => FetchSnapshotRequestBuilder {
+        cluster_id: Option<String>,
+        replica_id: BrokerId,
+        max_bytes: i32,
+        topics: Vec<FetchSnapshotRequestTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchSnapshotRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.replica_id)?;
+        s.emit_int32(self.max_bytes)?;
+        s.emit_array(ver, self.topics.iter())?;
+
+        let mut r = s.emit_tagged_fields_hdr(self.cluster_id.is_some() as usize)?;
+        if self.cluster_id.is_some() {
+            r = s.emit_tagged_field(0, ver, &self.cluster_id)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for FetchSnapshotRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = FetchSnapshotRequest {
+            cluster_id: None,
+            replica_id: d.read_field("replica_id", |d| d.read_int32())?,
+            max_bytes: d.read_field("max_bytes", |d| d.read_int32())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.cluster_id = d
+                    .read_field("cluster_id", |d| d.read_nullable_string())?
+                    .map(Into::into);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchSnapshotRequestTopic {
+    pub name: TopicName,
+    pub partitions: Vec<FetchSnapshotRequestPartition>,
+}
+
+builder! {
+    FetchSnapshotRequestTopic => FetchSnapshotRequestTopicBuilder {
+        name: TopicName,
+        partitions: Vec<FetchSnapshotRequestPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchSnapshotRequestTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchSnapshotRequestTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchSnapshotRequestTopic {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchSnapshotRequestPartition {
+    pub partition: i32,
+    // The current leader epoch of the partition, -1 for unknown leader epoch.
+    pub current_leader_epoch: i32,
+    // The snapshot endOffset and epoch to fetch.
+    pub snapshot_id: SnapshotId,
+    // The byte position within the snapshot to start fetching from.
+    pub position: i64,
+}
+
+builder! {
+    FetchSnapshotRequestPartition => FetchSnapshotRequestPartitionBuilder {
+        partition: i32,
+        current_leader_epoch: i32,
+        snapshot_id: SnapshotId,
+        position: i64,
+    }
+}
+
+impl KafkaProtoEncodable for FetchSnapshotRequestPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition)?;
+        s.emit_int32(self.current_leader_epoch)?;
+        self.snapshot_id.serialize(ver, s)?;
+        s.emit_int64(self.position)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchSnapshotRequestPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchSnapshotRequestPartition {
+            partition: d.read_field("partition", |d| d.read_int32())?,
+            current_leader_epoch: d.read_field("current_leader_epoch", |d| d.read_int32())?,
+            snapshot_id: SnapshotId::deserialize(ver, d)?,
+            position: d.read_field("position", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// Snapshot is identified by the end offset and the epoch of the last record it
+// contains. Shared by FetchSnapshot and Fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotId {
+    // default=-1
+    pub end_offset: i64,
+    // default=-1
+    pub epoch: i32,
+}
+
+impl Default for SnapshotId {
+    fn default() -> Self {
+        SnapshotId {
+            end_offset: -1,
+            epoch: -1,
+        }
+    }
+}
+
+builder! {
+    SnapshotId => SnapshotIdBuilder {
+        end_offset: i64,
+        epoch: i32,
+    }
+}
+
+impl KafkaProtoEncodable for SnapshotId {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int64(self.end_offset)?;
+        s.emit_int32(self.epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for SnapshotId {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = SnapshotId {
+            end_offset: d.read_field("end_offset", |d| d.read_int64())?,
+            epoch: d.read_field("epoch", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// Shared by FetchSnapshot and Fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderIdAndEpoch {
+    // default=-1
+    // The ID of the current leader or -1 if the leader is unknown.
+    pub leader_id: BrokerId,
+    // default=-1
+    // The latest known leader epoch.
+    pub leader_epoch: i32,
+}
+
+impl Default for LeaderIdAndEpoch {
+    fn default() -> Self {
+        LeaderIdAndEpoch {
+            leader_id: -1,
+            leader_epoch: -1,
+        }
+    }
+}
+
+builder! {
+    LeaderIdAndEpoch => LeaderIdAndEpochBuilder {
+        leader_id: BrokerId,
+        leader_epoch: i32,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderIdAndEpoch {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderIdAndEpoch {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = LeaderIdAndEpoch {
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for FetchSnapshotRequest {
+    const API_KEY: i16 = 59;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for FetchSnapshotRequest {
+    type Response = FetchSnapshotResponse;
+}
+
+#[derive(Default)]
+pub struct FetchSnapshotResponse {
+    pub throttle_time_ms: i32,
+    // The top level response error code.
+    pub error_code: i16,
+    pub topics: Vec<FetchSnapshotResponseTopic>,
+}
+
+builder! {
+    FetchSnapshotResponse => FetchSnapshotResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        topics: Vec<FetchSnapshotResponseTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchSnapshotResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchSnapshotResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchSnapshotResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchSnapshotResponseTopic {
+    pub name: TopicName,
+    pub partitions: Vec<FetchSnapshotResponsePartition>,
+}
+
+builder! {
+    FetchSnapshotResponseTopic => FetchSnapshotResponseTopicBuilder {
+        name: TopicName,
+        partitions: Vec<FetchSnapshotResponsePartition>,
+    }
+}
+
+impl KafkaProtoEncodable for FetchSnapshotResponseTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FetchSnapshotResponseTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = FetchSnapshotResponseTopic {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct FetchSnapshotResponsePartition {
+    pub index: i32,
+    pub error_code: i16,
+    // The snapshot endOffset and epoch fetched.
+    pub snapshot_id: SnapshotId,
+    // tag: 0
+    pub current_leader: Option<LeaderIdAndEpoch>,
+    // The total size of the snapshot.
+    pub size: i64,
+    // The starting byte position within the snapshot included in the Bytes field.
+    pub position: i64,
+    // Snapshot data in records format which may not be aligned on an offset boundary.
+    pub unaligned_records: Bytes,
+}
+
+builder! {
+    FetchSnapshotResponsePartition => FetchSnapshotResponsePartitionBuilder {
+        index: i32,
+        error_code: i16,
+        snapshot_id: SnapshotId,
+        current_leader: Option<LeaderIdAndEpoch>,
+        size: i64,
+        position: i64,
+        unaligned_records: Bytes,
+    }
+}
+
+impl KafkaProtoEncodable for FetchSnapshotResponsePartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.index)?;
+        s.emit_int16(self.error_code)?;
+        self.snapshot_id.serialize(ver, s)?;
+        s.emit_int64(self.size)?;
+        s.emit_int64(self.position)?;
+        s.emit_bytes(&self.unaligned_records)?;
+
+        let mut r = s.emit_tagged_fields_hdr(self.current_leader.is_some() as usize)?;
+        if let Some(current_leader) = &self.current_leader {
+            r = s.emit_tagged_field(0, ver, current_leader)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for FetchSnapshotResponsePartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = FetchSnapshotResponsePartition {
+            index: d.read_field("index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            snapshot_id: SnapshotId::deserialize(ver, d)?,
+            current_leader: None,
+            size: d.read_field("size", |d| d.read_int64())?,
+            position: d.read_field("position", |d| d.read_int64())?,
+            unaligned_records: d
+                .read_field("unaligned_records", |d| d.read_bytes())?
+                .to_vec(),
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.current_leader = Some(LeaderIdAndEpoch::deserialize(ver, d)?);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for FetchSnapshotResponse {
+    const API_KEY: i16 = 59;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -1,30 +1,79 @@
-use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use std::borrow::Cow;
+
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoBorrowDecodable};
 use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
 use linked_hash_map::LinkedHashMap;
 
-use crate::{Bytes, GroupId, KafkaMessage, KafkaRequest};
+use crate::{KafkaMessage, KafkaRequest};
 
-type ProtocolName = String;
+type ProtocolName<'a> = Cow<'a, str>;
 
-pub struct JoinGroupRequest /* p.name */ {
-    pub group_id: GroupId,       // f.name: f.entityType
+// Strings and bytes borrow from the buffer when decoded with
+// `deserialize_borrowed`, `into_owned` detaches the request from it.
+// JoinGroupRequest<'static> is the owned one.
+pub struct JoinGroupRequest<'a> /* p.name */ {
+    pub group_id: Cow<'a, str>,  // f.name: f.entityType
     pub session_timeout_ms: i32, // f.name: f.type
 
-    //TODO: versions: 1+, ignorable, default=-1
+    // versions: 1+, ignorable, default=-1
     pub rebalance_timeout_ms: i32,
-    pub member_id: String,
+    pub member_id: Cow<'a, str>,
 
-    //TODO: versions: 5+, nullableVersions: 5+
-    pub group_instance_id: Option<String>, // f.name: nullable=true
+    // versions: 5+, nullableVersions: 5+
+    pub group_instance_id: Option<Cow<'a, str>>, // f.name: nullable=true
 
-    pub protocol_type: String,
+    pub protocol_type: Cow<'a, str>,
 
     // f.type.startsWith("[]") + f.fields.contains(o: o.mapKey)
     // key: string
-    pub protocols: LinkedHashMap<ProtocolName, JoinGroupRequestProtocol>,
+    pub protocols: LinkedHashMap<ProtocolName<'a>, JoinGroupRequestProtocol<'a>>,
+}
+
+impl Default for JoinGroupRequest<'_> {
+    fn default() -> Self {
+        JoinGroupRequest {
+            group_id: Cow::Borrowed(""),
+            session_timeout_ms: 0,
+            rebalance_timeout_ms: -1,
+            member_id: Cow::Borrowed(""),
+            group_instance_id: None,
+            protocol_type: Cow::Borrowed(""),
+            protocols: LinkedHashMap::new(),
+        }
+    }
+}
+
+builder! {
+    JoinGroupRequest<'a> => JoinGroupRequestBuilder {
+        group_id: Cow<'a, str>,
+        session_timeout_ms: i32,
+        rebalance_timeout_ms: i32,
+        member_id: Cow<'a, str>,
+        group_instance_id: Option<Cow<'a, str>>,
+        protocol_type: Cow<'a, str>,
+        protocols: LinkedHashMap<ProtocolName<'a>, JoinGroupRequestProtocol<'a>>,
+    }
 }
 
-impl KafkaProtoEncodable for JoinGroupRequest {
+impl JoinGroupRequest<'_> {
+    pub fn into_owned(self) -> JoinGroupRequest<'static> {
+        JoinGroupRequest {
+            group_id: Cow::Owned(self.group_id.into_owned()),
+            session_timeout_ms: self.session_timeout_ms,
+            rebalance_timeout_ms: self.rebalance_timeout_ms,
+            member_id: Cow::Owned(self.member_id.into_owned()),
+            group_instance_id: self.group_instance_id.map(|v| Cow::Owned(v.into_owned())),
+            protocol_type: Cow::Owned(self.protocol_type.into_owned()),
+            protocols: self
+                .protocols
+                .into_iter()
+                .map(|(k, v)| (Cow::Owned(k.into_owned()), v.into_owned()))
+                .collect(),
+        }
+    }
+}
+
+impl KafkaProtoEncodable for JoinGroupRequest<'_> {
     fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
         s.emit_string(&self.group_id)?;
         s.emit_int32(self.session_timeout_ms)?;
@@ -37,65 +86,100 @@ impl KafkaProtoEncodable for JoinGroupRequest {
 
         if ver >= 5 {
             s.emit_nullable_string(self.group_instance_id.as_deref())?;
+        } else {
+            s.check_absent_field("group_instance_id", ver, self.group_instance_id.is_none())?;
         };
 
         s.emit_string(&self.protocol_type)?;
 
-        s.emit_array(self.protocols.values())
+        s.emit_array(ver, self.protocols.values())?;
+        s.emit_empty_tagged_fields()
     }
 }
 
-impl KafkaProtoDecodable for JoinGroupRequest {
-    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
-        Ok(JoinGroupRequest {
-            group_id: d.read_string()?.to_string(),
-            session_timeout_ms: d.read_int32()?,
-            rebalance_timeout_ms: if ver >= 1 { d.read_int32()? } else { -1 },
-            member_id: d.read_string()?.to_string(),
+impl<'a> KafkaProtoBorrowDecodable<'a> for JoinGroupRequest<'a> {
+    fn deserialize_borrowed<D: KafkaFlexibleDecoder<'a>>(
+        ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = JoinGroupRequest {
+            group_id: d.read_field("group_id", |d| d.read_string())?.into(),
+            session_timeout_ms: d.read_field("session_timeout_ms", |d| d.read_int32())?,
+            rebalance_timeout_ms: if ver >= 1 {
+                d.read_field("rebalance_timeout_ms", |d| d.read_int32())?
+            } else {
+                -1
+            },
+            member_id: d.read_field("member_id", |d| d.read_string())?.into(),
             group_instance_id: if ver >= 5 {
-                d.read_nullable_string()?.map(Into::into)
+                d.read_field("group_instance_id", |d| d.read_nullable_string())?
+                    .map(Into::into)
             } else {
                 None
             },
-            protocol_type: d.read_string()?.to_string(),
-            protocols: {
-                let len = d.read_array_hdr()?;
-                let mut protocols = LinkedHashMap::with_capacity(len);
-                for _ in 0..len {
-                    let p = JoinGroupRequestProtocol::deserialize(0, d)?;
-                    protocols.insert(p.name.clone(), p);
-                }
-                protocols
-            },
-        })
+            protocol_type: d.read_field("protocol_type", |d| d.read_string())?.into(),
+            protocols: d.read_field("protocols", |d| {
+                d.read_map(ver, |p: &JoinGroupRequestProtocol| p.name.clone())
+            })?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
     }
 }
 
-pub struct JoinGroupRequestProtocol {
-    pub name: ProtocolName,
-    pub metadata: Bytes,
+#[derive(Default)]
+pub struct JoinGroupRequestProtocol<'a> {
+    pub name: ProtocolName<'a>,
+    pub metadata: Cow<'a, [u8]>,
 }
 
-impl KafkaProtoEncodable for JoinGroupRequestProtocol {
+builder! {
+    JoinGroupRequestProtocol<'a> => JoinGroupRequestProtocolBuilder {
+        name: ProtocolName<'a>,
+        metadata: Cow<'a, [u8]>,
+    }
+}
+
+impl JoinGroupRequestProtocol<'_> {
+    pub fn into_owned(self) -> JoinGroupRequestProtocol<'static> {
+        JoinGroupRequestProtocol {
+            name: Cow::Owned(self.name.into_owned()),
+            metadata: Cow::Owned(self.metadata.into_owned()),
+        }
+    }
+}
+
+impl KafkaProtoEncodable for JoinGroupRequestProtocol<'_> {
     fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
         s.emit_string(&self.name)?;
-        s.emit_bytes(&self.metadata)
+        s.emit_bytes(&self.metadata)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl JoinGroupRequestProtocol<'_> {
+    // Metadata as a slice of `buf` the protocol was decoded from
+    pub fn shared_metadata(&self, buf: &bytes::Bytes) -> bytes::Bytes {
+        crate::de::share_bytes(buf, &self.metadata)
     }
 }
 
-impl KafkaProtoDecodable for JoinGroupRequestProtocol {
-    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+impl<'a> KafkaProtoBorrowDecodable<'a> for JoinGroupRequestProtocol<'a> {
+    fn deserialize_borrowed<D: KafkaFlexibleDecoder<'a>>(
         _ver: i16,
         d: &mut D,
     ) -> Result<Self, D::Error> {
-        Ok(JoinGroupRequestProtocol {
-            name: d.read_string()?.to_string(),
-            metadata: d.read_bytes()?.to_vec(),
-        })
+        let r = JoinGroupRequestProtocol {
+            name: d.read_field("name", |d| d.read_string())?.into(),
+            metadata: d.read_field("metadata", |d| d.read_bytes())?.into(),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
     }
 }
 
-impl KafkaMessage for JoinGroupRequest {
+impl KafkaMessage for JoinGroupRequest<'_> {
     const API_KEY: i16 = 11; // j.apiKey
 
     const MIN_API_VERSION: i16 = 0; // j.validVersions.split("-")[0]
@@ -103,7 +187,7 @@ impl KafkaMessage for JoinGroupRequest {
     const FLEXIBLE_VERSION: i16 = 6; // j.flexibleVersions[0]
 }
 
-impl KafkaRequest for JoinGroupRequest {
+impl KafkaRequest for JoinGroupRequest<'_> {
     type Response = ();
     //type Response = JoinGroupResponse;
 }
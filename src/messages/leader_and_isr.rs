@@ -0,0 +1,497 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+use linked_hash_map::LinkedHashMap;
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicId, TopicName};
+
+pub struct LeaderAndIsrRequest {
+    // The current controller ID.
+    pub controller_id: BrokerId,
+
+    // versions: 7+, default=false
+    // If KRaft controller id is used during migration. See KIP-866
+    pub is_kraft_controller: bool,
+
+    // The current controller epoch.
+    pub controller_epoch: i32,
+
+    // versions: 2+, ignorable, default=-1
+    // The current broker epoch.
+    pub broker_epoch: i64,
+
+    // versions: 5+, ignorable, default=0
+    // The type that indicates whether all topics are included in the request
+    pub type_: i8,
+
+    // versions: 0-1
+    // The state of each partition, in a v0 or v1 message.
+    pub ungrouped_partition_states: Vec<LeaderAndIsrPartitionState>,
+
+    // versions: 2+
+    // Each topic.
+    pub topic_states: Vec<LeaderAndIsrTopicState>,
+
+    // The current live leaders.
+    pub live_leaders: Vec<LeaderAndIsrLiveLeader>,
+}
+
+impl Default for LeaderAndIsrRequest {
+    fn default() -> Self {
+        LeaderAndIsrRequest {
+            controller_id: 0,
+            is_kraft_controller: false,
+            controller_epoch: 0,
+            broker_epoch: -1,
+            type_: 0,
+            ungrouped_partition_states: Vec::new(),
+            topic_states: Vec::new(),
+            live_leaders: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    LeaderAndIsrRequest => LeaderAndIsrRequestBuilder {
+        controller_id: BrokerId,
+        is_kraft_controller: bool,
+        controller_epoch: i32,
+        broker_epoch: i64,
+        type_: i8,
+        ungrouped_partition_states: Vec<LeaderAndIsrPartitionState>,
+        topic_states: Vec<LeaderAndIsrTopicState>,
+        live_leaders: Vec<LeaderAndIsrLiveLeader>,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderAndIsrRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.controller_id)?;
+        if ver >= 7 {
+            s.emit_bool(self.is_kraft_controller)?;
+        } else {
+            s.check_absent_field("is_kraft_controller", ver, !self.is_kraft_controller)?;
+        }
+        s.emit_int32(self.controller_epoch)?;
+        if ver >= 2 {
+            s.emit_int64(self.broker_epoch)?;
+        }
+        if ver >= 5 {
+            s.emit_int8(self.type_)?;
+        }
+        if ver <= 1 {
+            s.emit_array(ver, self.ungrouped_partition_states.iter())?;
+        } else {
+            s.check_absent_field(
+                "ungrouped_partition_states",
+                ver,
+                self.ungrouped_partition_states.is_empty(),
+            )?;
+        }
+        if ver >= 2 {
+            s.emit_array(ver, self.topic_states.iter())?;
+        } else {
+            s.check_absent_field("topic_states", ver, self.topic_states.is_empty())?;
+        }
+        s.emit_array(ver, self.live_leaders.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderAndIsrRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = LeaderAndIsrRequest {
+            controller_id: d.read_field("controller_id", |d| d.read_int32())?,
+            is_kraft_controller: if ver >= 7 {
+                d.read_field("is_kraft_controller", |d| d.read_bool())?
+            } else {
+                false
+            },
+            controller_epoch: d.read_field("controller_epoch", |d| d.read_int32())?,
+            broker_epoch: if ver >= 2 {
+                d.read_field("broker_epoch", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            type_: if ver >= 5 {
+                d.read_field("type_", |d| d.read_int8())?
+            } else {
+                0
+            },
+            ungrouped_partition_states: if ver <= 1 {
+                d.read_field("ungrouped_partition_states", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            topic_states: if ver >= 2 {
+                d.read_field("topic_states", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            live_leaders: d.read_field("live_leaders", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct LeaderAndIsrTopicState {
+    pub topic_name: TopicName,
+
+    // versions: 5+, ignorable
+    pub topic_id: TopicId,
+
+    pub partition_states: Vec<LeaderAndIsrPartitionState>,
+}
+
+builder! {
+    LeaderAndIsrTopicState => LeaderAndIsrTopicStateBuilder {
+        topic_name: TopicName,
+        topic_id: TopicId,
+        partition_states: Vec<LeaderAndIsrPartitionState>,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderAndIsrTopicState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        if ver >= 5 {
+            s.emit_uuid(self.topic_id.0)?;
+        }
+        s.emit_array(ver, self.partition_states.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderAndIsrTopicState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = LeaderAndIsrTopicState {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            topic_id: if ver >= 5 {
+                TopicId(d.read_field("topic_id", |d| d.read_uuid())?)
+            } else {
+                TopicId::ZERO
+            },
+            partition_states: d.read_field("partition_states", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct LeaderAndIsrLiveLeader {
+    pub broker_id: BrokerId,
+    pub host_name: String,
+    pub port: i32,
+}
+
+builder! {
+    LeaderAndIsrLiveLeader => LeaderAndIsrLiveLeaderBuilder {
+        broker_id: BrokerId,
+        host_name: String,
+        port: i32,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderAndIsrLiveLeader {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_string(&self.host_name)?;
+        s.emit_int32(self.port)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderAndIsrLiveLeader {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = LeaderAndIsrLiveLeader {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            host_name: d.read_field("host_name", |d| d.read_string())?.to_string(),
+            port: d.read_field("port", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct LeaderAndIsrPartitionState {
+    // versions: 0-1, ignorable
+    pub topic_name: TopicName,
+
+    pub partition_index: i32,
+    pub controller_epoch: i32,
+    // The broker ID of the leader.
+    pub leader: BrokerId,
+    pub leader_epoch: i32,
+    // The in-sync replica IDs.
+    pub isr: Vec<BrokerId>,
+    // The current epoch for the partition. The epoch is a monotonically
+    // increasing value which is incremented after every partition change.
+    pub partition_epoch: i32,
+    // The replica IDs.
+    pub replicas: Vec<BrokerId>,
+
+    // versions: 3+, ignorable
+    pub adding_replicas: Vec<BrokerId>,
+    // versions: 3+, ignorable
+    pub removing_replicas: Vec<BrokerId>,
+
+    // versions: 1+, ignorable, default=false
+    // Whether the replica should have existed on the broker or not.
+    pub is_new: bool,
+
+    // versions: 6+, default=0
+    // 1 if the partition is recovering from an unclean leader election; 0 otherwise.
+    pub leader_recovery_state: i8,
+}
+
+builder! {
+    LeaderAndIsrPartitionState => LeaderAndIsrPartitionStateBuilder {
+        topic_name: TopicName,
+        partition_index: i32,
+        controller_epoch: i32,
+        leader: BrokerId,
+        leader_epoch: i32,
+        isr: Vec<BrokerId>,
+        partition_epoch: i32,
+        replicas: Vec<BrokerId>,
+        adding_replicas: Vec<BrokerId>,
+        removing_replicas: Vec<BrokerId>,
+        is_new: bool,
+        leader_recovery_state: i8,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderAndIsrPartitionState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 1 {
+            s.emit_string(&self.topic_name)?;
+        }
+        s.emit_int32(self.partition_index)?;
+        s.emit_int32(self.controller_epoch)?;
+        s.emit_int32(self.leader)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_array(ver, self.isr.iter())?;
+        s.emit_int32(self.partition_epoch)?;
+        s.emit_array(ver, self.replicas.iter())?;
+        if ver >= 3 {
+            s.emit_array(ver, self.adding_replicas.iter())?;
+            s.emit_array(ver, self.removing_replicas.iter())?;
+        }
+        if ver >= 1 {
+            s.emit_bool(self.is_new)?;
+        }
+        if ver >= 6 {
+            s.emit_int8(self.leader_recovery_state)?;
+        } else {
+            s.check_absent_field(
+                "leader_recovery_state",
+                ver,
+                self.leader_recovery_state == 0,
+            )?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderAndIsrPartitionState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = LeaderAndIsrPartitionState {
+            topic_name: if ver <= 1 {
+                d.read_field("topic_name", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            controller_epoch: d.read_field("controller_epoch", |d| d.read_int32())?,
+            leader: d.read_field("leader", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            isr: d.read_field("isr", |d| d.read_array(ver))?,
+            partition_epoch: d.read_field("partition_epoch", |d| d.read_int32())?,
+            replicas: d.read_field("replicas", |d| d.read_array(ver))?,
+            adding_replicas: if ver >= 3 {
+                d.read_field("adding_replicas", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            removing_replicas: if ver >= 3 {
+                d.read_field("removing_replicas", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            is_new: if ver >= 1 {
+                d.read_field("is_new", |d| d.read_bool())?
+            } else {
+                false
+            },
+            leader_recovery_state: if ver >= 6 {
+                d.read_field("leader_recovery_state", |d| d.read_int8())?
+            } else {
+                0
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for LeaderAndIsrRequest {
+    const API_KEY: i16 = 4;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 7;
+    const FLEXIBLE_VERSION: i16 = 4;
+}
+
+impl KafkaRequest for LeaderAndIsrRequest {
+    type Response = LeaderAndIsrResponse;
+}
+
+#[derive(Default)]
+pub struct LeaderAndIsrResponse {
+    pub error_code: i16,
+
+    // versions: 0-4
+    // Each partition in v0 to v4 message.
+    pub partition_errors: Vec<LeaderAndIsrPartitionError>,
+
+    // versions: 5+
+    // key: topic_id
+    // Each topic
+    pub topics: LinkedHashMap<TopicId, LeaderAndIsrTopicError>,
+}
+
+builder! {
+    LeaderAndIsrResponse => LeaderAndIsrResponseBuilder {
+        error_code: i16,
+        partition_errors: Vec<LeaderAndIsrPartitionError>,
+        topics: LinkedHashMap<TopicId, LeaderAndIsrTopicError>,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderAndIsrResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        if ver <= 4 {
+            s.emit_array(ver, self.partition_errors.iter())?;
+        } else {
+            s.check_absent_field("partition_errors", ver, self.partition_errors.is_empty())?;
+        }
+        if ver >= 5 {
+            s.emit_array(ver, self.topics.values())?;
+        } else {
+            s.check_absent_field("topics", ver, self.topics.is_empty())?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderAndIsrResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = LeaderAndIsrResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            partition_errors: if ver <= 4 {
+                d.read_field("partition_errors", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            topics: if ver >= 5 {
+                d.read_field("topics", |d| {
+                    d.read_map(ver, |t: &LeaderAndIsrTopicError| t.topic_id)
+                })?
+            } else {
+                LinkedHashMap::new()
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct LeaderAndIsrTopicError {
+    pub topic_id: TopicId,
+    pub partition_errors: Vec<LeaderAndIsrPartitionError>,
+}
+
+builder! {
+    LeaderAndIsrTopicError => LeaderAndIsrTopicErrorBuilder {
+        topic_id: TopicId,
+        partition_errors: Vec<LeaderAndIsrPartitionError>,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderAndIsrTopicError {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(self.topic_id.0)?;
+        s.emit_array(ver, self.partition_errors.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderAndIsrTopicError {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = LeaderAndIsrTopicError {
+            topic_id: TopicId(d.read_field("topic_id", |d| d.read_uuid())?),
+            partition_errors: d.read_field("partition_errors", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct LeaderAndIsrPartitionError {
+    // versions: 0-4, ignorable
+    pub topic_name: TopicName,
+    pub partition_index: i32,
+    pub error_code: i16,
+}
+
+builder! {
+    LeaderAndIsrPartitionError => LeaderAndIsrPartitionErrorBuilder {
+        topic_name: TopicName,
+        partition_index: i32,
+        error_code: i16,
+    }
+}
+
+impl KafkaProtoEncodable for LeaderAndIsrPartitionError {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 4 {
+            s.emit_string(&self.topic_name)?;
+        }
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderAndIsrPartitionError {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = LeaderAndIsrPartitionError {
+            topic_name: if ver <= 4 {
+                d.read_field("topic_name", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for LeaderAndIsrResponse {
+    const API_KEY: i16 = 4;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 7;
+    const FLEXIBLE_VERSION: i16 = 4;
+}
@@ -1,7 +1,92 @@
+// Every message implements Default with the defaults of its schema, so it can
+// be built setting only the fields that matter, either with struct update
+// syntax, `FetchRequest { topics, ..Default::default() }`, or with its builder,
+// which starts from the same defaults:
+// `FetchRequest::builder().max_wait_ms(100).topics(topics).build()`
+macro_rules! builder {
+    ($message:ident $(<$lt:lifetime>)? => $builder:ident {
+        $($(#[$attr:meta])* $field:ident: $ty:ty,)*
+    }) => {
+        impl$(<$lt>)? $message$(<$lt>)? {
+            pub fn builder() -> $builder$(<$lt>)? {
+                $builder(Default::default())
+            }
+        }
+
+        pub struct $builder$(<$lt>)?($message$(<$lt>)?);
+
+        impl$(<$lt>)? $builder$(<$lt>)? {
+            $($(#[$attr])*
+            pub fn $field(mut self, $field: $ty) -> Self {
+                self.0.$field = $field;
+                self
+            })*
+
+            pub fn build(self) -> $message$(<$lt>)? {
+                self.0
+            }
+        }
+    };
+}
+
+pub mod allocate_producer_ids;
+pub mod alter_client_quotas;
+pub mod alter_partition;
+pub mod alter_user_scram_credentials;
+pub mod begin_quorum_epoch;
+pub mod broker_heartbeat;
+pub mod broker_registration;
+pub mod connect_protocol;
+pub mod consumer_group_describe;
+pub mod consumer_group_heartbeat;
+pub mod consumer_offsets;
+pub mod consumer_protocol;
+pub mod controlled_shutdown;
+pub mod describe_client_quotas;
+pub mod describe_cluster;
+pub mod describe_quorum;
+pub mod describe_user_scram_credentials;
+pub mod end_quorum_epoch;
+pub mod fetch;
+pub mod fetch_snapshot;
 pub mod join_group;
+pub mod leader_and_isr;
 pub mod request_header;
 pub mod response_header;
+pub mod stop_replica;
+#[cfg(test)]
+mod tests;
+pub mod transaction_log;
+pub mod unregister_broker;
+pub mod update_metadata;
+pub mod vote;
 
+pub use allocate_producer_ids::*;
+pub use alter_client_quotas::*;
+pub use alter_partition::*;
+pub use alter_user_scram_credentials::*;
+pub use begin_quorum_epoch::*;
+pub use broker_heartbeat::*;
+pub use broker_registration::*;
+pub use connect_protocol::*;
+pub use consumer_group_describe::*;
+pub use consumer_group_heartbeat::*;
+pub use consumer_offsets::*;
+pub use consumer_protocol::*;
+pub use controlled_shutdown::*;
+pub use describe_client_quotas::*;
+pub use describe_cluster::*;
+pub use describe_quorum::*;
+pub use describe_user_scram_credentials::*;
+pub use end_quorum_epoch::*;
+pub use fetch::*;
+pub use fetch_snapshot::*;
 pub use join_group::*;
+pub use leader_and_isr::*;
 pub use request_header::*;
 pub use response_header::*;
+pub use stop_replica::*;
+pub use transaction_log::*;
+pub use unregister_broker::*;
+pub use update_metadata::*;
+pub use vote::*;
@@ -1,5 +1,6 @@
 use crate::ser::KafkaProtoEncoder;
 
+#[derive(Default)]
 pub struct RequestHeader<'a> {
     pub request_api_key: i16,
     pub request_api_version: i16,
@@ -7,6 +8,15 @@ pub struct RequestHeader<'a> {
     pub client_id: &'a str,
 }
 
+builder! {
+    RequestHeader<'a> => RequestHeaderBuilder {
+        request_api_key: i16,
+        request_api_version: i16,
+        correlation_id: i32,
+        client_id: &'a str,
+    }
+}
+
 impl RequestHeader<'_> {
     pub fn serialize<S: KafkaProtoEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
         s.emit_int16(self.request_api_key)?;
@@ -14,7 +24,10 @@ impl RequestHeader<'_> {
         let mut r = s.emit_int32(self.correlation_id)?;
 
         if ver > 0 {
-            r = s.emit_string(&self.client_id)?;
+            r = s.emit_string(self.client_id)?;
+        }
+        if ver >= 2 {
+            r = s.emit_unsigned_varint(0)?; // empty tagged fields
         }
         Ok(r)
     }
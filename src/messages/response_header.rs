@@ -1,9 +1,16 @@
 use crate::{de::KafkaProtoDecoder, ser::KafkaProtoEncoder};
 
+#[derive(Default)]
 pub struct ResponseHeader {
     pub correlation_id: i32,
 }
 
+builder! {
+    ResponseHeader => ResponseHeaderBuilder {
+        correlation_id: i32,
+    }
+}
+
 impl ResponseHeader {
     pub fn serialize<S: KafkaProtoEncoder>(&self, _ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
         s.emit_int32(self.correlation_id)
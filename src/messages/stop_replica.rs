@@ -0,0 +1,387 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicName};
+
+pub struct StopReplicaRequest {
+    // The controller id.
+    pub controller_id: BrokerId,
+
+    // versions: 4+, default=false
+    // If KRaft controller id is used during migration. See KIP-866
+    pub is_kraft_controller: bool,
+
+    // The controller epoch.
+    pub controller_epoch: i32,
+
+    // versions: 1+, ignorable, default=-1
+    // The broker epoch.
+    pub broker_epoch: i64,
+
+    // versions: 0-2
+    // Whether these partitions should be deleted.
+    pub delete_partitions: bool,
+
+    // versions: 0
+    // The partitions to stop.
+    pub ungrouped_partitions: Vec<StopReplicaPartitionV0>,
+
+    // versions: 1-2
+    // The topics to stop.
+    pub topics: Vec<StopReplicaTopicV1>,
+
+    // versions: 3+
+    // Each topic.
+    pub topic_states: Vec<StopReplicaTopicState>,
+}
+
+impl Default for StopReplicaRequest {
+    fn default() -> Self {
+        StopReplicaRequest {
+            controller_id: 0,
+            is_kraft_controller: false,
+            controller_epoch: 0,
+            broker_epoch: -1,
+            delete_partitions: false,
+            ungrouped_partitions: Vec::new(),
+            topics: Vec::new(),
+            topic_states: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    StopReplicaRequest => StopReplicaRequestBuilder {
+        controller_id: BrokerId,
+        is_kraft_controller: bool,
+        controller_epoch: i32,
+        broker_epoch: i64,
+        delete_partitions: bool,
+        ungrouped_partitions: Vec<StopReplicaPartitionV0>,
+        topics: Vec<StopReplicaTopicV1>,
+        topic_states: Vec<StopReplicaTopicState>,
+    }
+}
+
+impl KafkaProtoEncodable for StopReplicaRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.controller_id)?;
+        if ver >= 4 {
+            s.emit_bool(self.is_kraft_controller)?;
+        } else {
+            s.check_absent_field("is_kraft_controller", ver, !self.is_kraft_controller)?;
+        }
+        s.emit_int32(self.controller_epoch)?;
+        if ver >= 1 {
+            s.emit_int64(self.broker_epoch)?;
+        }
+        if ver <= 2 {
+            s.emit_bool(self.delete_partitions)?;
+        } else {
+            s.check_absent_field("delete_partitions", ver, !self.delete_partitions)?;
+        }
+        if ver == 0 {
+            s.emit_array(ver, self.ungrouped_partitions.iter())?;
+        } else {
+            s.check_absent_field(
+                "ungrouped_partitions",
+                ver,
+                self.ungrouped_partitions.is_empty(),
+            )?;
+        }
+        if (1..=2).contains(&ver) {
+            s.emit_array(ver, self.topics.iter())?;
+        } else {
+            s.check_absent_field("topics", ver, self.topics.is_empty())?;
+        }
+        if ver >= 3 {
+            s.emit_array(ver, self.topic_states.iter())?;
+        } else {
+            s.check_absent_field("topic_states", ver, self.topic_states.is_empty())?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for StopReplicaRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = StopReplicaRequest {
+            controller_id: d.read_field("controller_id", |d| d.read_int32())?,
+            is_kraft_controller: if ver >= 4 {
+                d.read_field("is_kraft_controller", |d| d.read_bool())?
+            } else {
+                false
+            },
+            controller_epoch: d.read_field("controller_epoch", |d| d.read_int32())?,
+            broker_epoch: if ver >= 1 {
+                d.read_field("broker_epoch", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            delete_partitions: if ver <= 2 {
+                d.read_field("delete_partitions", |d| d.read_bool())?
+            } else {
+                false
+            },
+            ungrouped_partitions: if ver == 0 {
+                d.read_field("ungrouped_partitions", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            topics: if (1..=2).contains(&ver) {
+                d.read_field("topics", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            topic_states: if ver >= 3 {
+                d.read_field("topic_states", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct StopReplicaPartitionV0 {
+    pub topic_name: TopicName,
+    pub partition_index: i32,
+}
+
+builder! {
+    StopReplicaPartitionV0 => StopReplicaPartitionV0Builder {
+        topic_name: TopicName,
+        partition_index: i32,
+    }
+}
+
+impl KafkaProtoEncodable for StopReplicaPartitionV0 {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_int32(self.partition_index)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for StopReplicaPartitionV0 {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = StopReplicaPartitionV0 {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct StopReplicaTopicV1 {
+    pub name: TopicName,
+    pub partition_indexes: Vec<i32>,
+}
+
+builder! {
+    StopReplicaTopicV1 => StopReplicaTopicV1Builder {
+        name: TopicName,
+        partition_indexes: Vec<i32>,
+    }
+}
+
+impl KafkaProtoEncodable for StopReplicaTopicV1 {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_array(ver, self.partition_indexes.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for StopReplicaTopicV1 {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = StopReplicaTopicV1 {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            partition_indexes: d.read_field("partition_indexes", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct StopReplicaTopicState {
+    pub topic_name: TopicName,
+    pub partition_states: Vec<StopReplicaPartitionState>,
+}
+
+builder! {
+    StopReplicaTopicState => StopReplicaTopicStateBuilder {
+        topic_name: TopicName,
+        partition_states: Vec<StopReplicaPartitionState>,
+    }
+}
+
+impl KafkaProtoEncodable for StopReplicaTopicState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partition_states.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for StopReplicaTopicState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = StopReplicaTopicState {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partition_states: d.read_field("partition_states", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+pub struct StopReplicaPartitionState {
+    pub partition_index: i32,
+    // The leader epoch, default=-1
+    pub leader_epoch: i32,
+    // Whether this partition should be deleted.
+    pub delete_partition: bool,
+}
+
+impl Default for StopReplicaPartitionState {
+    fn default() -> Self {
+        StopReplicaPartitionState {
+            partition_index: 0,
+            leader_epoch: -1,
+            delete_partition: false,
+        }
+    }
+}
+
+builder! {
+    StopReplicaPartitionState => StopReplicaPartitionStateBuilder {
+        partition_index: i32,
+        leader_epoch: i32,
+        delete_partition: bool,
+    }
+}
+
+impl KafkaProtoEncodable for StopReplicaPartitionState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_bool(self.delete_partition)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for StopReplicaPartitionState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = StopReplicaPartitionState {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            delete_partition: d.read_field("delete_partition", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for StopReplicaRequest {
+    const API_KEY: i16 = 5;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 4;
+    const FLEXIBLE_VERSION: i16 = 2;
+}
+
+impl KafkaRequest for StopReplicaRequest {
+    type Response = StopReplicaResponse;
+}
+
+#[derive(Default)]
+pub struct StopReplicaResponse {
+    // The top-level error code, or 0 if there was no top-level error.
+    pub error_code: i16,
+    // The responses for each partition.
+    pub partition_errors: Vec<StopReplicaPartitionError>,
+}
+
+builder! {
+    StopReplicaResponse => StopReplicaResponseBuilder {
+        error_code: i16,
+        partition_errors: Vec<StopReplicaPartitionError>,
+    }
+}
+
+impl KafkaProtoEncodable for StopReplicaResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.partition_errors.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for StopReplicaResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = StopReplicaResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            partition_errors: d.read_field("partition_errors", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct StopReplicaPartitionError {
+    pub topic_name: TopicName,
+    pub partition_index: i32,
+    pub error_code: i16,
+}
+
+builder! {
+    StopReplicaPartitionError => StopReplicaPartitionErrorBuilder {
+        topic_name: TopicName,
+        partition_index: i32,
+        error_code: i16,
+    }
+}
+
+impl KafkaProtoEncodable for StopReplicaPartitionError {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for StopReplicaPartitionError {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = StopReplicaPartitionError {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for StopReplicaResponse {
+    const API_KEY: i16 = 5;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 4;
+    const FLEXIBLE_VERSION: i16 = 2;
+}
@@ -0,0 +1,1985 @@
+use std::borrow::Cow;
+
+use linked_hash_map::LinkedHashMap;
+
+use super::*;
+use crate::de::{deserialize_request, KafkaFlexiDeserializer, KafkaProtoDecodable};
+use crate::ser::{message_size, serialize_message, KafkaFlexiSerializer, KafkaProtoEncodable};
+use crate::{KafkaMessage, TopicId};
+
+fn join_group_request() -> JoinGroupRequest<'static> {
+    let mut protocols = LinkedHashMap::new();
+    protocols.insert(
+        Cow::Borrowed("range"),
+        JoinGroupRequestProtocol {
+            name: Cow::Borrowed("range"),
+            metadata: Cow::Owned(vec![1, 2, 3]),
+        },
+    );
+    JoinGroupRequest {
+        group_id: Cow::Borrowed("group"),
+        session_timeout_ms: 10_000,
+        rebalance_timeout_ms: 30_000,
+        member_id: Cow::Borrowed("member"),
+        group_instance_id: Some(Cow::Borrowed("instance")),
+        protocol_type: Cow::Borrowed("consumer"),
+        protocols,
+    }
+}
+
+// Whether `v` points into `buf`. Clones of borrowed Cows stay borrowed.
+fn borrows_from<T>(buf: &[u8], v: Cow<'_, T>) -> bool
+where
+    T: ToOwned + AsRef<[u8]> + ?Sized,
+{
+    match v {
+        Cow::Borrowed(v) => buf.as_ptr_range().contains(&v.as_ref().as_ptr()),
+        Cow::Owned(_) => false,
+    }
+}
+
+fn encode<M: KafkaProtoEncodable + KafkaMessage>(m: &M, ver: i16) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let flexible = ver >= M::FLEXIBLE_VERSION;
+    m.serialize(ver, &mut KafkaFlexiSerializer::new(flexible, &mut buf))
+        .unwrap();
+    buf
+}
+
+// Body of `m` at version `ver` is `expected`, which decodes to the same
+// message: it takes the whole buffer and encodes to the same bytes
+fn check_codec<M>(m: &M, ver: i16, expected: &[u8])
+where
+    M: KafkaProtoEncodable + KafkaProtoDecodable + KafkaMessage,
+{
+    assert_eq!(encode(m, ver), expected, "version {}", ver);
+
+    let mut d = KafkaFlexiDeserializer::new(ver >= M::FLEXIBLE_VERSION, expected);
+    let decoded = M::deserialize(ver, &mut d).unwrap();
+    assert_eq!(d.de.position(), expected.len(), "version {}", ver);
+    assert_eq!(encode(&decoded, ver), expected, "version {}", ver);
+}
+
+// Same for payloads prefixed with their version, like group protocol metadata
+// or records of internal topics
+fn check_payload<M>(
+    m: &M,
+    ver: i16,
+    expected: &[u8],
+    to_bytes: impl Fn(&M, i16) -> crate::error::Result<Vec<u8>>,
+    from_bytes: impl Fn(&[u8]) -> crate::error::Result<M>,
+) {
+    assert_eq!(to_bytes(m, ver).unwrap(), expected, "version {}", ver);
+
+    let decoded = from_bytes(expected).unwrap();
+    assert_eq!(
+        to_bytes(&decoded, ver).unwrap(),
+        expected,
+        "version {}",
+        ver
+    );
+}
+
+#[test]
+fn join_group_request_borrows_from_buffer() {
+    // Non-flexible and flexible (compact strings) versions
+    for ver in [5, 7] {
+        let mut buf = Vec::new();
+        serialize_message(&join_group_request(), ver, "client", &mut buf).unwrap();
+
+        let (hdr, r) = deserialize_request::<JoinGroupRequest>(&buf).unwrap();
+        assert_eq!(hdr.client_id, "client");
+        assert!(borrows_from(&buf, r.group_id.clone()));
+        assert!(borrows_from(&buf, r.member_id.clone()));
+        assert!(borrows_from(&buf, r.group_instance_id.clone().unwrap()));
+        assert!(borrows_from(&buf, r.protocol_type.clone()));
+        let (key, protocol) = r.protocols.iter().next().unwrap();
+        assert!(borrows_from(&buf, key.clone()));
+        assert!(borrows_from(&buf, protocol.name.clone()));
+        assert!(borrows_from(&buf, protocol.metadata.clone()));
+
+        assert_eq!(r.group_id, "group");
+        assert_eq!(r.group_instance_id.as_deref(), Some("instance"));
+        assert_eq!(*protocol.metadata, [1, 2, 3]);
+
+        // Owned copy outlives the buffer
+        let owned = r.into_owned();
+        drop(buf);
+        assert!(matches!(owned.group_id, Cow::Owned(_)));
+        assert_eq!(owned.member_id, "member");
+        assert_eq!(owned.protocols["range"].name, "range");
+    }
+}
+
+#[test]
+fn ignorable_field_is_dropped_by_older_versions() {
+    let with_cluster_id = FetchRequest {
+        cluster_id: Some("cluster".to_string()),
+        ..Default::default()
+    };
+    let mut expected = Vec::new();
+    serialize_message(&FetchRequest::default(), 11, "c", &mut expected).unwrap();
+    let mut buf = Vec::new();
+    serialize_message(&with_cluster_id, 11, "c", &mut buf).unwrap();
+    assert_eq!(buf, expected);
+    assert_eq!(
+        message_size(&with_cluster_id, 11, "c").unwrap(),
+        expected.len()
+    );
+
+    // Non-ignorable field is rejected
+    let with_replica_state = FetchRequest {
+        replica_state: Some(FetchRequestReplicaState::default()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        serialize_message(&with_replica_state, 14, "c", &mut Vec::new()),
+        Err(crate::error::Error::UnsupportedFieldForVersion {
+            field: "replica_state",
+            version: 14
+        })
+    ));
+}
+
+#[test]
+fn defaults_follow_schema() {
+    let r = FetchRequestReplicaState::default();
+    assert_eq!((r.replica_id, r.replica_epoch), (-1, -1));
+    let e = FetchResponseEpochEndOffset::default();
+    assert_eq!((e.epoch, e.end_offset), (-1, -1));
+    assert_eq!(
+        SnapshotId::default(),
+        SnapshotId {
+            end_offset: -1,
+            epoch: -1
+        }
+    );
+    assert_eq!(
+        LeaderIdAndEpoch::default(),
+        LeaderIdAndEpoch {
+            leader_id: -1,
+            leader_epoch: -1
+        }
+    );
+
+    let p = FetchResponsePartition::default();
+    assert_eq!((p.last_stable_offset, p.log_start_offset), (-1, -1));
+    assert_eq!(p.preferred_read_replica, -1);
+    assert_eq!(JoinGroupRequest::default().rebalance_timeout_ms, -1);
+}
+
+#[test]
+fn builders_start_from_defaults() {
+    let r = ControlledShutdownRequest::builder().broker_id(3).build();
+    assert_eq!((r.broker_id, r.broker_epoch), (3, -1));
+
+    let p = FetchResponsePartition::builder()
+        .partition_index(1)
+        .high_watermark(10)
+        .build();
+    assert_eq!((p.partition_index, p.high_watermark), (1, 10));
+    assert_eq!((p.last_stable_offset, p.log_start_offset), (-1, -1));
+    assert_eq!(p.preferred_read_replica, -1);
+
+    let r = JoinGroupRequest::builder()
+        .group_id(Cow::Borrowed("group"))
+        .build();
+    assert_eq!(r.group_id, "group");
+    assert_eq!(r.rebalance_timeout_ms, -1);
+}
+
+fn quota_entity(name: Option<&str>) -> ClientQuotaEntity {
+    ClientQuotaEntity {
+        entity_type: "u".to_string(),
+        entity_name: name.map(str::to_string),
+    }
+}
+
+#[test]
+fn alter_client_quotas_codec() {
+    let r = AlterClientQuotasRequest {
+        entries: vec![AlterClientQuotasRequestEntry {
+            entity: vec![quota_entity(Some("a"))],
+            ops: vec![AlterClientQuotasRequestOp {
+                key: "k".to_string(),
+                value: 1.5,
+                remove: false,
+            }],
+        }],
+        validate_only: true,
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 1, // entries
+            0, 0, 0, 1, 0, 1, b'u', 0, 1, b'a', // entity
+            0, 0, 0, 1, 0, 1, b'k', 0x3f, 0xf8, 0, 0, 0, 0, 0, 0, 0, // ops
+            1, // validate_only
+        ],
+    );
+    check_codec(
+        &r,
+        1,
+        &[
+            2, // entries
+            2, 2, b'u', 2, b'a', 0, // entity
+            2, 2, b'k', 0x3f, 0xf8, 0, 0, 0, 0, 0, 0, 0, 0, // ops
+            0, // entry tagged fields
+            1, 0,
+        ],
+    );
+
+    let r = AlterClientQuotasResponse {
+        throttle_time_ms: 10,
+        entries: vec![AlterClientQuotasResponseEntry {
+            error_code: 0,
+            error_message: None,
+            entity: vec![quota_entity(None)],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 10, // throttle_time_ms
+            0, 0, 0, 1, 0, 0, 0xff, 0xff, // entries
+            0, 0, 0, 1, 0, 1, b'u', 0xff, 0xff, // entity
+        ],
+    );
+    check_codec(
+        &r,
+        1,
+        &[
+            0, 0, 0, 10, // throttle_time_ms
+            2, 0, 0, 0, // entries
+            2, 2, b'u', 0, 0, // entity
+            0, 0,
+        ],
+    );
+}
+
+#[test]
+fn describe_client_quotas_codec() {
+    let r = DescribeClientQuotasRequest {
+        components: vec![DescribeClientQuotasRequestComponent {
+            entity_type: "u".to_string(),
+            match_type: MATCH_TYPE_EXACT,
+            match_: Some("a".to_string()),
+        }],
+        strict: false,
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 1, 0, 1, b'u', 0, 0, 1, b'a', // components
+            0,    // strict
+        ],
+    );
+    check_codec(
+        &r,
+        1,
+        &[
+            2, 2, b'u', 0, 2, b'a', 0, // components
+            0, 0,
+        ],
+    );
+
+    let r = DescribeClientQuotasResponse {
+        entries: Some(vec![DescribeClientQuotasResponseEntry {
+            entity: vec![quota_entity(Some("a"))],
+            values: vec![DescribeClientQuotasResponseValue {
+                key: "k".to_string(),
+                value: 1.5,
+            }],
+        }]),
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 0, 0, 0, 0xff, 0xff, // throttle_time_ms, error
+            0, 0, 0, 1, // entries
+            0, 0, 0, 1, 0, 1, b'u', 0, 1, b'a', // entity
+            0, 0, 0, 1, 0, 1, b'k', 0x3f, 0xf8, 0, 0, 0, 0, 0, 0, // values
+        ],
+    );
+    check_codec(
+        &r,
+        1,
+        &[
+            0, 0, 0, 0, 0, 0, 0, // throttle_time_ms, error
+            2, // entries
+            2, 2, b'u', 2, b'a', 0, // entity
+            2, 2, b'k', 0x3f, 0xf8, 0, 0, 0, 0, 0, 0, 0, // values
+            0, 0,
+        ],
+    );
+
+    // Null entries
+    let r = DescribeClientQuotasResponse::default();
+    check_codec(
+        &r,
+        0,
+        &[0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    );
+    check_codec(&r, 1, &[0, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn alter_user_scram_credentials_codec() {
+    let r = AlterUserScramCredentialsRequest {
+        deletions: vec![ScramCredentialDeletion {
+            name: "a".to_string(),
+            mechanism: SCRAM_SHA_256,
+        }],
+        upsertions: vec![ScramCredentialUpsertion {
+            name: "b".to_string(),
+            mechanism: SCRAM_SHA_512,
+            iterations: 4096,
+            salt: vec![1],
+            salted_password: vec![2, 3],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            2, 2, b'a', 1, 0, // deletions
+            2, 2, b'b', 2, 0, 0, 0x10, 0, 2, 1, 3, 2, 3, 0, // upsertions
+            0,
+        ],
+    );
+
+    let r = AlterUserScramCredentialsResponse {
+        throttle_time_ms: 0,
+        results: vec![AlterUserScramCredentialsResult {
+            user: "a".to_string(),
+            error_code: 0,
+            error_message: None,
+        }],
+    };
+    check_codec(&r, 0, &[0, 0, 0, 0, 2, 2, b'a', 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn describe_user_scram_credentials_codec() {
+    let r = DescribeUserScramCredentialsRequest {
+        users: Some(vec![DescribeUserScramCredentialsRequestUser {
+            name: "a".to_string(),
+        }]),
+    };
+    check_codec(&r, 0, &[2, 2, b'a', 0, 0]);
+    check_codec(&DescribeUserScramCredentialsRequest::default(), 0, &[0, 0]);
+
+    let r = DescribeUserScramCredentialsResponse {
+        results: vec![DescribeUserScramCredentialsResult {
+            user: "a".to_string(),
+            error_code: 0,
+            error_message: None,
+            credential_infos: vec![ScramCredentialInfo {
+                mechanism: SCRAM_SHA_256,
+                iterations: 4096,
+            }],
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 0, 0, 0, 0, // throttle_time_ms, error
+            2, 2, b'a', 0, 0, 0, // results
+            2, 1, 0, 0, 0x10, 0, 0, // credential_infos
+            0, 0,
+        ],
+    );
+}
+
+#[test]
+fn begin_quorum_epoch_codec() {
+    let r = BeginQuorumEpochRequest {
+        cluster_id: Some("c".to_string()),
+        topics: vec![BeginQuorumEpochRequestTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![BeginQuorumEpochRequestPartition {
+                partition_index: 0,
+                leader_id: 1,
+                leader_epoch: 2,
+            }],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 1, b'c', // cluster_id
+            0, 0, 0, 1, 0, 1, b't', // topics
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, // partitions
+        ],
+    );
+
+    let r = BeginQuorumEpochResponse {
+        error_code: 0,
+        topics: vec![BeginQuorumEpochResponseTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![BeginQuorumEpochResponsePartition {
+                partition_index: 0,
+                error_code: 0,
+                leader_id: 1,
+                leader_epoch: 2,
+            }],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, // error_code
+            0, 0, 0, 1, 0, 1, b't', // topics
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, // partitions
+        ],
+    );
+}
+
+#[test]
+fn end_quorum_epoch_codec() {
+    let r = EndQuorumEpochRequest {
+        cluster_id: None,
+        topics: vec![EndQuorumEpochRequestTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![EndQuorumEpochRequestPartition {
+                partition_index: 0,
+                leader_id: 1,
+                leader_epoch: 2,
+                preferred_successors: vec![3],
+            }],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0xff, 0xff, // cluster_id
+            0, 0, 0, 1, 0, 1, b't', // topics
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, // partitions
+            0, 0, 0, 1, 0, 0, 0, 3, // preferred_successors
+        ],
+    );
+
+    let r = EndQuorumEpochResponse {
+        error_code: 0,
+        topics: vec![EndQuorumEpochResponseTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![EndQuorumEpochResponsePartition {
+                partition_index: 0,
+                error_code: 0,
+                leader_id: 1,
+                leader_epoch: 2,
+            }],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, // error_code
+            0, 0, 0, 1, 0, 1, b't', // topics
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, // partitions
+        ],
+    );
+}
+
+#[test]
+fn vote_codec() {
+    let r = VoteRequest {
+        cluster_id: Some("c".to_string()),
+        topics: vec![VoteRequestTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![VoteRequestPartition {
+                partition_index: 0,
+                candidate_epoch: 2,
+                candidate_id: 1,
+                last_offset_epoch: 1,
+                last_offset: 10,
+            }],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            2, b'c', // cluster_id
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 1, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, 0, // last_offset
+            0, 0,
+        ],
+    );
+
+    let r = VoteResponse {
+        error_code: 0,
+        topics: vec![VoteResponseTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![VoteResponsePartition {
+                partition_index: 0,
+                error_code: 0,
+                leader_id: 1,
+                leader_epoch: 2,
+                vote_granted: true,
+            }],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, // error_code
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, 1, 0, // partitions
+            0, 0,
+        ],
+    );
+}
+
+#[test]
+fn describe_quorum_codec() {
+    let r = DescribeQuorumRequest {
+        topics: vec![DescribeQuorumRequestTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![DescribeQuorumRequestPartition { partition_index: 0 }],
+        }],
+    };
+    for ver in [0, 1] {
+        check_codec(&r, ver, &[2, 2, b't', 2, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    let r = DescribeQuorumResponse {
+        error_code: 0,
+        topics: vec![DescribeQuorumResponseTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![DescribeQuorumResponsePartition {
+                partition_index: 0,
+                error_code: 0,
+                leader_id: 1,
+                leader_epoch: 2,
+                high_watermark: 10,
+                current_voters: vec![QuorumReplicaState {
+                    replica_id: 1,
+                    log_end_offset: 10,
+                    last_fetch_timestamp: 5,
+                    last_caught_up_timestamp: 6,
+                }],
+                observers: Vec::new(),
+            }],
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, // error_code
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, // high_watermark
+            2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 10, 0, // current_voters
+            1, // observers
+            0, 0, 0,
+        ],
+    );
+    check_codec(
+        &r,
+        1,
+        &[
+            0, 0, // error_code
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, // high_watermark
+            2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 10, // current_voters
+            0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 6, 0, // timestamps
+            1, // observers
+            0, 0, 0,
+        ],
+    );
+}
+
+#[test]
+fn fetch_snapshot_codec() {
+    let r = FetchSnapshotRequest {
+        cluster_id: Some("c".to_string()),
+        replica_id: 1,
+        topics: vec![FetchSnapshotRequestTopic {
+            name: "t".to_string(),
+            partitions: vec![FetchSnapshotRequestPartition {
+                partition: 0,
+                current_leader_epoch: 2,
+                snapshot_id: SnapshotId {
+                    end_offset: 10,
+                    epoch: 2,
+                },
+                position: 0,
+            }],
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 1, 0x7f, 0xff, 0xff, 0xff, // replica_id, max_bytes
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 2, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, 0, 0, 0, 2, 0, // snapshot_id
+            0, 0, 0, 0, 0, 0, 0, 0, 0, // position
+            0, // topic tagged fields
+            1, 0, 2, 2, b'c', // cluster_id
+        ],
+    );
+
+    let r = FetchSnapshotResponse {
+        topics: vec![FetchSnapshotResponseTopic {
+            name: "t".to_string(),
+            partitions: vec![FetchSnapshotResponsePartition {
+                index: 0,
+                error_code: 0,
+                snapshot_id: SnapshotId {
+                    end_offset: 10,
+                    epoch: 2,
+                },
+                current_leader: Some(LeaderIdAndEpoch {
+                    leader_id: 1,
+                    leader_epoch: 2,
+                }),
+                size: 3,
+                position: 0,
+                unaligned_records: vec![1, 2, 3],
+            }],
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, 0, 0, 0, 2, 0, // snapshot_id
+            0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, // size, position
+            4, 1, 2, 3, // unaligned_records
+            1, 0, 9, 0, 0, 0, 1, 0, 0, 0, 2, 0, // current_leader
+            0, 0,
+        ],
+    );
+}
+
+fn fetch_request(ver: i16) -> FetchRequest {
+    FetchRequest {
+        cluster_id: (ver >= 12).then_some("c".to_string()),
+        replica_id: if ver >= 15 { -1 } else { 1 },
+        replica_state: (ver >= 15).then_some(FetchRequestReplicaState {
+            replica_id: 1,
+            replica_epoch: 2,
+        }),
+        max_wait_ms: 500,
+        min_bytes: 1,
+        topics: vec![FetchRequestTopic {
+            topic: "t".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partitions: vec![FetchRequestPartition {
+                fetch_offset: 100,
+                partition_max_bytes: 1 << 20,
+                ..Default::default()
+            }],
+        }],
+        ..Default::default()
+    }
+}
+
+fn fetch_response(ver: i16) -> FetchResponse {
+    FetchResponse {
+        responses: vec![FetchResponseTopic {
+            topic: "t".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partitions: vec![FetchResponsePartition {
+                high_watermark: 10,
+                aborted_transactions: Some(vec![FetchResponseAbortedTransaction {
+                    producer_id: 5,
+                    first_offset: 6,
+                }]),
+                current_leader: (ver >= 12).then_some(LeaderIdAndEpoch {
+                    leader_id: 1,
+                    leader_epoch: 2,
+                }),
+                snapshot_id: (ver >= 15).then_some(SnapshotId {
+                    end_offset: 10,
+                    epoch: 2,
+                }),
+                records: Some((1..=3).collect()),
+                ..Default::default()
+            }],
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn fetch_codec() {
+    check_codec(
+        &fetch_request(0),
+        0,
+        &[
+            0, 0, 0, 1, 0, 0, 1, 0xf4, 0, 0, 0, 1, // replica_id, max_wait_ms, min_bytes
+            0, 0, 0, 1, 0, 1, b't', // topics
+            0, 0, 0, 1, 0, 0, 0, 0, // partitions
+            0, 0, 0, 0, 0, 0, 0, 100, 0, 0x10, 0, 0, // fetch_offset, partition_max_bytes
+        ],
+    );
+    check_codec(
+        &fetch_request(12),
+        12,
+        &[
+            0, 0, 0, 1, 0, 0, 1, 0xf4, 0, 0, 0, 1, // replica_id, max_wait_ms, min_bytes
+            0x7f, 0xff, 0xff, 0xff, 0, // max_bytes, isolation_level
+            0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, // session_id, session_epoch
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, // partitions
+            0, 0, 0, 0, 0, 0, 0, 100, 0xff, 0xff, 0xff, 0xff, // fetch_offset, epoch
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // log_start_offset
+            0, 0x10, 0, 0, 0, // partition_max_bytes
+            0, // topic tagged fields
+            1, 1, // forgotten_topics_data, rack_id
+            1, 0, 2, 2, b'c', // cluster_id
+        ],
+    );
+    check_codec(
+        &fetch_request(15),
+        15,
+        &[
+            0, 0, 1, 0xf4, 0, 0, 0, 1, // max_wait_ms, min_bytes
+            0x7f, 0xff, 0xff, 0xff, 0, // max_bytes, isolation_level
+            0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, // session_id, session_epoch
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // topics
+            2, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, // partitions
+            0, 0, 0, 0, 0, 0, 0, 100, 0xff, 0xff, 0xff, 0xff, // fetch_offset, epoch
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // log_start_offset
+            0, 0x10, 0, 0, 0, // partition_max_bytes
+            0, // topic tagged fields
+            1, 1, // forgotten_topics_data, rack_id
+            2, 0, 2, 2, b'c', // cluster_id
+            1, 13, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, // replica_state
+        ],
+    );
+
+    check_codec(
+        &fetch_response(0),
+        0,
+        &[
+            0, 0, 0, 1, 0, 1, b't', // responses
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, // high_watermark
+            0, 0, 0, 3, 1, 2, 3, // records
+        ],
+    );
+    check_codec(
+        &fetch_response(12),
+        12,
+        &[
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code, session_id
+            2, 2, b't', // responses
+            2, 0, 0, 0, 0, 0, 0, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, // high_watermark
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // last_stable_offset
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // log_start_offset
+            2, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 6, 0, // aborted_transactions
+            0xff, 0xff, 0xff, 0xff, // preferred_read_replica
+            4, 1, 2, 3, // records
+            1, 1, 9, 0, 0, 0, 1, 0, 0, 0, 2, 0, // current_leader
+            0, 0,
+        ],
+    );
+    check_codec(
+        &fetch_response(15),
+        15,
+        &[
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code, session_id
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // responses
+            2, 0, 0, 0, 0, 0, 0, // partitions
+            0, 0, 0, 0, 0, 0, 0, 10, // high_watermark
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // last_stable_offset
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // log_start_offset
+            2, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 6, 0, // aborted_transactions
+            0xff, 0xff, 0xff, 0xff, // preferred_read_replica
+            4, 1, 2, 3, // records
+            2, 1, 9, 0, 0, 0, 1, 0, 0, 0, 2, 0, // current_leader
+            2, 13, 0, 0, 0, 0, 0, 0, 0, 10, 0, 0, 0, 2, 0, // snapshot_id
+            0, 0,
+        ],
+    );
+}
+
+#[test]
+fn allocate_producer_ids_codec() {
+    let r = AllocateProducerIdsRequest {
+        broker_id: 1,
+        broker_epoch: 2,
+    };
+    check_codec(&r, 0, &[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0]);
+
+    let r = AllocateProducerIdsResponse {
+        producer_id_start: 1000,
+        producer_id_len: 10,
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code
+            0, 0, 0, 0, 0, 0, 0x03, 0xe8, 0, 0, 0, 10, // producer_id_start, len
+            0,
+        ],
+    );
+}
+
+#[test]
+fn alter_partition_codec() {
+    let r = AlterPartitionRequest {
+        broker_id: 1,
+        broker_epoch: 2,
+        topics: vec![AlterPartitionRequestTopic {
+            topic_name: "t".to_string(),
+            partitions: vec![AlterPartitionRequestPartition {
+                partition_index: 0,
+                leader_epoch: 3,
+                new_isr: vec![1, 2],
+                partition_epoch: 4,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, // broker_id, broker_epoch
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 3, // partitions
+            3, 0, 0, 0, 1, 0, 0, 0, 2, // new_isr
+            0, 0, 0, 4, 0, // partition_epoch
+            0, 0,
+        ],
+    );
+
+    let r = AlterPartitionRequest {
+        broker_id: 1,
+        broker_epoch: 2,
+        topics: vec![AlterPartitionRequestTopic {
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partitions: vec![AlterPartitionRequestPartition {
+                partition_index: 0,
+                leader_epoch: 3,
+                new_isr_with_epochs: vec![AlterPartitionRequestBrokerState {
+                    broker_id: 1,
+                    broker_epoch: 5,
+                }],
+                leader_recovery_state: 1,
+                partition_epoch: 4,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+    check_codec(
+        &r,
+        3,
+        &[
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, // broker_id, broker_epoch
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 3, // partitions
+            2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 5, 0, // new_isr_with_epochs
+            1, 0, 0, 0, 4, 0, // leader_recovery_state, partition_epoch
+            0, 0,
+        ],
+    );
+
+    let r = AlterPartitionResponse {
+        topics: vec![AlterPartitionResponseTopic {
+            topic_name: "t".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partitions: vec![AlterPartitionResponsePartition {
+                partition_index: 0,
+                error_code: 0,
+                leader_id: 1,
+                leader_epoch: 3,
+                isr: vec![1],
+                leader_recovery_state: 1,
+                partition_epoch: 4,
+            }],
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code
+            2, 2, b't', // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 3, // partitions
+            2, 0, 0, 0, 1, // isr
+            0, 0, 0, 4, 0, // partition_epoch
+            0, 0,
+        ],
+    );
+    check_codec(
+        &r,
+        3,
+        &[
+            0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // topics
+            2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 3, // partitions
+            2, 0, 0, 0, 1, // isr
+            1, 0, 0, 0, 4, 0, // leader_recovery_state, partition_epoch
+            0, 0,
+        ],
+    );
+}
+
+#[test]
+fn broker_heartbeat_codec() {
+    let r = BrokerHeartbeatRequest {
+        broker_id: 1,
+        broker_epoch: 2,
+        current_metadata_offset: 10,
+        want_fence: true,
+        ..Default::default()
+    };
+    let fields = [
+        0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, // broker_id, broker_epoch
+        0, 0, 0, 0, 0, 0, 0, 10, 1, 0, // offset, want_fence, want_shut_down
+    ];
+    check_codec(&r, 0, &[&fields[..], &[0]].concat());
+
+    let r = BrokerHeartbeatRequest {
+        offline_log_dirs: vec![uuid::Uuid::from_u128(1)],
+        ..r
+    };
+    check_codec(
+        &r,
+        1,
+        &[
+            &fields[..],
+            &[1, 0, 17, 2], // offline_log_dirs
+            &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+        ]
+        .concat(),
+    );
+
+    let r = BrokerHeartbeatResponse::default();
+    for ver in [0, 1] {
+        check_codec(&r, ver, &[0, 0, 0, 0, 0, 0, 0, 1, 0, 0]);
+    }
+}
+
+#[test]
+fn broker_registration_codec() {
+    let r = BrokerRegistrationRequest {
+        broker_id: 1,
+        cluster_id: "c".to_string(),
+        incarnation_id: uuid::Uuid::from_u128(2),
+        listeners: std::iter::once((
+            "l".to_string(),
+            BrokerRegistrationListener {
+                name: "l".to_string(),
+                host: "h".to_string(),
+                port: 9092,
+                security_protocol: 0,
+            },
+        ))
+        .collect(),
+        features: std::iter::once((
+            "f".to_string(),
+            BrokerRegistrationFeature {
+                name: "f".to_string(),
+                min_supported_version: 0,
+                max_supported_version: 1,
+            },
+        ))
+        .collect(),
+        ..Default::default()
+    };
+    let fields = [
+        0, 0, 0, 1, 2, b'c', // broker_id, cluster_id
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, // incarnation_id
+        2, 2, b'l', 2, b'h', 0x23, 0x84, 0, 0, 0, // listeners
+        2, 2, b'f', 0, 0, 0, 1, 0, // features
+        0, // rack
+    ];
+    check_codec(&r, 0, &[&fields[..], &[0]].concat());
+
+    let r = BrokerRegistrationRequest {
+        log_dirs: vec![uuid::Uuid::from_u128(3)],
+        previous_broker_epoch: 5,
+        ..r
+    };
+    check_codec(
+        &r,
+        3,
+        &[
+            &fields[..],
+            &[0, 2], // is_migrating_zk_broker, log_dirs
+            &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3],
+            &[0, 0, 0, 0, 0, 0, 0, 5, 0], // previous_broker_epoch
+        ]
+        .concat(),
+    );
+
+    let r = BrokerRegistrationResponse {
+        broker_epoch: 7,
+        ..Default::default()
+    };
+    for ver in [0, 3] {
+        check_codec(&r, ver, &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 0]);
+    }
+}
+
+#[test]
+fn describe_cluster_codec() {
+    let r = DescribeClusterRequest {
+        include_cluster_authorized_operations: true,
+        ..Default::default()
+    };
+    check_codec(&r, 0, &[1, 0]);
+    let r = DescribeClusterRequest {
+        endpoint_type: 2,
+        ..r
+    };
+    check_codec(&r, 1, &[1, 2, 0]);
+
+    let r = DescribeClusterResponse {
+        cluster_id: "c".to_string(),
+        controller_id: 1,
+        brokers: std::iter::once((
+            1,
+            DescribeClusterBroker {
+                broker_id: 1,
+                host: "h".to_string(),
+                port: 9092,
+                rack: None,
+            },
+        ))
+        .collect(),
+        ..Default::default()
+    };
+    let brokers = [
+        2, b'c', 0, 0, 0, 1, // cluster_id, controller_id
+        2, 0, 0, 0, 1, 2, b'h', 0, 0, 0x23, 0x84, 0, 0, // brokers
+        0x80, 0, 0, 0, 0, // cluster_authorized_operations
+    ];
+    check_codec(&r, 0, &[&[0, 0, 0, 0, 0, 0, 0], &brokers[..]].concat());
+    let r = DescribeClusterResponse {
+        endpoint_type: 2,
+        ..r
+    };
+    check_codec(&r, 1, &[&[0, 0, 0, 0, 0, 0, 0, 2], &brokers[..]].concat());
+}
+
+#[test]
+fn unregister_broker_codec() {
+    check_codec(
+        &UnregisterBrokerRequest { broker_id: 1 },
+        0,
+        &[0, 0, 0, 1, 0],
+    );
+    check_codec(
+        &UnregisterBrokerResponse::default(),
+        0,
+        &[0, 0, 0, 0, 0, 0, 0, 0],
+    );
+}
+
+#[test]
+fn controlled_shutdown_codec() {
+    let r = ControlledShutdownRequest {
+        broker_id: 1,
+        broker_epoch: 2,
+    };
+    check_codec(
+        &ControlledShutdownRequest { broker_id: 1, ..r },
+        0,
+        &[0, 0, 0, 1],
+    );
+    check_codec(&r, 3, &[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0]);
+
+    let r = ControlledShutdownResponse {
+        error_code: 0,
+        remaining_partitions: std::iter::once((
+            ("t".to_string(), 0),
+            ControlledShutdownRemainingPartition {
+                topic_name: "t".to_string(),
+                partition_index: 0,
+            },
+        ))
+        .collect(),
+    };
+    check_codec(&r, 0, &[0, 0, 0, 0, 0, 1, 0, 1, b't', 0, 0, 0, 0]);
+    check_codec(&r, 3, &[0, 0, 2, 2, b't', 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn controlled_shutdown_v0_header_has_no_client_id() {
+    let r = ControlledShutdownRequest {
+        broker_id: 1,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    serialize_message(&r, 0, "client", &mut buf).unwrap();
+    assert_eq!(
+        buf,
+        [
+            0, 7, 0, 0, 0, 0, 0, 0, // api key, version, correlation id
+            0, 0, 0, 1, // broker_id
+        ]
+    );
+    assert_eq!(message_size(&r, 0, "client").unwrap(), buf.len());
+    let (hdr, decoded) = deserialize_request::<ControlledShutdownRequest>(&buf).unwrap();
+    assert_eq!(hdr.client_id, "");
+    assert_eq!((decoded.broker_id, decoded.broker_epoch), (1, -1));
+
+    // Version 1 has the client id of RequestHeader v1
+    let mut buf = Vec::new();
+    serialize_message(&r, 1, "c", &mut buf).unwrap();
+    assert_eq!(buf, [0, 7, 0, 1, 0, 0, 0, 0, 0, 1, b'c', 0, 0, 0, 1]);
+    let (hdr, _) = deserialize_request::<ControlledShutdownRequest>(&buf).unwrap();
+    assert_eq!(hdr.client_id, "c");
+}
+
+#[test]
+fn leader_and_isr_codec() {
+    let partition = |topic_name: &str| LeaderAndIsrPartitionState {
+        topic_name: topic_name.to_string(),
+        partition_index: 0,
+        controller_epoch: 2,
+        leader: 1,
+        leader_epoch: 3,
+        isr: vec![1],
+        partition_epoch: 4,
+        replicas: vec![1],
+        ..Default::default()
+    };
+    let live_leaders = || {
+        vec![LeaderAndIsrLiveLeader {
+            broker_id: 1,
+            host_name: "h".to_string(),
+            port: 9092,
+        }]
+    };
+
+    let r = LeaderAndIsrRequest {
+        controller_id: 1,
+        controller_epoch: 2,
+        ungrouped_partition_states: vec![partition("t")],
+        live_leaders: live_leaders(),
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 1, 0, 0, 0, 2, // controller_id, controller_epoch
+            0, 0, 0, 1, 0, 1, b't', // ungrouped_partition_states
+            0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 3, // partition, leader
+            0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 4, // isr, partition_epoch
+            0, 0, 0, 1, 0, 0, 0, 1, // replicas
+            0, 0, 0, 1, 0, 0, 0, 1, 0, 1, b'h', 0, 0, 0x23, 0x84, // live_leaders
+        ],
+    );
+
+    let r = LeaderAndIsrRequest {
+        controller_id: 1,
+        controller_epoch: 2,
+        broker_epoch: 5,
+        topic_states: vec![LeaderAndIsrTopicState {
+            topic_name: "t".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partition_states: vec![LeaderAndIsrPartitionState {
+                is_new: true,
+                ..partition("")
+            }],
+        }],
+        live_leaders: live_leaders(),
+        ..Default::default()
+    };
+    let partition_states = [
+        2, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 3, // partition, leader
+        2, 0, 0, 0, 1, 0, 0, 0, 4, // isr, partition_epoch
+        2, 0, 0, 0, 1, 1, 1, // replicas, adding_replicas, removing_replicas
+        1, // is_new
+    ];
+    let live_leaders_bytes = [2, 0, 0, 0, 1, 2, b'h', 0, 0, 0x23, 0x84, 0, 0];
+    check_codec(
+        &r,
+        4,
+        &[
+            &[0, 0, 0, 1, 0, 0, 0, 2][..], // controller_id, controller_epoch
+            &[0, 0, 0, 0, 0, 0, 0, 5],     // broker_epoch
+            &[2, 2, b't'],                 // topic_states
+            &partition_states,
+            &[0, 0], // partition, topic tagged fields
+            &live_leaders_bytes,
+        ]
+        .concat(),
+    );
+
+    let r = LeaderAndIsrRequest {
+        is_kraft_controller: true,
+        type_: 1,
+        topic_states: vec![LeaderAndIsrTopicState {
+            topic_name: "t".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partition_states: vec![LeaderAndIsrPartitionState {
+                is_new: true,
+                leader_recovery_state: 1,
+                ..partition("")
+            }],
+        }],
+        ..r
+    };
+    check_codec(
+        &r,
+        7,
+        &[
+            &[0, 0, 0, 1, 1, 0, 0, 0, 2][..], // controller_id, is_kraft_controller
+            &[0, 0, 0, 0, 0, 0, 0, 5, 1],     // broker_epoch, type
+            &[2, 2, b't'],                    // topic_states
+            &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], // topic_id
+            &partition_states,
+            &[1, 0, 0], // leader_recovery_state, tagged fields
+            &live_leaders_bytes,
+        ]
+        .concat(),
+    );
+
+    let error = |topic_name: &str| LeaderAndIsrPartitionError {
+        topic_name: topic_name.to_string(),
+        partition_index: 0,
+        error_code: 0,
+    };
+    let r = LeaderAndIsrResponse {
+        partition_errors: vec![error("t")],
+        ..Default::default()
+    };
+    check_codec(&r, 0, &[0, 0, 0, 0, 0, 1, 0, 1, b't', 0, 0, 0, 0, 0, 0]);
+    check_codec(&r, 4, &[0, 0, 2, 2, b't', 0, 0, 0, 0, 0, 0, 0, 0]);
+
+    let topic_id = TopicId(uuid::Uuid::from_u128(1));
+    let r = LeaderAndIsrResponse {
+        topics: std::iter::once((
+            topic_id,
+            LeaderAndIsrTopicError {
+                topic_id,
+                partition_errors: vec![error("")],
+            },
+        ))
+        .collect(),
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        7,
+        &[
+            0, 0, 2, // error_code, topics
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // topic_id
+            2, 0, 0, 0, 0, 0, 0, 0, // partition_errors
+            0, 0,
+        ],
+    );
+}
+
+#[test]
+fn stop_replica_codec() {
+    let r = StopReplicaRequest {
+        controller_id: 1,
+        controller_epoch: 2,
+        delete_partitions: true,
+        ungrouped_partitions: vec![StopReplicaPartitionV0 {
+            topic_name: "t".to_string(),
+            partition_index: 0,
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 1, 0, 0, 0, 2, 1, // controller_id, controller_epoch, delete
+            0, 0, 0, 1, 0, 1, b't', 0, 0, 0, 0, // ungrouped_partitions
+        ],
+    );
+
+    let r = StopReplicaRequest {
+        controller_id: 1,
+        controller_epoch: 2,
+        broker_epoch: 5,
+        delete_partitions: true,
+        topics: vec![StopReplicaTopicV1 {
+            name: "t".to_string(),
+            partition_indexes: vec![0],
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        2,
+        &[
+            0, 0, 0, 1, 0, 0, 0, 2, // controller_id, controller_epoch
+            0, 0, 0, 0, 0, 0, 0, 5, 1, // broker_epoch, delete_partitions
+            2, 2, b't', 2, 0, 0, 0, 0, 0, // topics
+            0,
+        ],
+    );
+
+    let r = StopReplicaRequest {
+        controller_id: 1,
+        is_kraft_controller: true,
+        controller_epoch: 2,
+        broker_epoch: 5,
+        topic_states: vec![StopReplicaTopicState {
+            topic_name: "t".to_string(),
+            partition_states: vec![StopReplicaPartitionState {
+                partition_index: 0,
+                leader_epoch: 3,
+                delete_partition: true,
+            }],
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        4,
+        &[
+            0, 0, 0, 1, 1, 0, 0, 0, 2, // controller_id, kraft, controller_epoch
+            0, 0, 0, 0, 0, 0, 0, 5, // broker_epoch
+            2, 2, b't', // topic_states
+            2, 0, 0, 0, 0, 0, 0, 0, 3, 1, 0, // partition_states
+            0, 0,
+        ],
+    );
+
+    let r = StopReplicaResponse {
+        error_code: 0,
+        partition_errors: vec![StopReplicaPartitionError {
+            topic_name: "t".to_string(),
+            partition_index: 0,
+            error_code: 0,
+        }],
+    };
+    check_codec(&r, 0, &[0, 0, 0, 0, 0, 1, 0, 1, b't', 0, 0, 0, 0, 0, 0]);
+    for ver in [2, 4] {
+        check_codec(&r, ver, &[0, 0, 2, 2, b't', 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
+
+#[test]
+fn update_metadata_codec() {
+    let partition = |topic_name: &str| UpdateMetadataPartitionState {
+        topic_name: topic_name.to_string(),
+        partition_index: 0,
+        controller_epoch: 2,
+        leader: 1,
+        leader_epoch: 3,
+        isr: vec![1],
+        zk_version: 4,
+        replicas: vec![1],
+        offline_replicas: Vec::new(),
+    };
+
+    let r = UpdateMetadataRequest {
+        controller_id: 1,
+        controller_epoch: 2,
+        ungrouped_partition_states: vec![partition("t")],
+        live_brokers: vec![UpdateMetadataBroker {
+            id: 1,
+            v0_host: "h".to_string(),
+            v0_port: 9092,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            0, 0, 0, 1, 0, 0, 0, 2, // controller_id, controller_epoch
+            0, 0, 0, 1, 0, 1, b't', // ungrouped_partition_states
+            0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 3, // partition, leader
+            0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 4, // isr, zk_version
+            0, 0, 0, 1, 0, 0, 0, 1, // replicas
+            0, 0, 0, 1, 0, 0, 0, 1, 0, 1, b'h', 0, 0, 0x23, 0x84, // live_brokers
+        ],
+    );
+
+    let r = UpdateMetadataRequest {
+        controller_id: 1,
+        controller_epoch: 2,
+        broker_epoch: 5,
+        topic_states: vec![UpdateMetadataTopicState {
+            topic_name: "t".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partition_states: vec![partition("")],
+        }],
+        live_brokers: vec![UpdateMetadataBroker {
+            id: 1,
+            endpoints: vec![UpdateMetadataEndpoint {
+                port: 9092,
+                host: "h".to_string(),
+                listener: "l".to_string(),
+                security_protocol: 0,
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let partition_states = [
+        2, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 3, // partition, leader
+        2, 0, 0, 0, 1, 0, 0, 0, 4, // isr, zk_version
+        2, 0, 0, 0, 1, 1, 0, // replicas, offline_replicas
+        0, // topic tagged fields
+    ];
+    let live_brokers = [
+        2, 0, 0, 0, 1, // live_brokers
+        2, 0, 0, 0x23, 0x84, 2, b'h', 2, b'l', 0, 0, 0, // endpoints
+        0, 0, // rack
+        0,
+    ];
+    check_codec(
+        &r,
+        6,
+        &[
+            &[0, 0, 0, 1, 0, 0, 0, 2][..], // controller_id, controller_epoch
+            &[0, 0, 0, 0, 0, 0, 0, 5],     // broker_epoch
+            &[2, 2, b't'],                 // topic_states
+            &partition_states,
+            &live_brokers,
+        ]
+        .concat(),
+    );
+
+    let r = UpdateMetadataRequest {
+        is_kraft_controller: true,
+        ..r
+    };
+    check_codec(
+        &r,
+        8,
+        &[
+            &[0, 0, 0, 1, 1, 0, 0, 0, 2][..], // controller_id, is_kraft_controller
+            &[0, 0, 0, 0, 0, 0, 0, 5],        // broker_epoch
+            &[2, 2, b't'],                    // topic_states
+            &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], // topic_id
+            &partition_states,
+            &live_brokers,
+        ]
+        .concat(),
+    );
+
+    let r = UpdateMetadataResponse::default();
+    check_codec(&r, 0, &[0, 0]);
+    for ver in [6, 8] {
+        check_codec(&r, ver, &[0, 0, 0]);
+    }
+}
+
+#[test]
+fn consumer_group_heartbeat_codec() {
+    let r = ConsumerGroupHeartbeatRequest {
+        group_id: "g".to_string(),
+        member_id: "m".to_string(),
+        rebalance_timeout_ms: 300,
+        subscribed_topic_names: Some(vec!["t".to_string()]),
+        topic_partitions: Some(Vec::new()),
+        ..Default::default()
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            2, b'g', 2, b'm', 0, 0, 0, 0, // group_id, member_id, member_epoch
+            0, 0, 0, 0, 1, 0x2c, // instance_id, rack_id, rebalance_timeout_ms
+            2, 2, b't', 0, 1, // subscribed_topic_names, server_assignor, partitions
+            0,
+        ],
+    );
+    check_codec(
+        &ConsumerGroupHeartbeatRequest::default(),
+        0,
+        &[1, 1, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0],
+    );
+
+    let r = ConsumerGroupHeartbeatResponse {
+        member_id: Some("m".to_string()),
+        member_epoch: 1,
+        heartbeat_interval_ms: 5000,
+        assignment: Some(ConsumerGroupHeartbeatAssignment {
+            topic_partitions: vec![ConsumerGroupHeartbeatTopicPartitions {
+                topic_id: TopicId(uuid::Uuid::from_u128(1)),
+                partitions: vec![0],
+            }],
+        }),
+        ..Default::default()
+    };
+    let fields = [
+        0, 0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code, error_message
+        2, b'm', 0, 0, 0, 1, 0, 0, 0x13, 0x88, // member_id, epoch, interval
+    ];
+    check_codec(
+        &r,
+        0,
+        &[
+            &fields[..],
+            &[1, 2],                                           // assignment
+            &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], // topic_id
+            &[2, 0, 0, 0, 0, 0, 0, 0],                         // partitions
+        ]
+        .concat(),
+    );
+    let r = ConsumerGroupHeartbeatResponse {
+        assignment: None,
+        ..r
+    };
+    check_codec(&r, 0, &[&fields[..], &[0xff, 0]].concat());
+}
+
+#[test]
+fn consumer_group_describe_codec() {
+    let r = ConsumerGroupDescribeRequest {
+        group_ids: vec!["g".to_string()],
+        include_authorized_operations: true,
+    };
+    check_codec(&r, 0, &[2, 2, b'g', 1, 0]);
+
+    let r = ConsumerGroupDescribeResponse {
+        throttle_time_ms: 0,
+        groups: vec![ConsumerGroupDescribeGroup {
+            group_id: "g".to_string(),
+            group_state: "s".to_string(),
+            group_epoch: 1,
+            assignment_epoch: 1,
+            assignor_name: "a".to_string(),
+            members: vec![ConsumerGroupDescribeMember {
+                member_id: "m".to_string(),
+                member_epoch: 1,
+                client_id: "c".to_string(),
+                client_host: "h".to_string(),
+                subscribed_topic_names: vec!["t".to_string()],
+                assignment: ConsumerGroupDescribeAssignment {
+                    topic_partitions: vec![ConsumerGroupDescribeTopicPartitions {
+                        topic_id: TopicId(uuid::Uuid::from_u128(1)),
+                        topic_name: "t".to_string(),
+                        partitions: vec![0],
+                    }],
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+    check_codec(
+        &r,
+        0,
+        &[
+            &[0, 0, 0, 0, 2][..],                                 // throttle_time_ms, groups
+            &[0, 0, 0, 2, b'g', 2, b's'],                         // error, group_id, group_state
+            &[0, 0, 0, 1, 0, 0, 0, 1, 2, b'a'],                   // epochs, assignor_name
+            &[2, 2, b'm', 0, 0, 0, 0, 0, 1],                      // members
+            &[2, b'c', 2, b'h', 2, 2, b't', 0],                   // client, subscriptions
+            &[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], // assignment
+            &[2, b't', 2, 0, 0, 0, 0, 0, 0],
+            &[1, 0],                // target_assignment
+            &[0, 0x80, 0, 0, 0, 0], // authorized_operations
+            &[0],
+        ]
+        .concat(),
+    );
+}
+
+#[test]
+fn connect_protocol_worker_state_codec() {
+    let check = |s: &ConnectProtocolWorkerState, ver, expected: &[u8]| {
+        check_payload(
+            s,
+            ver,
+            expected,
+            ConnectProtocolWorkerState::to_bytes,
+            ConnectProtocolWorkerState::from_bytes,
+        )
+    };
+    let fields = [0, 1, b'u', 0, 0, 0, 0, 0, 0, 0, 10]; // url, config_offset
+
+    let s = ConnectProtocolWorkerState {
+        url: "u".to_string(),
+        config_offset: 10,
+        allocation: None,
+    };
+    check(&s, 0, &[&[0, 0][..], &fields].concat());
+    check(
+        &s,
+        2,
+        &[&[0, 2][..], &fields, &[0xff, 0xff, 0xff, 0xff]].concat(),
+    );
+
+    let s = ConnectProtocolWorkerState {
+        allocation: Some(vec![1]),
+        ..s
+    };
+    check(&s, 1, &[&[0, 1][..], &fields, &[0, 0, 0, 1, 1]].concat());
+}
+
+#[test]
+fn connect_protocol_assignment_codec() {
+    let check = |a: &ConnectProtocolAssignment, ver, expected: &[u8]| {
+        check_payload(
+            a,
+            ver,
+            expected,
+            ConnectProtocolAssignment::to_bytes,
+            ConnectProtocolAssignment::from_bytes,
+        )
+    };
+    let fields = [
+        0, 0, 0, 1, b'l', 0, 1, b'u', // error, leader, leader_url
+        0, 0, 0, 0, 0, 0, 0, 10, // config_offset
+    ];
+    let assignment = [
+        0, 0, 0, 1, 0, 1, b'c', // assignment, connector
+        0, 0, 0, 2, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, // tasks
+    ];
+
+    let a = ConnectProtocolAssignment {
+        leader: "l".to_string(),
+        leader_url: "u".to_string(),
+        config_offset: 10,
+        assignment: Some(vec![ConnectProtocolConnectorAssignment {
+            connector: "c".to_string(),
+            tasks: vec![CONNECTOR_TASK, 0],
+        }]),
+        ..Default::default()
+    };
+    check(&a, 0, &[&[0, 0][..], &fields, &assignment].concat());
+
+    let a = ConnectProtocolAssignment {
+        revoked: Some(Vec::new()),
+        scheduled_delay: 5,
+        ..a
+    };
+    check(
+        &a,
+        1,
+        &[
+            &[0, 1][..],
+            &fields,
+            &assignment,
+            &[0, 0, 0, 0, 0, 0, 0, 5], // revoked, scheduled_delay
+        ]
+        .concat(),
+    );
+
+    // Both arrays are nullable since v1
+    let a = ConnectProtocolAssignment {
+        assignment: None,
+        revoked: None,
+        ..a
+    };
+    check(
+        &a,
+        2,
+        &[
+            &[0, 2][..],
+            &fields,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 5],
+        ]
+        .concat(),
+    );
+}
+
+#[test]
+fn offset_commit_record_codec() {
+    let key = OffsetCommitKey {
+        group: "g".to_string(),
+        topic: "t".to_string(),
+        partition: 1,
+    };
+    let fields = [0, 1, b'g', 0, 1, b't', 0, 0, 0, 1]; // group, topic, partition
+    for ver in [0, 1] {
+        let expected = [&[0, ver as u8][..], &fields].concat();
+        check_payload(
+            &key,
+            ver,
+            &expected,
+            OffsetCommitKey::to_bytes,
+            OffsetCommitKey::from_bytes,
+        );
+        assert!(matches!(
+            ConsumerOffsetsRecord::from_bytes(&expected, None).unwrap(),
+            ConsumerOffsetsRecord::OffsetCommit(_, None)
+        ));
+    }
+
+    let check = |v: &OffsetCommitValue, ver, expected: &[u8]| {
+        check_payload(
+            v,
+            ver,
+            expected,
+            OffsetCommitValue::to_bytes,
+            OffsetCommitValue::from_bytes,
+        )
+    };
+    let offset = [0, 0, 0, 0, 0, 0, 0, 5];
+    let commit_timestamp = [0, 0, 0, 0, 0, 0, 0, 7];
+    let value = || OffsetCommitValue {
+        offset: 5,
+        metadata: "m".to_string(),
+        commit_timestamp: 7,
+        ..Default::default()
+    };
+    check(
+        &value(),
+        0,
+        &[&[0, 0][..], &offset, &[0, 1, b'm'], &commit_timestamp].concat(),
+    );
+
+    // Expire timestamp is in v1 only
+    let expiring = OffsetCommitValue {
+        expire_timestamp: 9,
+        ..value()
+    };
+    check(
+        &expiring,
+        1,
+        &[
+            &[0, 1][..],
+            &offset,
+            &[0, 1, b'm'],
+            &commit_timestamp,
+            &[0, 0, 0, 0, 0, 0, 0, 9],
+        ]
+        .concat(),
+    );
+
+    let v = OffsetCommitValue {
+        leader_epoch: 2,
+        ..value()
+    };
+    check(
+        &v,
+        3,
+        &[
+            &[0, 3][..],
+            &offset,
+            &[0, 0, 0, 2, 0, 1, b'm'],
+            &commit_timestamp,
+        ]
+        .concat(),
+    );
+    check(
+        &v,
+        4,
+        &[
+            &[0, 4][..],
+            &offset,
+            &[0, 0, 0, 2, 2, b'm'],
+            &commit_timestamp,
+            &[0],
+        ]
+        .concat(),
+    );
+}
+
+#[test]
+fn group_metadata_record_codec() {
+    let key = GroupMetadataKey {
+        group: "g".to_string(),
+    };
+    let expected = [0, 2, 0, 1, b'g'];
+    check_payload(
+        &key,
+        2,
+        &expected,
+        GroupMetadataKey::to_bytes,
+        GroupMetadataKey::from_bytes,
+    );
+    assert!(matches!(
+        ConsumerOffsetsRecord::from_bytes(&expected, None).unwrap(),
+        ConsumerOffsetsRecord::GroupMetadata(_, None)
+    ));
+
+    let check = |v: &GroupMetadataValue, ver, expected: &[u8]| {
+        check_payload(
+            v,
+            ver,
+            expected,
+            GroupMetadataValue::to_bytes,
+            GroupMetadataValue::from_bytes,
+        )
+    };
+    let v = GroupMetadataValue {
+        protocol_type: "c".to_string(),
+        ..Default::default()
+    };
+    check(
+        &v,
+        0,
+        &[
+            0, 0, 0, 1, b'c', 0, 0, 0, 0, // protocol_type, generation
+            0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, // protocol, leader, members
+        ],
+    );
+
+    let member = GroupMetadataMember {
+        member_id: "m".to_string(),
+        client_id: "c".to_string(),
+        client_host: "h".to_string(),
+        session_timeout: 10,
+        subscription: vec![1],
+        assignment: vec![2],
+        ..Default::default()
+    };
+    let v = GroupMetadataValue {
+        generation: 1,
+        protocol: Some("r".to_string()),
+        leader: Some("m".to_string()),
+        members: vec![member],
+        ..v
+    };
+    check(
+        &v,
+        0,
+        &[
+            &[0, 0, 0, 1, b'c', 0, 0, 0, 1][..], // protocol_type, generation
+            &[0, 1, b'r', 0, 1, b'm'],           // protocol, leader
+            &[0, 0, 0, 1, 0, 1, b'm', 0, 1, b'c', 0, 1, b'h'], // members
+            &[0, 0, 0, 10, 0, 0, 0, 1, 1, 0, 0, 0, 1, 2], // session_timeout, metadata
+        ]
+        .concat(),
+    );
+
+    let mut v = GroupMetadataValue {
+        current_state_timestamp: 3,
+        ..v
+    };
+    v.members[0].group_instance_id = Some("i".to_string());
+    v.members[0].rebalance_timeout = 20;
+    check(
+        &v,
+        4,
+        &[
+            &[0, 4, 2, b'c', 0, 0, 0, 1, 2, b'r', 2, b'm'][..],
+            &[0, 0, 0, 0, 0, 0, 0, 3], // current_state_timestamp
+            &[2, 2, b'm', 2, b'i', 2, b'c', 2, b'h'], // members
+            &[0, 0, 0, 20, 0, 0, 0, 10, 2, 1, 2, 2, 0], // timeouts, metadata
+            &[0],
+        ]
+        .concat(),
+    );
+}
+
+#[test]
+fn transaction_log_record_codec() {
+    let key = TransactionLogKey {
+        transactional_id: "x".to_string(),
+    };
+    check_payload(
+        &key,
+        0,
+        &[0, 0, 0, 1, b'x'],
+        TransactionLogKey::to_bytes,
+        TransactionLogKey::from_bytes,
+    );
+
+    let check = |v: &TransactionLogValue, ver, expected: &[u8]| {
+        check_payload(
+            v,
+            ver,
+            expected,
+            TransactionLogValue::to_bytes,
+            TransactionLogValue::from_bytes,
+        )
+    };
+    let fields = [
+        0, 0, 0, 0, 0, 0, 0, 1, 0, 2, // producer_id, producer_epoch
+        0, 0, 0xea, 0x60, 1, // transaction_timeout_ms, transaction_status
+    ];
+    let timestamps = [0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 4];
+    let value = || TransactionLogValue {
+        producer_id: 1,
+        producer_epoch: 2,
+        transaction_timeout_ms: 60_000,
+        transaction_status: TransactionState::Ongoing as i8,
+        transaction_partitions: Some(vec![TransactionLogPartitions {
+            topic: "t".to_string(),
+            partition_ids: vec![0],
+        }]),
+        transaction_last_update_timestamp_ms: 3,
+        transaction_start_timestamp_ms: 4,
+        ..Default::default()
+    };
+    check(
+        &value(),
+        0,
+        &[
+            &[0, 0][..],
+            &fields,
+            &[0, 0, 0, 1, 0, 1, b't', 0, 0, 0, 1, 0, 0, 0, 0], // partitions
+            &timestamps,
+        ]
+        .concat(),
+    );
+    let v = TransactionLogValue {
+        transaction_partitions: None,
+        ..value()
+    };
+    check(
+        &v,
+        0,
+        &[&[0, 0][..], &fields, &[0xff, 0xff, 0xff, 0xff], &timestamps].concat(),
+    );
+
+    // Tagged fields are written only when they differ from defaults
+    check(
+        &value(),
+        1,
+        &[
+            &[0, 1][..],
+            &fields,
+            &[2, 2, b't', 2, 0, 0, 0, 0, 0], // partitions
+            &timestamps,
+            &[0],
+        ]
+        .concat(),
+    );
+    let v = TransactionLogValue {
+        previous_producer_id: 5,
+        client_transaction_version: 2,
+        ..value()
+    };
+    check(
+        &v,
+        1,
+        &[
+            &[0, 1][..],
+            &fields,
+            &[2, 2, b't', 2, 0, 0, 0, 0, 0],
+            &timestamps,
+            &[2, 0, 8, 0, 0, 0, 0, 0, 0, 0, 5], // previous_producer_id
+            &[2, 2, 0, 2],                      // client_transaction_version
+        ]
+        .concat(),
+    );
+    assert!(matches!(
+        TransactionState::from_i8(v.transaction_status),
+        Some(TransactionState::Ongoing)
+    ));
+}
@@ -0,0 +1,293 @@
+use crate::de::{deserialize_versioned, KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{serialize_versioned, KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{Bytes, ProducerId, TopicName, TransactionalId};
+
+// Record of the __transaction_state topic. Both key and value are prefixed with
+// their version. Null value is a tombstone for an expired transactional id.
+#[derive(Default)]
+pub struct TransactionLogKey {
+    pub transactional_id: TransactionalId,
+}
+
+builder! {
+    TransactionLogKey => TransactionLogKeyBuilder {
+        transactional_id: TransactionalId,
+    }
+}
+
+impl TransactionLogKey {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 0;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for TransactionLogKey {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.transactional_id)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for TransactionLogKey {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = TransactionLogKey {
+            transactional_id: d
+                .read_field("transactional_id", |d| d.read_string())?
+                .to_string(),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// Ref: TransactionState.java
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i8)]
+pub enum TransactionState {
+    Empty = 0,
+    Ongoing = 1,
+    PrepareCommit = 2,
+    PrepareAbort = 3,
+    CompleteCommit = 4,
+    CompleteAbort = 5,
+    Dead = 6,
+    PrepareEpochFence = 7,
+}
+
+impl TransactionState {
+    pub fn from_i8(v: i8) -> Option<Self> {
+        match v {
+            0 => Some(TransactionState::Empty),
+            1 => Some(TransactionState::Ongoing),
+            2 => Some(TransactionState::PrepareCommit),
+            3 => Some(TransactionState::PrepareAbort),
+            4 => Some(TransactionState::CompleteCommit),
+            5 => Some(TransactionState::CompleteAbort),
+            6 => Some(TransactionState::Dead),
+            7 => Some(TransactionState::PrepareEpochFence),
+            _ => None,
+        }
+    }
+}
+
+pub struct TransactionLogValue {
+    pub producer_id: ProducerId,
+
+    // versions: 1+, tag: 0, default=-1
+    // Producer id in use before an epoch overflow bumped the producer id
+    pub previous_producer_id: ProducerId,
+
+    // versions: 1+, tag: 1, default=-1
+    // Producer id to be used after the transaction completes
+    pub next_producer_id: ProducerId,
+
+    pub producer_epoch: i16,
+
+    // versions: 1+, tag: 3, default=-1
+    pub next_producer_epoch: i16,
+
+    pub transaction_timeout_ms: i32,
+    // TransactionState of the transaction, kept as is to preserve unknown states
+    pub transaction_status: i8,
+
+    // nullableVersions: 0+
+    pub transaction_partitions: Option<Vec<TransactionLogPartitions>>,
+
+    pub transaction_last_update_timestamp_ms: i64,
+    pub transaction_start_timestamp_ms: i64,
+
+    // versions: 1+, tag: 2, default=0
+    pub client_transaction_version: i16,
+}
+
+impl Default for TransactionLogValue {
+    fn default() -> Self {
+        TransactionLogValue {
+            producer_id: 0,
+            previous_producer_id: -1,
+            next_producer_id: -1,
+            producer_epoch: 0,
+            next_producer_epoch: -1,
+            transaction_timeout_ms: 0,
+            transaction_status: 0,
+            transaction_partitions: None,
+            transaction_last_update_timestamp_ms: 0,
+            transaction_start_timestamp_ms: 0,
+            client_transaction_version: 0,
+        }
+    }
+}
+
+builder! {
+    TransactionLogValue => TransactionLogValueBuilder {
+        producer_id: ProducerId,
+        previous_producer_id: ProducerId,
+        next_producer_id: ProducerId,
+        producer_epoch: i16,
+        next_producer_epoch: i16,
+        transaction_timeout_ms: i32,
+        transaction_status: i8,
+        transaction_partitions: Option<Vec<TransactionLogPartitions>>,
+        transaction_last_update_timestamp_ms: i64,
+        transaction_start_timestamp_ms: i64,
+        client_transaction_version: i16,
+    }
+}
+
+impl TransactionLogValue {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 1;
+    pub const FLEXIBLE_VERSION: i16 = 1;
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+
+    pub fn state(&self) -> Option<TransactionState> {
+        TransactionState::from_i8(self.transaction_status)
+    }
+}
+
+impl KafkaProtoEncodable for TransactionLogValue {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int64(self.producer_id)?;
+        s.emit_int16(self.producer_epoch)?;
+        s.emit_int32(self.transaction_timeout_ms)?;
+        s.emit_int8(self.transaction_status)?;
+        s.emit_nullable_array(ver, self.transaction_partitions.as_ref().map(|v| v.iter()))?;
+        s.emit_int64(self.transaction_last_update_timestamp_ms)?;
+        s.emit_int64(self.transaction_start_timestamp_ms)?;
+
+        // Tagged fields are emitted only when they differ from default
+        let emit_previous_producer_id = self.previous_producer_id != -1;
+        let emit_next_producer_id = self.next_producer_id != -1;
+        let emit_client_transaction_version = self.client_transaction_version != 0;
+        let emit_next_producer_epoch = self.next_producer_epoch != -1;
+        let mut r = s.emit_tagged_fields_hdr(
+            emit_previous_producer_id as usize
+                + emit_next_producer_id as usize
+                + emit_client_transaction_version as usize
+                + emit_next_producer_epoch as usize,
+        )?;
+        if emit_previous_producer_id {
+            r = s.emit_tagged_field(0, ver, &self.previous_producer_id)?;
+        }
+        if emit_next_producer_id {
+            r = s.emit_tagged_field(1, ver, &self.next_producer_id)?;
+        }
+        if emit_client_transaction_version {
+            r = s.emit_tagged_field(2, ver, &self.client_transaction_version)?;
+        }
+        if emit_next_producer_epoch {
+            r = s.emit_tagged_field(3, ver, &self.next_producer_epoch)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for TransactionLogValue {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = TransactionLogValue {
+            producer_id: d.read_field("producer_id", |d| d.read_int64())?,
+            previous_producer_id: -1,
+            next_producer_id: -1,
+            producer_epoch: d.read_field("producer_epoch", |d| d.read_int16())?,
+            next_producer_epoch: -1,
+            transaction_timeout_ms: d.read_field("transaction_timeout_ms", |d| d.read_int32())?,
+            transaction_status: d.read_field("transaction_status", |d| d.read_int8())?,
+            transaction_partitions: d
+                .read_field("transaction_partitions", |d| d.read_nullable_array(ver))?,
+            transaction_last_update_timestamp_ms: d
+                .read_field("transaction_last_update_timestamp_ms", |d| d.read_int64())?,
+            transaction_start_timestamp_ms: d
+                .read_field("transaction_start_timestamp_ms", |d| d.read_int64())?,
+            client_transaction_version: 0,
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.previous_producer_id =
+                    d.read_field("previous_producer_id", |d| d.read_int64())?;
+                Ok(true)
+            }
+            1 => {
+                r.next_producer_id = d.read_field("next_producer_id", |d| d.read_int64())?;
+                Ok(true)
+            }
+            2 => {
+                r.client_transaction_version =
+                    d.read_field("client_transaction_version", |d| d.read_int16())?;
+                Ok(true)
+            }
+            3 => {
+                r.next_producer_epoch = d.read_field("next_producer_epoch", |d| d.read_int16())?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct TransactionLogPartitions {
+    pub topic: TopicName,
+    pub partition_ids: Vec<i32>,
+}
+
+builder! {
+    TransactionLogPartitions => TransactionLogPartitionsBuilder {
+        topic: TopicName,
+        partition_ids: Vec<i32>,
+    }
+}
+
+impl KafkaProtoEncodable for TransactionLogPartitions {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic)?;
+        s.emit_array(ver, self.partition_ids.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for TransactionLogPartitions {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = TransactionLogPartitions {
+            topic: d.read_field("topic", |d| d.read_string())?.to_string(),
+            partition_ids: d.read_field("partition_ids", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
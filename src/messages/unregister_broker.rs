@@ -0,0 +1,97 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest};
+
+#[derive(Default)]
+pub struct UnregisterBrokerRequest {
+    // The broker ID to unregister.
+    pub broker_id: BrokerId,
+}
+
+builder! {
+    UnregisterBrokerRequest => UnregisterBrokerRequestBuilder {
+        broker_id: BrokerId,
+    }
+}
+
+impl KafkaProtoEncodable for UnregisterBrokerRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UnregisterBrokerRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = UnregisterBrokerRequest {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for UnregisterBrokerRequest {
+    const API_KEY: i16 = 64;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for UnregisterBrokerRequest {
+    type Response = UnregisterBrokerResponse;
+}
+
+#[derive(Default)]
+pub struct UnregisterBrokerResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+}
+
+builder! {
+    UnregisterBrokerResponse => UnregisterBrokerResponseBuilder {
+        throttle_time_ms: i32,
+        error_code: i16,
+        error_message: Option<String>,
+    }
+}
+
+impl KafkaProtoEncodable for UnregisterBrokerResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.throttle_time_ms)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_nullable_string(self.error_message.as_deref())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UnregisterBrokerResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = UnregisterBrokerResponse {
+            throttle_time_ms: d.read_field("throttle_time_ms", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            error_message: d
+                .read_field("error_message", |d| d.read_nullable_string())?
+                .map(Into::into),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for UnregisterBrokerResponse {
+    const API_KEY: i16 = 64;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
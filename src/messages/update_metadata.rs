@@ -0,0 +1,427 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicId, TopicName};
+
+pub struct UpdateMetadataRequest {
+    // The controller id.
+    pub controller_id: BrokerId,
+
+    // versions: 8+, default=false
+    // If KRaft controller id is used during migration. See KIP-866
+    pub is_kraft_controller: bool,
+
+    // The controller epoch.
+    pub controller_epoch: i32,
+
+    // versions: 5+, ignorable, default=-1
+    // The broker epoch.
+    pub broker_epoch: i64,
+
+    // versions: 0-4
+    // In older versions of this RPC, each partition that we would like to update.
+    pub ungrouped_partition_states: Vec<UpdateMetadataPartitionState>,
+
+    // versions: 5+
+    // In newer versions of this RPC, each topic that we would like to update.
+    pub topic_states: Vec<UpdateMetadataTopicState>,
+
+    pub live_brokers: Vec<UpdateMetadataBroker>,
+}
+
+impl Default for UpdateMetadataRequest {
+    fn default() -> Self {
+        UpdateMetadataRequest {
+            controller_id: 0,
+            is_kraft_controller: false,
+            controller_epoch: 0,
+            broker_epoch: -1,
+            ungrouped_partition_states: Vec::new(),
+            topic_states: Vec::new(),
+            live_brokers: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    UpdateMetadataRequest => UpdateMetadataRequestBuilder {
+        controller_id: BrokerId,
+        is_kraft_controller: bool,
+        controller_epoch: i32,
+        broker_epoch: i64,
+        ungrouped_partition_states: Vec<UpdateMetadataPartitionState>,
+        topic_states: Vec<UpdateMetadataTopicState>,
+        live_brokers: Vec<UpdateMetadataBroker>,
+    }
+}
+
+impl KafkaProtoEncodable for UpdateMetadataRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.controller_id)?;
+        if ver >= 8 {
+            s.emit_bool(self.is_kraft_controller)?;
+        } else {
+            s.check_absent_field("is_kraft_controller", ver, !self.is_kraft_controller)?;
+        }
+        s.emit_int32(self.controller_epoch)?;
+        if ver >= 5 {
+            s.emit_int64(self.broker_epoch)?;
+        }
+        if ver <= 4 {
+            s.emit_array(ver, self.ungrouped_partition_states.iter())?;
+        } else {
+            s.check_absent_field(
+                "ungrouped_partition_states",
+                ver,
+                self.ungrouped_partition_states.is_empty(),
+            )?;
+        }
+        if ver >= 5 {
+            s.emit_array(ver, self.topic_states.iter())?;
+        } else {
+            s.check_absent_field("topic_states", ver, self.topic_states.is_empty())?;
+        }
+        s.emit_array(ver, self.live_brokers.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UpdateMetadataRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = UpdateMetadataRequest {
+            controller_id: d.read_field("controller_id", |d| d.read_int32())?,
+            is_kraft_controller: if ver >= 8 {
+                d.read_field("is_kraft_controller", |d| d.read_bool())?
+            } else {
+                false
+            },
+            controller_epoch: d.read_field("controller_epoch", |d| d.read_int32())?,
+            broker_epoch: if ver >= 5 {
+                d.read_field("broker_epoch", |d| d.read_int64())?
+            } else {
+                -1
+            },
+            ungrouped_partition_states: if ver <= 4 {
+                d.read_field("ungrouped_partition_states", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            topic_states: if ver >= 5 {
+                d.read_field("topic_states", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            live_brokers: d.read_field("live_brokers", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct UpdateMetadataTopicState {
+    pub topic_name: TopicName,
+
+    // versions: 7+, ignorable
+    pub topic_id: TopicId,
+
+    pub partition_states: Vec<UpdateMetadataPartitionState>,
+}
+
+builder! {
+    UpdateMetadataTopicState => UpdateMetadataTopicStateBuilder {
+        topic_name: TopicName,
+        topic_id: TopicId,
+        partition_states: Vec<UpdateMetadataPartitionState>,
+    }
+}
+
+impl KafkaProtoEncodable for UpdateMetadataTopicState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        if ver >= 7 {
+            s.emit_uuid(self.topic_id.0)?;
+        }
+        s.emit_array(ver, self.partition_states.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UpdateMetadataTopicState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = UpdateMetadataTopicState {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            topic_id: if ver >= 7 {
+                TopicId(d.read_field("topic_id", |d| d.read_uuid())?)
+            } else {
+                TopicId::ZERO
+            },
+            partition_states: d.read_field("partition_states", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct UpdateMetadataBroker {
+    pub id: BrokerId,
+
+    // versions: 0
+    // The broker hostname.
+    pub v0_host: String,
+    // versions: 0
+    // The broker port.
+    pub v0_port: i32,
+
+    // versions: 1+, ignorable
+    // The broker endpoints.
+    pub endpoints: Vec<UpdateMetadataEndpoint>,
+
+    // versions: 2+, ignorable
+    // The rack which this broker belongs to.
+    pub rack: Option<String>,
+}
+
+builder! {
+    UpdateMetadataBroker => UpdateMetadataBrokerBuilder {
+        id: BrokerId,
+        v0_host: String,
+        v0_port: i32,
+        endpoints: Vec<UpdateMetadataEndpoint>,
+        rack: Option<String>,
+    }
+}
+
+impl KafkaProtoEncodable for UpdateMetadataBroker {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.id)?;
+        if ver == 0 {
+            s.emit_string(&self.v0_host)?;
+            s.emit_int32(self.v0_port)?;
+        } else {
+            s.check_absent_field("v0_host", ver, self.v0_host.is_empty())?;
+            s.check_absent_field("v0_port", ver, self.v0_port == 0)?;
+        }
+        if ver >= 1 {
+            s.emit_array(ver, self.endpoints.iter())?;
+        }
+        if ver >= 2 {
+            s.emit_nullable_string(self.rack.as_deref())?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UpdateMetadataBroker {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = UpdateMetadataBroker {
+            id: d.read_field("id", |d| d.read_int32())?,
+            v0_host: if ver == 0 {
+                d.read_field("v0_host", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            v0_port: if ver == 0 {
+                d.read_field("v0_port", |d| d.read_int32())?
+            } else {
+                0
+            },
+            endpoints: if ver >= 1 {
+                d.read_field("endpoints", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            rack: if ver >= 2 {
+                d.read_field("rack", |d| d.read_nullable_string())?
+                    .map(Into::into)
+            } else {
+                None
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct UpdateMetadataEndpoint {
+    pub port: i32,
+    pub host: String,
+
+    // versions: 3+, ignorable
+    pub listener: String,
+
+    pub security_protocol: i16,
+}
+
+builder! {
+    UpdateMetadataEndpoint => UpdateMetadataEndpointBuilder {
+        port: i32,
+        host: String,
+        listener: String,
+        security_protocol: i16,
+    }
+}
+
+impl KafkaProtoEncodable for UpdateMetadataEndpoint {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.port)?;
+        s.emit_string(&self.host)?;
+        if ver >= 3 {
+            s.emit_string(&self.listener)?;
+        }
+        s.emit_int16(self.security_protocol)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UpdateMetadataEndpoint {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = UpdateMetadataEndpoint {
+            port: d.read_field("port", |d| d.read_int32())?,
+            host: d.read_field("host", |d| d.read_string())?.to_string(),
+            listener: if ver >= 3 {
+                d.read_field("listener", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            security_protocol: d.read_field("security_protocol", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct UpdateMetadataPartitionState {
+    // versions: 0-4
+    pub topic_name: TopicName,
+
+    pub partition_index: i32,
+    pub controller_epoch: i32,
+    // The ID of the broker which is the current partition leader.
+    pub leader: BrokerId,
+    pub leader_epoch: i32,
+    // The brokers which are in the ISR for this partition.
+    pub isr: Vec<BrokerId>,
+    pub zk_version: i32,
+    // All the replicas of this partition.
+    pub replicas: Vec<BrokerId>,
+
+    // versions: 4+, ignorable
+    // The replicas of this partition which are offline.
+    pub offline_replicas: Vec<BrokerId>,
+}
+
+builder! {
+    UpdateMetadataPartitionState => UpdateMetadataPartitionStateBuilder {
+        topic_name: TopicName,
+        partition_index: i32,
+        controller_epoch: i32,
+        leader: BrokerId,
+        leader_epoch: i32,
+        isr: Vec<BrokerId>,
+        zk_version: i32,
+        replicas: Vec<BrokerId>,
+        offline_replicas: Vec<BrokerId>,
+    }
+}
+
+impl KafkaProtoEncodable for UpdateMetadataPartitionState {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        if ver <= 4 {
+            s.emit_string(&self.topic_name)?;
+        } else {
+            s.check_absent_field("topic_name", ver, self.topic_name.is_empty())?;
+        }
+        s.emit_int32(self.partition_index)?;
+        s.emit_int32(self.controller_epoch)?;
+        s.emit_int32(self.leader)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_array(ver, self.isr.iter())?;
+        s.emit_int32(self.zk_version)?;
+        s.emit_array(ver, self.replicas.iter())?;
+        if ver >= 4 {
+            s.emit_array(ver, self.offline_replicas.iter())?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UpdateMetadataPartitionState {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = UpdateMetadataPartitionState {
+            topic_name: if ver <= 4 {
+                d.read_field("topic_name", |d| d.read_string())?.to_string()
+            } else {
+                String::new()
+            },
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            controller_epoch: d.read_field("controller_epoch", |d| d.read_int32())?,
+            leader: d.read_field("leader", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            isr: d.read_field("isr", |d| d.read_array(ver))?,
+            zk_version: d.read_field("zk_version", |d| d.read_int32())?,
+            replicas: d.read_field("replicas", |d| d.read_array(ver))?,
+            offline_replicas: if ver >= 4 {
+                d.read_field("offline_replicas", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for UpdateMetadataRequest {
+    const API_KEY: i16 = 6;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 8;
+    const FLEXIBLE_VERSION: i16 = 6;
+}
+
+impl KafkaRequest for UpdateMetadataRequest {
+    type Response = UpdateMetadataResponse;
+}
+
+#[derive(Default)]
+pub struct UpdateMetadataResponse {
+    pub error_code: i16,
+}
+
+builder! {
+    UpdateMetadataResponse => UpdateMetadataResponseBuilder {
+        error_code: i16,
+    }
+}
+
+impl KafkaProtoEncodable for UpdateMetadataResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UpdateMetadataResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = UpdateMetadataResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for UpdateMetadataResponse {
+    const API_KEY: i16 = 6;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 8;
+    const FLEXIBLE_VERSION: i16 = 6;
+}
@@ -0,0 +1,256 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, KafkaRequest, TopicName};
+
+#[derive(Default)]
+pub struct VoteRequest {
+    pub cluster_id: Option<String>,
+    pub topics: Vec<VoteRequestTopic>,
+}
+
+builder! {
+    VoteRequest => VoteRequestBuilder {
+        cluster_id: Option<String>,
+        topics: Vec<VoteRequestTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for VoteRequest {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_nullable_string(self.cluster_id.as_deref())?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for VoteRequest {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = VoteRequest {
+            cluster_id: d
+                .read_field("cluster_id", |d| d.read_nullable_string())?
+                .map(Into::into),
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct VoteRequestTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<VoteRequestPartition>,
+}
+
+builder! {
+    VoteRequestTopic => VoteRequestTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<VoteRequestPartition>,
+    }
+}
+
+impl KafkaProtoEncodable for VoteRequestTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for VoteRequestTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = VoteRequestTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct VoteRequestPartition {
+    pub partition_index: i32,
+    // The bumped epoch of the candidate sending the request.
+    pub candidate_epoch: i32,
+    // The ID of the voter sending the request.
+    pub candidate_id: BrokerId,
+    // The epoch of the last record written to the metadata log.
+    pub last_offset_epoch: i32,
+    // The offset of the last record written to the metadata log.
+    pub last_offset: i64,
+}
+
+builder! {
+    VoteRequestPartition => VoteRequestPartitionBuilder {
+        partition_index: i32,
+        candidate_epoch: i32,
+        candidate_id: BrokerId,
+        last_offset_epoch: i32,
+        last_offset: i64,
+    }
+}
+
+impl KafkaProtoEncodable for VoteRequestPartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int32(self.candidate_epoch)?;
+        s.emit_int32(self.candidate_id)?;
+        s.emit_int32(self.last_offset_epoch)?;
+        s.emit_int64(self.last_offset)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for VoteRequestPartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = VoteRequestPartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            candidate_epoch: d.read_field("candidate_epoch", |d| d.read_int32())?,
+            candidate_id: d.read_field("candidate_id", |d| d.read_int32())?,
+            last_offset_epoch: d.read_field("last_offset_epoch", |d| d.read_int32())?,
+            last_offset: d.read_field("last_offset", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for VoteRequest {
+    const API_KEY: i16 = 52;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+impl KafkaRequest for VoteRequest {
+    type Response = VoteResponse;
+}
+
+#[derive(Default)]
+pub struct VoteResponse {
+    // The top level error code.
+    pub error_code: i16,
+    pub topics: Vec<VoteResponseTopic>,
+}
+
+builder! {
+    VoteResponse => VoteResponseBuilder {
+        error_code: i16,
+        topics: Vec<VoteResponseTopic>,
+    }
+}
+
+impl KafkaProtoEncodable for VoteResponse {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(self.error_code)?;
+        s.emit_array(ver, self.topics.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for VoteResponse {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = VoteResponse {
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            topics: d.read_field("topics", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct VoteResponseTopic {
+    pub topic_name: TopicName,
+    pub partitions: Vec<VoteResponsePartition>,
+}
+
+builder! {
+    VoteResponseTopic => VoteResponseTopicBuilder {
+        topic_name: TopicName,
+        partitions: Vec<VoteResponsePartition>,
+    }
+}
+
+impl KafkaProtoEncodable for VoteResponseTopic {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.topic_name)?;
+        s.emit_array(ver, self.partitions.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for VoteResponseTopic {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = VoteResponseTopic {
+            topic_name: d.read_field("topic_name", |d| d.read_string())?.to_string(),
+            partitions: d.read_field("partitions", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct VoteResponsePartition {
+    pub partition_index: i32,
+    pub error_code: i16,
+    // The ID of the current leader or -1 if the leader is unknown.
+    pub leader_id: BrokerId,
+    // The latest known leader epoch.
+    pub leader_epoch: i32,
+    // True if the vote was granted and false otherwise.
+    pub vote_granted: bool,
+}
+
+builder! {
+    VoteResponsePartition => VoteResponsePartitionBuilder {
+        partition_index: i32,
+        error_code: i16,
+        leader_id: BrokerId,
+        leader_epoch: i32,
+        vote_granted: bool,
+    }
+}
+
+impl KafkaProtoEncodable for VoteResponsePartition {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_index)?;
+        s.emit_int16(self.error_code)?;
+        s.emit_int32(self.leader_id)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_bool(self.vote_granted)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for VoteResponsePartition {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = VoteResponsePartition {
+            partition_index: d.read_field("partition_index", |d| d.read_int32())?,
+            error_code: d.read_field("error_code", |d| d.read_int16())?,
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            vote_granted: d.read_field("vote_granted", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for VoteResponse {
+    const API_KEY: i16 = 52;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
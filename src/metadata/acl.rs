@@ -0,0 +1,112 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::KafkaMessage;
+
+#[derive(Default)]
+pub struct AccessControlEntryRecord {
+    pub id: uuid::Uuid,
+    pub resource_type: i8,
+    pub resource_name: String,
+    pub pattern_type: i8,
+    pub principal: String,
+    pub host: String,
+    pub operation: i8,
+    pub permission_type: i8,
+}
+
+builder! {
+    AccessControlEntryRecord => AccessControlEntryRecordBuilder {
+        id: uuid::Uuid,
+        resource_type: i8,
+        resource_name: String,
+        pattern_type: i8,
+        principal: String,
+        host: String,
+        operation: i8,
+        permission_type: i8,
+    }
+}
+
+impl KafkaProtoEncodable for AccessControlEntryRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(self.id)?;
+        s.emit_int8(self.resource_type)?;
+        s.emit_string(&self.resource_name)?;
+        s.emit_int8(self.pattern_type)?;
+        s.emit_string(&self.principal)?;
+        s.emit_string(&self.host)?;
+        s.emit_int8(self.operation)?;
+        s.emit_int8(self.permission_type)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for AccessControlEntryRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = AccessControlEntryRecord {
+            id: d.read_field("id", |d| d.read_uuid())?,
+            resource_type: d.read_field("resource_type", |d| d.read_int8())?,
+            resource_name: d
+                .read_field("resource_name", |d| d.read_string())?
+                .to_string(),
+            pattern_type: d.read_field("pattern_type", |d| d.read_int8())?,
+            principal: d.read_field("principal", |d| d.read_string())?.to_string(),
+            host: d.read_field("host", |d| d.read_string())?.to_string(),
+            operation: d.read_field("operation", |d| d.read_int8())?,
+            permission_type: d.read_field("permission_type", |d| d.read_int8())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for AccessControlEntryRecord {
+    const API_KEY: i16 = 6;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct RemoveAccessControlEntryRecord {
+    pub id: uuid::Uuid,
+}
+
+builder! {
+    RemoveAccessControlEntryRecord => RemoveAccessControlEntryRecordBuilder {
+        id: uuid::Uuid,
+    }
+}
+
+impl KafkaProtoEncodable for RemoveAccessControlEntryRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(self.id)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for RemoveAccessControlEntryRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = RemoveAccessControlEntryRecord {
+            id: d.read_field("id", |d| d.read_uuid())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for RemoveAccessControlEntryRecord {
+    const API_KEY: i16 = 7;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
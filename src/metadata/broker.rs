@@ -0,0 +1,451 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+use linked_hash_map::LinkedHashMap;
+
+use crate::{BrokerId, KafkaMessage};
+
+pub struct RegisterBrokerRecord {
+    pub broker_id: BrokerId,
+
+    // versions: 2+, default=false
+    // True if the broker is a ZK broker in migration mode.
+    pub is_migrating_zk_broker: bool,
+
+    // The incarnation id of the broker process.
+    pub incarnation_id: uuid::Uuid,
+    pub broker_epoch: i64,
+
+    // key: name
+    pub end_points: LinkedHashMap<String, RegisterBrokerEndpoint>,
+    // key: name
+    pub features: LinkedHashMap<String, RegisterBrokerFeature>,
+
+    pub rack: Option<String>,
+    // True if the broker is fenced, default=true
+    pub fenced: bool,
+
+    // versions: 1+, default=false
+    pub in_controlled_shutdown: bool,
+
+    // versions: 3+
+    // Log directories configured in this broker which are available.
+    pub log_dirs: Vec<uuid::Uuid>,
+}
+
+impl Default for RegisterBrokerRecord {
+    fn default() -> Self {
+        RegisterBrokerRecord {
+            broker_id: 0,
+            is_migrating_zk_broker: false,
+            incarnation_id: uuid::Uuid::nil(),
+            broker_epoch: 0,
+            end_points: LinkedHashMap::new(),
+            features: LinkedHashMap::new(),
+            rack: None,
+            fenced: true,
+            in_controlled_shutdown: false,
+            log_dirs: Vec::new(),
+        }
+    }
+}
+
+builder! {
+    RegisterBrokerRecord => RegisterBrokerRecordBuilder {
+        broker_id: BrokerId,
+        is_migrating_zk_broker: bool,
+        incarnation_id: uuid::Uuid,
+        broker_epoch: i64,
+        end_points: LinkedHashMap<String, RegisterBrokerEndpoint>,
+        features: LinkedHashMap<String, RegisterBrokerFeature>,
+        rack: Option<String>,
+        fenced: bool,
+        in_controlled_shutdown: bool,
+        log_dirs: Vec<uuid::Uuid>,
+    }
+}
+
+impl KafkaProtoEncodable for RegisterBrokerRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        if ver >= 2 {
+            s.emit_bool(self.is_migrating_zk_broker)?;
+        } else {
+            s.check_absent_field("is_migrating_zk_broker", ver, !self.is_migrating_zk_broker)?;
+        }
+        s.emit_uuid(self.incarnation_id)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_array(ver, self.end_points.values())?;
+        s.emit_array(ver, self.features.values())?;
+        s.emit_nullable_string(self.rack.as_deref())?;
+        s.emit_bool(self.fenced)?;
+        if ver >= 1 {
+            s.emit_bool(self.in_controlled_shutdown)?;
+        } else {
+            s.check_absent_field("in_controlled_shutdown", ver, !self.in_controlled_shutdown)?;
+        }
+        if ver >= 3 {
+            s.emit_array(ver, self.log_dirs.iter())?;
+        } else {
+            s.check_absent_field("log_dirs", ver, self.log_dirs.is_empty())?;
+        }
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for RegisterBrokerRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = RegisterBrokerRecord {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            is_migrating_zk_broker: if ver >= 2 {
+                d.read_field("is_migrating_zk_broker", |d| d.read_bool())?
+            } else {
+                false
+            },
+            incarnation_id: d.read_field("incarnation_id", |d| d.read_uuid())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+            end_points: d.read_field("end_points", |d| {
+                d.read_map(ver, |e: &RegisterBrokerEndpoint| e.name.clone())
+            })?,
+            features: d.read_field("features", |d| {
+                d.read_map(ver, |f: &RegisterBrokerFeature| f.name.clone())
+            })?,
+            rack: d
+                .read_field("rack", |d| d.read_nullable_string())?
+                .map(Into::into),
+            fenced: d.read_field("fenced", |d| d.read_bool())?,
+            in_controlled_shutdown: if ver >= 1 {
+                d.read_field("in_controlled_shutdown", |d| d.read_bool())?
+            } else {
+                false
+            },
+            log_dirs: if ver >= 3 {
+                d.read_field("log_dirs", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct RegisterBrokerEndpoint {
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub security_protocol: i16,
+}
+
+builder! {
+    RegisterBrokerEndpoint => RegisterBrokerEndpointBuilder {
+        name: String,
+        host: String,
+        port: u16,
+        security_protocol: i16,
+    }
+}
+
+impl KafkaProtoEncodable for RegisterBrokerEndpoint {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_string(&self.host)?;
+        s.emit_uint16(self.port)?;
+        s.emit_int16(self.security_protocol)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for RegisterBrokerEndpoint {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = RegisterBrokerEndpoint {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            host: d.read_field("host", |d| d.read_string())?.to_string(),
+            port: d.read_field("port", |d| d.read_uint16())?,
+            security_protocol: d.read_field("security_protocol", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct RegisterBrokerFeature {
+    pub name: String,
+    pub min_supported_version: i16,
+    pub max_supported_version: i16,
+}
+
+builder! {
+    RegisterBrokerFeature => RegisterBrokerFeatureBuilder {
+        name: String,
+        min_supported_version: i16,
+        max_supported_version: i16,
+    }
+}
+
+impl KafkaProtoEncodable for RegisterBrokerFeature {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_int16(self.min_supported_version)?;
+        s.emit_int16(self.max_supported_version)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for RegisterBrokerFeature {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = RegisterBrokerFeature {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            min_supported_version: d.read_field("min_supported_version", |d| d.read_int16())?,
+            max_supported_version: d.read_field("max_supported_version", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for RegisterBrokerRecord {
+    const API_KEY: i16 = 0;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 3;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct UnregisterBrokerRecord {
+    pub broker_id: BrokerId,
+    pub broker_epoch: i64,
+}
+
+builder! {
+    UnregisterBrokerRecord => UnregisterBrokerRecordBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for UnregisterBrokerRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UnregisterBrokerRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = UnregisterBrokerRecord {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for UnregisterBrokerRecord {
+    const API_KEY: i16 = 1;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct FenceBrokerRecord {
+    pub id: BrokerId,
+    pub epoch: i64,
+}
+
+builder! {
+    FenceBrokerRecord => FenceBrokerRecordBuilder {
+        id: BrokerId,
+        epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for FenceBrokerRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.id)?;
+        s.emit_int64(self.epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FenceBrokerRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = FenceBrokerRecord {
+            id: d.read_field("id", |d| d.read_int32())?,
+            epoch: d.read_field("epoch", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for FenceBrokerRecord {
+    const API_KEY: i16 = 8;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct UnfenceBrokerRecord {
+    pub id: BrokerId,
+    pub epoch: i64,
+}
+
+builder! {
+    UnfenceBrokerRecord => UnfenceBrokerRecordBuilder {
+        id: BrokerId,
+        epoch: i64,
+    }
+}
+
+impl KafkaProtoEncodable for UnfenceBrokerRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.id)?;
+        s.emit_int64(self.epoch)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for UnfenceBrokerRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = UnfenceBrokerRecord {
+            id: d.read_field("id", |d| d.read_int32())?,
+            epoch: d.read_field("epoch", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for UnfenceBrokerRecord {
+    const API_KEY: i16 = 9;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct BrokerRegistrationChangeRecord {
+    pub broker_id: BrokerId,
+    pub broker_epoch: i64,
+
+    // versions: 0+, tag: 0, default=0
+    // -1 if the broker has been unfenced, 0 if no change, 1 if the broker has
+    // been fenced.
+    pub fenced: i8,
+
+    // versions: 1+, tag: 1, default=0
+    // 0 if no change, 1 if the broker is in controlled shutdown.
+    pub in_controlled_shutdown: i8,
+
+    // versions: 2+, tag: 2
+    // Log directories configured in this broker which are available.
+    pub log_dirs: Option<Vec<uuid::Uuid>>,
+}
+
+builder! {
+    BrokerRegistrationChangeRecord => BrokerRegistrationChangeRecordBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+        fenced: i8,
+        in_controlled_shutdown: i8,
+        log_dirs: Option<Vec<uuid::Uuid>>,
+    }
+}
+
+impl KafkaProtoEncodable for BrokerRegistrationChangeRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_int64(self.broker_epoch)?;
+
+        if ver < 1 {
+            s.check_absent_field(
+                "in_controlled_shutdown",
+                ver,
+                self.in_controlled_shutdown == 0,
+            )?;
+        }
+        if ver < 2 {
+            s.check_absent_field("log_dirs", ver, self.log_dirs.is_none())?;
+        }
+
+        let emit_fenced = self.fenced != 0;
+        let emit_in_controlled_shutdown = self.in_controlled_shutdown != 0;
+        let mut r = s.emit_tagged_fields_hdr(
+            emit_fenced as usize
+                + emit_in_controlled_shutdown as usize
+                + self.log_dirs.is_some() as usize,
+        )?;
+        if emit_fenced {
+            r = s.emit_tagged_field(0, ver, &self.fenced)?;
+        }
+        if emit_in_controlled_shutdown {
+            r = s.emit_tagged_field(1, ver, &self.in_controlled_shutdown)?;
+        }
+        if let Some(log_dirs) = &self.log_dirs {
+            r = s.emit_tagged_field(2, ver, log_dirs)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for BrokerRegistrationChangeRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = BrokerRegistrationChangeRecord {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+            fenced: 0,
+            in_controlled_shutdown: 0,
+            log_dirs: None,
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.fenced = d.read_field("fenced", |d| d.read_int8())?;
+                Ok(true)
+            }
+            1 if ver >= 1 => {
+                r.in_controlled_shutdown =
+                    d.read_field("in_controlled_shutdown", |d| d.read_int8())?;
+                Ok(true)
+            }
+            2 if ver >= 2 => {
+                r.log_dirs = Some(d.read_field("log_dirs", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for BrokerRegistrationChangeRecord {
+    const API_KEY: i16 = 15;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 2;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,239 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::messages::ClientQuotaEntity;
+use crate::{BrokerId, KafkaMessage, ProducerId};
+
+#[derive(Default)]
+pub struct ConfigRecord {
+    // The type of resource this configuration applies to.
+    pub resource_type: i8,
+    pub resource_name: String,
+    pub name: String,
+    // The value of the configuration, or null if it is being deleted.
+    pub value: Option<String>,
+}
+
+builder! {
+    ConfigRecord => ConfigRecordBuilder {
+        resource_type: i8,
+        resource_name: String,
+        name: String,
+        value: Option<String>,
+    }
+}
+
+impl KafkaProtoEncodable for ConfigRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int8(self.resource_type)?;
+        s.emit_string(&self.resource_name)?;
+        s.emit_string(&self.name)?;
+        s.emit_nullable_string(self.value.as_deref())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ConfigRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = ConfigRecord {
+            resource_type: d.read_field("resource_type", |d| d.read_int8())?,
+            resource_name: d
+                .read_field("resource_name", |d| d.read_string())?
+                .to_string(),
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            value: d
+                .read_field("value", |d| d.read_nullable_string())?
+                .map(Into::into),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ConfigRecord {
+    const API_KEY: i16 = 4;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct FeatureLevelRecord {
+    pub name: String,
+    // The current finalized feature level of this feature for the cluster, a
+    // value of 0 means feature not supported.
+    pub feature_level: i16,
+}
+
+builder! {
+    FeatureLevelRecord => FeatureLevelRecordBuilder {
+        name: String,
+        feature_level: i16,
+    }
+}
+
+impl KafkaProtoEncodable for FeatureLevelRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_int16(self.feature_level)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for FeatureLevelRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = FeatureLevelRecord {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            feature_level: d.read_field("feature_level", |d| d.read_int16())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for FeatureLevelRecord {
+    const API_KEY: i16 = 12;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct ClientQuotaRecord {
+    pub entity: Vec<ClientQuotaEntity>,
+    pub key: String,
+    pub value: f64,
+    // Whether the quota configuration value should be removed.
+    pub remove: bool,
+}
+
+builder! {
+    ClientQuotaRecord => ClientQuotaRecordBuilder {
+        entity: Vec<ClientQuotaEntity>,
+        key: String,
+        value: f64,
+        remove: bool,
+    }
+}
+
+impl KafkaProtoEncodable for ClientQuotaRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.entity.iter())?;
+        s.emit_string(&self.key)?;
+        s.emit_float64(self.value)?;
+        s.emit_bool(self.remove)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ClientQuotaRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = ClientQuotaRecord {
+            entity: d.read_field("entity", |d| d.read_array(ver))?,
+            key: d.read_field("key", |d| d.read_string())?.to_string(),
+            value: d.read_field("value", |d| d.read_float64())?,
+            remove: d.read_field("remove", |d| d.read_bool())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ClientQuotaRecord {
+    const API_KEY: i16 = 13;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct ProducerIdsRecord {
+    // The ID of the requesting broker
+    pub broker_id: BrokerId,
+    pub broker_epoch: i64,
+    // The next producerId that will be assigned (i.e. the first producerId in
+    // the next assigned block)
+    pub next_producer_id: ProducerId,
+}
+
+builder! {
+    ProducerIdsRecord => ProducerIdsRecordBuilder {
+        broker_id: BrokerId,
+        broker_epoch: i64,
+        next_producer_id: ProducerId,
+    }
+}
+
+impl KafkaProtoEncodable for ProducerIdsRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.broker_id)?;
+        s.emit_int64(self.broker_epoch)?;
+        s.emit_int64(self.next_producer_id)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for ProducerIdsRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = ProducerIdsRecord {
+            broker_id: d.read_field("broker_id", |d| d.read_int32())?,
+            broker_epoch: d.read_field("broker_epoch", |d| d.read_int64())?,
+            next_producer_id: d.read_field("next_producer_id", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for ProducerIdsRecord {
+    const API_KEY: i16 = 14;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+// Written by the active controller to advance the high watermark when there is
+// nothing else to commit
+#[derive(Default)]
+pub struct NoOpRecord;
+
+builder! {
+    NoOpRecord => NoOpRecordBuilder {}
+}
+
+impl KafkaProtoEncodable for NoOpRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for NoOpRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        d.skip_tagged_fields()?;
+        Ok(NoOpRecord)
+    }
+}
+
+impl KafkaMessage for NoOpRecord {
+    const API_KEY: i16 = 20;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
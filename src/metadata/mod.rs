@@ -0,0 +1,173 @@
+// Records of the KRaft __cluster_metadata log and its snapshots.
+//
+// Value of every record is framed as ApiMessageAndVersion:
+//
+//   frame_version => UNSIGNED_VARINT, always 1
+//   api_key => UNSIGNED_VARINT, type of the record
+//   version => UNSIGNED_VARINT, version of the record
+//   record => flexible encoding of the record
+//
+// Ref: https://github.com/apache/kafka/blob/3.7.0/server-common/src/main/java/org/apache/kafka/server/common/serialization/AbstractApiMessageSerde.java
+use std::convert::TryInto;
+use std::io::Write;
+
+use crate::de::{KafkaFlexiDeserializer, KafkaProtoDecodable, KafkaProtoDecoder};
+use crate::error::{Error, Result};
+use crate::ser::{KafkaFlexiSerializer, KafkaProtoEncodable, KafkaProtoEncoder};
+use crate::{Bytes, KafkaMessage};
+
+mod acl;
+mod broker;
+mod config;
+mod snapshot;
+#[cfg(test)]
+mod tests;
+mod topic;
+
+pub use acl::*;
+pub use broker::*;
+pub use config::*;
+pub use snapshot::*;
+pub use topic::*;
+
+pub enum MetadataRecord {
+    RegisterBroker(RegisterBrokerRecord),
+    UnregisterBroker(UnregisterBrokerRecord),
+    Topic(TopicRecord),
+    Partition(PartitionRecord),
+    Config(ConfigRecord),
+    PartitionChange(PartitionChangeRecord),
+    AccessControlEntry(AccessControlEntryRecord),
+    RemoveAccessControlEntry(RemoveAccessControlEntryRecord),
+    FenceBroker(FenceBrokerRecord),
+    UnfenceBroker(UnfenceBrokerRecord),
+    RemoveTopic(RemoveTopicRecord),
+    FeatureLevel(FeatureLevelRecord),
+    ClientQuota(ClientQuotaRecord),
+    ProducerIds(ProducerIdsRecord),
+    BrokerRegistrationChange(BrokerRegistrationChangeRecord),
+    NoOp(NoOpRecord),
+    // Record types this crate doesn't know about, with their api key
+    Unknown(i16),
+}
+
+impl MetadataRecord {
+    pub fn api_key(&self) -> i16 {
+        match self {
+            MetadataRecord::RegisterBroker(_) => RegisterBrokerRecord::API_KEY,
+            MetadataRecord::UnregisterBroker(_) => UnregisterBrokerRecord::API_KEY,
+            MetadataRecord::Topic(_) => TopicRecord::API_KEY,
+            MetadataRecord::Partition(_) => PartitionRecord::API_KEY,
+            MetadataRecord::Config(_) => ConfigRecord::API_KEY,
+            MetadataRecord::PartitionChange(_) => PartitionChangeRecord::API_KEY,
+            MetadataRecord::AccessControlEntry(_) => AccessControlEntryRecord::API_KEY,
+            MetadataRecord::RemoveAccessControlEntry(_) => RemoveAccessControlEntryRecord::API_KEY,
+            MetadataRecord::FenceBroker(_) => FenceBrokerRecord::API_KEY,
+            MetadataRecord::UnfenceBroker(_) => UnfenceBrokerRecord::API_KEY,
+            MetadataRecord::RemoveTopic(_) => RemoveTopicRecord::API_KEY,
+            MetadataRecord::FeatureLevel(_) => FeatureLevelRecord::API_KEY,
+            MetadataRecord::ClientQuota(_) => ClientQuotaRecord::API_KEY,
+            MetadataRecord::ProducerIds(_) => ProducerIdsRecord::API_KEY,
+            MetadataRecord::BrokerRegistrationChange(_) => BrokerRegistrationChangeRecord::API_KEY,
+            MetadataRecord::NoOp(_) => NoOpRecord::API_KEY,
+            MetadataRecord::Unknown(api_key) => *api_key,
+        }
+    }
+
+    fn serialize<W: Write>(&self, ver: i16, s: &mut KafkaFlexiSerializer<W>) -> Result<()> {
+        match self {
+            MetadataRecord::RegisterBroker(r) => r.serialize(ver, s),
+            MetadataRecord::UnregisterBroker(r) => r.serialize(ver, s),
+            MetadataRecord::Topic(r) => r.serialize(ver, s),
+            MetadataRecord::Partition(r) => r.serialize(ver, s),
+            MetadataRecord::Config(r) => r.serialize(ver, s),
+            MetadataRecord::PartitionChange(r) => r.serialize(ver, s),
+            MetadataRecord::AccessControlEntry(r) => r.serialize(ver, s),
+            MetadataRecord::RemoveAccessControlEntry(r) => r.serialize(ver, s),
+            MetadataRecord::FenceBroker(r) => r.serialize(ver, s),
+            MetadataRecord::UnfenceBroker(r) => r.serialize(ver, s),
+            MetadataRecord::RemoveTopic(r) => r.serialize(ver, s),
+            MetadataRecord::FeatureLevel(r) => r.serialize(ver, s),
+            MetadataRecord::ClientQuota(r) => r.serialize(ver, s),
+            MetadataRecord::ProducerIds(r) => r.serialize(ver, s),
+            MetadataRecord::BrokerRegistrationChange(r) => r.serialize(ver, s),
+            MetadataRecord::NoOp(r) => r.serialize(ver, s),
+            MetadataRecord::Unknown(api_key) => Err(Error::UnknownApiKey(*api_key)),
+        }
+    }
+
+    fn deserialize(api_key: i16, ver: i16, d: &mut KafkaFlexiDeserializer) -> Result<Self> {
+        let r = match api_key {
+            RegisterBrokerRecord::API_KEY => MetadataRecord::RegisterBroker(read(ver, d)?),
+            UnregisterBrokerRecord::API_KEY => MetadataRecord::UnregisterBroker(read(ver, d)?),
+            TopicRecord::API_KEY => MetadataRecord::Topic(read(ver, d)?),
+            PartitionRecord::API_KEY => MetadataRecord::Partition(read(ver, d)?),
+            ConfigRecord::API_KEY => MetadataRecord::Config(read(ver, d)?),
+            PartitionChangeRecord::API_KEY => MetadataRecord::PartitionChange(read(ver, d)?),
+            AccessControlEntryRecord::API_KEY => MetadataRecord::AccessControlEntry(read(ver, d)?),
+            RemoveAccessControlEntryRecord::API_KEY => {
+                MetadataRecord::RemoveAccessControlEntry(read(ver, d)?)
+            }
+            FenceBrokerRecord::API_KEY => MetadataRecord::FenceBroker(read(ver, d)?),
+            UnfenceBrokerRecord::API_KEY => MetadataRecord::UnfenceBroker(read(ver, d)?),
+            RemoveTopicRecord::API_KEY => MetadataRecord::RemoveTopic(read(ver, d)?),
+            FeatureLevelRecord::API_KEY => MetadataRecord::FeatureLevel(read(ver, d)?),
+            ClientQuotaRecord::API_KEY => MetadataRecord::ClientQuota(read(ver, d)?),
+            ProducerIdsRecord::API_KEY => MetadataRecord::ProducerIds(read(ver, d)?),
+            BrokerRegistrationChangeRecord::API_KEY => {
+                MetadataRecord::BrokerRegistrationChange(read(ver, d)?)
+            }
+            NoOpRecord::API_KEY => MetadataRecord::NoOp(read(ver, d)?),
+            api_key => MetadataRecord::Unknown(api_key),
+        };
+        Ok(r)
+    }
+}
+
+// Newer versions of a record may add fields which can not be skipped
+fn read<M>(ver: i16, d: &mut KafkaFlexiDeserializer) -> Result<M>
+where
+    M: KafkaProtoDecodable + KafkaMessage,
+{
+    if ver < M::MIN_API_VERSION || ver > M::MAX_API_VERSION {
+        return Err(Error::UnsupportedVersion(ver));
+    }
+    M::deserialize(ver, d)
+}
+
+pub struct ApiMessageAndVersion {
+    pub version: i16,
+    pub message: MetadataRecord,
+}
+
+impl ApiMessageAndVersion {
+    pub const FRAME_VERSION: u32 = 1;
+
+    pub fn to_bytes(&self) -> Result<Bytes> {
+        let mut buf = Vec::new();
+        let mut s = KafkaFlexiSerializer::new(true, &mut buf);
+        s.serializer.emit_unsigned_varint(Self::FRAME_VERSION)?;
+        s.serializer
+            .emit_unsigned_varint(self.message.api_key().try_into()?)?;
+        s.serializer
+            .emit_unsigned_varint(self.version.try_into()?)?;
+        self.message.serialize(self.version, &mut s)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        let mut d = KafkaFlexiDeserializer::new(true, buf);
+        let frame_version = d.de.read_unsigned_varint()?;
+        if frame_version != Self::FRAME_VERSION {
+            return Err(Error::UnsupportedVersion(
+                frame_version.try_into().unwrap_or(i16::MAX),
+            ));
+        }
+        let api_key = d.de.read_unsigned_varint()?.try_into()?;
+        let version = d.de.read_unsigned_varint()?.try_into()?;
+        Ok(ApiMessageAndVersion {
+            version,
+            message: MetadataRecord::deserialize(api_key, version, &mut d)?,
+        })
+    }
+}
@@ -0,0 +1,165 @@
+// KRaft snapshot, stored as `<end offset>-<epoch>.checkpoint` file in the
+// __cluster_metadata-0 directory. File is a sequence of record batches:
+//
+//   control batch with SnapshotHeaderRecord at offset 0
+//   batches of ApiMessageAndVersion values
+//   control batch with SnapshotFooterRecord
+//
+// Ref: https://github.com/apache/kafka/blob/3.7.0/raft/src/main/java/org/apache/kafka/snapshot/RecordsSnapshotWriter.java
+use std::convert::TryInto;
+use std::io::Write;
+
+use super::ApiMessageAndVersion;
+use crate::error::{Error, Result};
+use crate::messages::SnapshotId;
+use crate::records::{ControlRecord, Record, RecordBatch, RecordBatches};
+use crate::records::{SnapshotFooterRecord, SnapshotHeaderRecord};
+use crate::records::{NO_PRODUCER_EPOCH, NO_PRODUCER_ID, NO_SEQUENCE};
+
+pub const SNAPSHOT_SUFFIX: &str = ".checkpoint";
+
+impl SnapshotId {
+    // Offsets and epochs are zero padded, so that file names sort by snapshot id
+    pub fn file_name(&self) -> String {
+        format!(
+            "{:020}-{:010}{}",
+            self.end_offset, self.epoch, SNAPSHOT_SUFFIX
+        )
+    }
+
+    // Returns None for files which are not complete snapshots, e.g. partial
+    // (.checkpoint.part) or deleted (.checkpoint.deleted) ones.
+    pub fn from_file_name(name: &str) -> Option<SnapshotId> {
+        let mut parts = name.strip_suffix(SNAPSHOT_SUFFIX)?.splitn(2, '-');
+        Some(SnapshotId {
+            end_offset: parts.next()?.parse().ok()?,
+            epoch: parts.next()?.parse().ok()?,
+        })
+    }
+}
+
+pub struct Snapshot {
+    pub header: SnapshotHeaderRecord,
+    pub records: Vec<ApiMessageAndVersion>,
+    // None if the snapshot is truncated
+    pub footer: Option<SnapshotFooterRecord>,
+}
+
+impl Snapshot {
+    pub fn from_bytes(buf: &[u8]) -> Result<Self> {
+        let mut batches = RecordBatches::new(buf);
+
+        let header = match batches.next().transpose()? {
+            Some(batch) => match batch.control_record()? {
+                Some(ControlRecord::SnapshotHeader(header)) => header,
+                _ => return Err(Error::MalformedSnapshot),
+            },
+            None => return Err(Error::MalformedSnapshot),
+        };
+
+        let mut snapshot = Snapshot {
+            header,
+            records: Vec::new(),
+            footer: None,
+        };
+        for batch in batches {
+            let batch = batch?;
+            match batch.control_record()? {
+                None => {
+                    for r in &batch.records {
+                        let value = r.value.as_deref().ok_or(Error::InvalidRecord)?;
+                        snapshot
+                            .records
+                            .push(ApiMessageAndVersion::from_bytes(value)?);
+                    }
+                }
+                Some(ControlRecord::SnapshotFooter(footer)) => snapshot.footer = Some(footer),
+                // Other control records (e.g. KRaft voters) are skipped
+                Some(_) => {}
+            }
+        }
+        Ok(snapshot)
+    }
+}
+
+// Writes batches of a snapshot one by one. Every batch is stamped with
+// last_contained_log_timestamp, which keeps the output reproducible.
+pub struct SnapshotWriter<W> {
+    writer: W,
+    snapshot_id: SnapshotId,
+    timestamp: i64,
+    next_offset: i64,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    // Writes the header batch
+    pub fn new(
+        writer: W,
+        snapshot_id: SnapshotId,
+        last_contained_log_timestamp: i64,
+    ) -> Result<SnapshotWriter<W>> {
+        let mut w = SnapshotWriter {
+            writer,
+            snapshot_id,
+            timestamp: last_contained_log_timestamp,
+            next_offset: 0,
+        };
+        let header = SnapshotHeaderRecord {
+            last_contained_log_timestamp,
+        };
+        w.write_control(ControlRecord::SnapshotHeader(header))?;
+        Ok(w)
+    }
+
+    // Writes records as a single batch
+    pub fn append(&mut self, records: &[ApiMessageAndVersion]) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let records = records
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                Ok(Record {
+                    attributes: 0,
+                    timestamp_delta: 0,
+                    offset_delta: i.try_into()?,
+                    key: None,
+                    value: Some(r.to_bytes()?),
+                    headers: Vec::new(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.write_batch(0, records)
+    }
+
+    // Writes the footer batch and returns the underlying writer
+    pub fn finish(mut self) -> Result<W> {
+        self.write_control(ControlRecord::SnapshotFooter(SnapshotFooterRecord))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_control(&mut self, c: ControlRecord) -> Result<()> {
+        self.write_batch(RecordBatch::CONTROL_FLAG_MASK, vec![c.to_record(0)?])
+    }
+
+    fn write_batch(&mut self, attributes: i16, records: Vec<Record>) -> Result<()> {
+        let last_offset_delta: i32 = (records.len() - 1).try_into()?;
+        let batch = RecordBatch {
+            base_offset: self.next_offset,
+            partition_leader_epoch: self.snapshot_id.epoch,
+            attributes,
+            last_offset_delta,
+            base_timestamp: self.timestamp,
+            max_timestamp: self.timestamp,
+            producer_id: NO_PRODUCER_ID,
+            producer_epoch: NO_PRODUCER_EPOCH,
+            base_sequence: NO_SEQUENCE,
+            records,
+        };
+        batch.write(&mut self.writer)?;
+        self.next_offset = batch.last_offset() + 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,444 @@
+use std::iter::once;
+
+use super::*;
+use crate::messages::{ClientQuotaEntity, SnapshotId};
+use crate::records::RecordBatch;
+use crate::TopicId;
+
+fn topic(name: &str, id: u128) -> ApiMessageAndVersion {
+    ApiMessageAndVersion {
+        version: 0,
+        message: MetadataRecord::Topic(TopicRecord {
+            name: name.to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(id)),
+        }),
+    }
+}
+
+fn id(n: u128) -> [u8; 16] {
+    n.to_be_bytes()
+}
+
+// Frame of `message` at `version` is `expected`, which decodes to a record of
+// the same type and version encoding to the same bytes
+fn check_record(version: i16, message: MetadataRecord, expected: &[u8]) {
+    let r = ApiMessageAndVersion { version, message };
+    assert_eq!(r.to_bytes().unwrap(), expected, "version {}", version);
+
+    let decoded = ApiMessageAndVersion::from_bytes(expected).unwrap();
+    assert_eq!(
+        (decoded.version, decoded.message.api_key()),
+        (version, r.message.api_key())
+    );
+    assert_eq!(decoded.to_bytes().unwrap(), expected, "version {}", version);
+}
+
+fn write_snapshot() -> Vec<u8> {
+    let id = SnapshotId {
+        end_offset: 3,
+        epoch: 7,
+    };
+    let mut w = SnapshotWriter::new(Vec::new(), id, 1_600_000_000_000).unwrap();
+    w.append(&[topic("a", 1), topic("b", 2)]).unwrap();
+    w.append(&[]).unwrap();
+    w.append(&[topic("c", 3)]).unwrap();
+    w.finish().unwrap()
+}
+
+#[test]
+fn snapshot_round_trip() {
+    let buf = write_snapshot();
+    let snapshot = Snapshot::from_bytes(&buf).unwrap();
+
+    assert_eq!(
+        snapshot.header.last_contained_log_timestamp,
+        1_600_000_000_000
+    );
+    assert!(snapshot.footer.is_some());
+    let topics: Vec<(i16, &str, TopicId)> = snapshot
+        .records
+        .iter()
+        .map(|r| match &r.message {
+            MetadataRecord::Topic(t) => (r.version, t.name.as_str(), t.topic_id),
+            _ => panic!("unexpected record type {}", r.message.api_key()),
+        })
+        .collect();
+    assert_eq!(
+        topics,
+        vec![
+            (0, "a", TopicId(uuid::Uuid::from_u128(1))),
+            (0, "b", TopicId(uuid::Uuid::from_u128(2))),
+            (0, "c", TopicId(uuid::Uuid::from_u128(3))),
+        ]
+    );
+
+    // Offsets are contiguous from 0, every batch carries snapshot epoch
+    let batches: Vec<RecordBatch> = crate::records::RecordBatches::new(&buf)
+        .collect::<Result<_>>()
+        .unwrap();
+    let offsets: Vec<(i64, i64)> = batches
+        .iter()
+        .map(|b| (b.base_offset, b.last_offset()))
+        .collect();
+    assert_eq!(offsets, vec![(0, 0), (1, 2), (3, 3), (4, 4)]);
+    assert!(batches.iter().all(|b| b.partition_leader_epoch == 7));
+}
+
+#[test]
+fn truncated_snapshot_has_no_footer() {
+    let buf = write_snapshot();
+    let snapshot = Snapshot::from_bytes(&buf[..buf.len() - 1]).unwrap();
+    assert_eq!(snapshot.records.len(), 3);
+    assert!(snapshot.footer.is_none());
+
+    assert!(matches!(
+        Snapshot::from_bytes(&[]),
+        Err(Error::MalformedSnapshot)
+    ));
+}
+
+#[test]
+fn partition_defaults_follow_schema() {
+    let p = PartitionRecord::default();
+    assert_eq!(
+        (p.partition_id, p.leader, p.leader_epoch, p.partition_epoch),
+        (-1, -1, -1, -1)
+    );
+    assert_eq!(p.leader_recovery_state, 0);
+
+    let c = PartitionChangeRecord::default();
+    assert_eq!((c.partition_id, c.leader), (-1, NO_LEADER_CHANGE));
+    assert_eq!(c.leader_recovery_state, -1);
+}
+
+#[test]
+fn partition_builder_starts_from_defaults() {
+    let p = PartitionRecord::builder().partition_id(0).leader(1).build();
+    assert_eq!((p.partition_id, p.leader, p.leader_epoch), (0, 1, -1));
+}
+
+#[test]
+fn broker_records_codec() {
+    let register = || RegisterBrokerRecord {
+        broker_id: 1,
+        incarnation_id: uuid::Uuid::from_u128(2),
+        broker_epoch: 3,
+        end_points: once((
+            "e".to_string(),
+            RegisterBrokerEndpoint {
+                name: "e".to_string(),
+                host: "h".to_string(),
+                port: 9092,
+                security_protocol: 0,
+            },
+        ))
+        .collect(),
+        features: once((
+            "f".to_string(),
+            RegisterBrokerFeature {
+                name: "f".to_string(),
+                min_supported_version: 1,
+                max_supported_version: 2,
+            },
+        ))
+        .collect(),
+        ..Default::default()
+    };
+    let end_points = [2, 2, b'e', 2, b'h', 0x23, 0x84, 0, 0, 0];
+    let features = [2, 2, b'f', 0, 1, 0, 2, 0];
+    check_record(
+        0,
+        MetadataRecord::RegisterBroker(register()),
+        &[
+            &[1, 0, 0, 0, 0, 0, 1][..], // frame, broker_id
+            &id(2),
+            &[0, 0, 0, 0, 0, 0, 0, 3], // broker_epoch
+            &end_points,
+            &features,
+            &[0, 1, 0], // rack, fenced
+        ]
+        .concat(),
+    );
+    let r = RegisterBrokerRecord {
+        rack: Some("r".to_string()),
+        log_dirs: vec![uuid::Uuid::from_u128(5)],
+        ..register()
+    };
+    check_record(
+        3,
+        MetadataRecord::RegisterBroker(r),
+        &[
+            &[1, 0, 3, 0, 0, 0, 1, 0][..], // frame, broker_id, is_migrating_zk_broker
+            &id(2),
+            &[0, 0, 0, 0, 0, 0, 0, 3],
+            &end_points,
+            &features,
+            &[2, b'r', 1, 0, 2], // rack, fenced, in_controlled_shutdown, log_dirs
+            &id(5),
+            &[0],
+        ]
+        .concat(),
+    );
+
+    let epoch = [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2]; // id, epoch
+    let r = UnregisterBrokerRecord {
+        broker_id: 1,
+        broker_epoch: 2,
+    };
+    check_record(
+        0,
+        MetadataRecord::UnregisterBroker(r),
+        &[&[1, 1, 0][..], &epoch, &[0]].concat(),
+    );
+    let r = FenceBrokerRecord { id: 1, epoch: 2 };
+    check_record(
+        0,
+        MetadataRecord::FenceBroker(r),
+        &[&[1, 8, 0][..], &epoch, &[0]].concat(),
+    );
+    let r = UnfenceBrokerRecord { id: 1, epoch: 2 };
+    check_record(
+        0,
+        MetadataRecord::UnfenceBroker(r),
+        &[&[1, 9, 0][..], &epoch, &[0]].concat(),
+    );
+
+    // Changes are tagged fields
+    let r = BrokerRegistrationChangeRecord {
+        broker_id: 1,
+        broker_epoch: 2,
+        fenced: -1,
+        ..Default::default()
+    };
+    check_record(
+        0,
+        MetadataRecord::BrokerRegistrationChange(r),
+        &[&[1, 15, 0][..], &epoch, &[1, 0, 1, 0xff]].concat(),
+    );
+    let r = BrokerRegistrationChangeRecord {
+        broker_id: 1,
+        broker_epoch: 2,
+        in_controlled_shutdown: 1,
+        log_dirs: Some(vec![uuid::Uuid::from_u128(5)]),
+        ..Default::default()
+    };
+    check_record(
+        2,
+        MetadataRecord::BrokerRegistrationChange(r),
+        &[&[1, 15, 2][..], &epoch, &[2, 1, 1, 1, 2, 17, 2], &id(5)].concat(),
+    );
+}
+
+#[test]
+fn topic_records_codec() {
+    let partition = || PartitionRecord {
+        partition_id: 0,
+        topic_id: TopicId(uuid::Uuid::from_u128(1)),
+        replicas: vec![1],
+        isr: vec![1],
+        leader: 1,
+        leader_epoch: 2,
+        partition_epoch: 3,
+        ..Default::default()
+    };
+    let fields = [
+        2, 0, 0, 0, 1, 2, 0, 0, 0, 1, 1, 1, // replicas, isr, removing, adding
+        0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, // leader, leader_epoch, partition_epoch
+    ];
+    check_record(
+        0,
+        MetadataRecord::Partition(partition()),
+        &[&[1, 3, 0, 0, 0, 0, 0][..], &id(1), &fields, &[0]].concat(),
+    );
+    let r = PartitionRecord {
+        directories: vec![uuid::Uuid::from_u128(5)],
+        ..partition()
+    };
+    check_record(
+        1,
+        MetadataRecord::Partition(r),
+        &[
+            &[1, 3, 1, 0, 0, 0, 0][..],
+            &id(1),
+            &fields,
+            &[2],
+            &id(5),
+            &[0],
+        ]
+        .concat(),
+    );
+    let r = PartitionRecord {
+        directories: vec![uuid::Uuid::from_u128(5)],
+        leader_recovery_state: 1,
+        eligible_leader_replicas: Some(vec![2]),
+        ..partition()
+    };
+    check_record(
+        2,
+        MetadataRecord::Partition(r),
+        &[
+            &[1, 3, 2, 0, 0, 0, 0][..],
+            &id(1),
+            &fields,
+            &[2],
+            &id(5),
+            &[2, 0, 1, 1],          // leader_recovery_state
+            &[1, 5, 2, 0, 0, 0, 2], // eligible_leader_replicas
+        ]
+        .concat(),
+    );
+
+    // Only changed fields are written
+    let r = PartitionChangeRecord {
+        partition_id: 0,
+        topic_id: TopicId(uuid::Uuid::from_u128(1)),
+        isr: Some(vec![1]),
+        leader: 1,
+        ..Default::default()
+    };
+    check_record(
+        0,
+        MetadataRecord::PartitionChange(r),
+        &[
+            &[1, 5, 0, 0, 0, 0, 0][..],
+            &id(1),
+            &[2, 0, 5, 2, 0, 0, 0, 1], // isr
+            &[1, 4, 0, 0, 0, 1],       // leader
+        ]
+        .concat(),
+    );
+    let r = PartitionChangeRecord {
+        partition_id: 0,
+        topic_id: TopicId(uuid::Uuid::from_u128(1)),
+        leader_recovery_state: 0,
+        directories: Some(vec![uuid::Uuid::from_u128(5)]),
+        eligible_leader_replicas: Some(Vec::new()),
+        ..Default::default()
+    };
+    check_record(
+        2,
+        MetadataRecord::PartitionChange(r),
+        &[
+            &[1, 5, 2, 0, 0, 0, 0][..],
+            &id(1),
+            &[3, 5, 1, 0], // leader_recovery_state
+            &[6, 17, 2],   // directories
+            &id(5),
+            &[7, 1, 1], // eligible_leader_replicas
+        ]
+        .concat(),
+    );
+
+    let r = RemoveTopicRecord {
+        topic_id: TopicId(uuid::Uuid::from_u128(1)),
+    };
+    check_record(
+        0,
+        MetadataRecord::RemoveTopic(r),
+        &[&[1, 10, 0][..], &id(1), &[0]].concat(),
+    );
+}
+
+#[test]
+fn config_records_codec() {
+    let r = ConfigRecord {
+        resource_type: 2,
+        resource_name: "t".to_string(),
+        name: "n".to_string(),
+        value: Some("v".to_string()),
+    };
+    check_record(
+        0,
+        MetadataRecord::Config(r),
+        &[1, 4, 0, 2, 2, b't', 2, b'n', 2, b'v', 0],
+    );
+    let r = ConfigRecord {
+        resource_type: 2,
+        resource_name: "t".to_string(),
+        name: "n".to_string(),
+        value: None,
+    };
+    check_record(
+        0,
+        MetadataRecord::Config(r),
+        &[1, 4, 0, 2, 2, b't', 2, b'n', 0, 0],
+    );
+
+    let r = FeatureLevelRecord {
+        name: "f".to_string(),
+        feature_level: 7,
+    };
+    check_record(
+        0,
+        MetadataRecord::FeatureLevel(r),
+        &[1, 12, 0, 2, b'f', 0, 7, 0],
+    );
+
+    let r = ClientQuotaRecord {
+        entity: vec![ClientQuotaEntity {
+            entity_type: "u".to_string(),
+            entity_name: Some("a".to_string()),
+        }],
+        key: "k".to_string(),
+        value: 1.5,
+        remove: false,
+    };
+    check_record(
+        0,
+        MetadataRecord::ClientQuota(r),
+        &[
+            1, 13, 0, 2, 2, b'u', 2, b'a', 0, // frame, entity
+            2, b'k', 0x3f, 0xf8, 0, 0, 0, 0, 0, 0, 0, // key, value, remove
+            0,
+        ],
+    );
+
+    let r = ProducerIdsRecord {
+        broker_id: 1,
+        broker_epoch: 2,
+        next_producer_id: 1000,
+    };
+    check_record(
+        0,
+        MetadataRecord::ProducerIds(r),
+        &[
+            1, 14, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, // frame, broker_id, epoch
+            0, 0, 0, 0, 0, 0, 0x03, 0xe8, 0, // next_producer_id
+        ],
+    );
+
+    check_record(0, MetadataRecord::NoOp(NoOpRecord), &[1, 20, 0, 0]);
+}
+
+#[test]
+fn acl_records_codec() {
+    let r = AccessControlEntryRecord {
+        id: uuid::Uuid::from_u128(1),
+        resource_type: 2,
+        resource_name: "t".to_string(),
+        pattern_type: 3,
+        principal: "p".to_string(),
+        host: "*".to_string(),
+        operation: 4,
+        permission_type: 3,
+    };
+    check_record(
+        0,
+        MetadataRecord::AccessControlEntry(r),
+        &[
+            &[1, 6, 0][..],
+            &id(1),
+            &[2, 2, b't', 3, 2, b'p', 2, b'*', 4, 3, 0],
+        ]
+        .concat(),
+    );
+
+    let r = RemoveAccessControlEntryRecord {
+        id: uuid::Uuid::from_u128(1),
+    };
+    check_record(
+        0,
+        MetadataRecord::RemoveAccessControlEntry(r),
+        &[&[1, 7, 0][..], &id(1), &[0]].concat(),
+    );
+}
@@ -0,0 +1,453 @@
+use crate::de::{KafkaFlexibleDecoder, KafkaProtoDecodable};
+use crate::ser::{KafkaFlexibleEncoder, KafkaProtoEncodable};
+
+use crate::{BrokerId, KafkaMessage, TopicId, TopicName};
+
+#[derive(Default)]
+pub struct TopicRecord {
+    pub name: TopicName,
+    pub topic_id: TopicId,
+}
+
+builder! {
+    TopicRecord => TopicRecordBuilder {
+        name: TopicName,
+        topic_id: TopicId,
+    }
+}
+
+impl KafkaProtoEncodable for TopicRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(&self.name)?;
+        s.emit_uuid(self.topic_id.0)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for TopicRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = TopicRecord {
+            name: d.read_field("name", |d| d.read_string())?.to_string(),
+            topic_id: TopicId(d.read_field("topic_id", |d| d.read_uuid())?),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for TopicRecord {
+    const API_KEY: i16 = 2;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+pub struct PartitionRecord {
+    // default=-1
+    pub partition_id: i32,
+    pub topic_id: TopicId,
+    pub replicas: Vec<BrokerId>,
+    pub isr: Vec<BrokerId>,
+    pub removing_replicas: Vec<BrokerId>,
+    pub adding_replicas: Vec<BrokerId>,
+    // default=-1
+    // The lead replica, or -1 if there is no leader.
+    pub leader: BrokerId,
+
+    // versions: 0+, tag: 0, default=0
+    // 0 if the leader was elected from the ISR, 1 if it is recovering from an
+    // unclean election.
+    pub leader_recovery_state: i8,
+
+    // default=-1
+    pub leader_epoch: i32,
+    // default=-1
+    pub partition_epoch: i32,
+
+    // versions: 1+
+    // Log directory of every replica, in the order of replicas.
+    pub directories: Vec<uuid::Uuid>,
+
+    // versions: 2+, tag: 1, default=null
+    // Eligible leader replicas of this partition (KIP-966).
+    pub eligible_leader_replicas: Option<Vec<BrokerId>>,
+    // versions: 2+, tag: 2, default=null
+    // Last known eligible leader replicas of this partition.
+    pub last_known_elr: Option<Vec<BrokerId>>,
+}
+
+impl Default for PartitionRecord {
+    fn default() -> Self {
+        PartitionRecord {
+            partition_id: -1,
+            topic_id: TopicId::ZERO,
+            replicas: Vec::new(),
+            isr: Vec::new(),
+            removing_replicas: Vec::new(),
+            adding_replicas: Vec::new(),
+            leader: -1,
+            leader_recovery_state: 0,
+            leader_epoch: -1,
+            partition_epoch: -1,
+            directories: Vec::new(),
+            eligible_leader_replicas: None,
+            last_known_elr: None,
+        }
+    }
+}
+
+builder! {
+    PartitionRecord => PartitionRecordBuilder {
+        partition_id: i32,
+        topic_id: TopicId,
+        replicas: Vec<BrokerId>,
+        isr: Vec<BrokerId>,
+        removing_replicas: Vec<BrokerId>,
+        adding_replicas: Vec<BrokerId>,
+        leader: BrokerId,
+        leader_recovery_state: i8,
+        leader_epoch: i32,
+        partition_epoch: i32,
+        directories: Vec<uuid::Uuid>,
+        eligible_leader_replicas: Option<Vec<BrokerId>>,
+        last_known_elr: Option<Vec<BrokerId>>,
+    }
+}
+
+impl KafkaProtoEncodable for PartitionRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_id)?;
+        s.emit_uuid(self.topic_id.0)?;
+        s.emit_array(ver, self.replicas.iter())?;
+        s.emit_array(ver, self.isr.iter())?;
+        s.emit_array(ver, self.removing_replicas.iter())?;
+        s.emit_array(ver, self.adding_replicas.iter())?;
+        s.emit_int32(self.leader)?;
+        s.emit_int32(self.leader_epoch)?;
+        s.emit_int32(self.partition_epoch)?;
+        if ver >= 1 {
+            s.emit_array(ver, self.directories.iter())?;
+        } else {
+            s.check_absent_field("directories", ver, self.directories.is_empty())?;
+        }
+        if ver < 2 {
+            s.check_absent_field(
+                "eligible_leader_replicas",
+                ver,
+                self.eligible_leader_replicas.is_none(),
+            )?;
+            s.check_absent_field("last_known_elr", ver, self.last_known_elr.is_none())?;
+        }
+
+        let emit_leader_recovery_state = self.leader_recovery_state != 0;
+        let mut r = s.emit_tagged_fields_hdr(
+            emit_leader_recovery_state as usize
+                + self.eligible_leader_replicas.is_some() as usize
+                + self.last_known_elr.is_some() as usize,
+        )?;
+        if emit_leader_recovery_state {
+            r = s.emit_tagged_field(0, ver, &self.leader_recovery_state)?;
+        }
+        if let Some(elr) = &self.eligible_leader_replicas {
+            r = s.emit_tagged_field(1, ver, elr)?;
+        }
+        if let Some(elr) = &self.last_known_elr {
+            r = s.emit_tagged_field(2, ver, elr)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for PartitionRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = PartitionRecord {
+            partition_id: d.read_field("partition_id", |d| d.read_int32())?,
+            topic_id: TopicId(d.read_field("topic_id", |d| d.read_uuid())?),
+            replicas: d.read_field("replicas", |d| d.read_array(ver))?,
+            isr: d.read_field("isr", |d| d.read_array(ver))?,
+            removing_replicas: d.read_field("removing_replicas", |d| d.read_array(ver))?,
+            adding_replicas: d.read_field("adding_replicas", |d| d.read_array(ver))?,
+            leader: d.read_field("leader", |d| d.read_int32())?,
+            leader_recovery_state: 0,
+            leader_epoch: d.read_field("leader_epoch", |d| d.read_int32())?,
+            partition_epoch: d.read_field("partition_epoch", |d| d.read_int32())?,
+            directories: if ver >= 1 {
+                d.read_field("directories", |d| d.read_array(ver))?
+            } else {
+                Vec::new()
+            },
+            eligible_leader_replicas: None,
+            last_known_elr: None,
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.leader_recovery_state =
+                    d.read_field("leader_recovery_state", |d| d.read_int8())?;
+                Ok(true)
+            }
+            1 if ver >= 2 => {
+                r.eligible_leader_replicas =
+                    Some(d.read_field("eligible_leader_replicas", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            2 if ver >= 2 => {
+                r.last_known_elr = Some(d.read_field("last_known_elr", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for PartitionRecord {
+    const API_KEY: i16 = 3;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 2;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+// Leader value of PartitionChangeRecord which keeps current leader
+pub const NO_LEADER_CHANGE: BrokerId = -2;
+
+// Every field but partition_id and topic_id is tagged and is only present when
+// it changes, None stands for no change.
+pub struct PartitionChangeRecord {
+    // default=-1
+    pub partition_id: i32,
+    pub topic_id: TopicId,
+
+    // versions: 0+, tag: 0
+    pub isr: Option<Vec<BrokerId>>,
+
+    // versions: 0+, tag: 1, default=-2
+    // The lead replica, -1 if there is no leader, or NO_LEADER_CHANGE.
+    pub leader: BrokerId,
+
+    // versions: 0+, tag: 2
+    pub replicas: Option<Vec<BrokerId>>,
+    // versions: 0+, tag: 3
+    pub removing_replicas: Option<Vec<BrokerId>>,
+    // versions: 0+, tag: 4
+    pub adding_replicas: Option<Vec<BrokerId>>,
+
+    // versions: 0+, tag: 5, default=-1
+    // -1 if it didn't change; 0 if the leader was elected from the ISR, 1 if it
+    // is recovering from an unclean election.
+    pub leader_recovery_state: i8,
+
+    // versions: 1+, tag: 6
+    pub directories: Option<Vec<uuid::Uuid>>,
+    // versions: 2+, tag: 7
+    pub eligible_leader_replicas: Option<Vec<BrokerId>>,
+    // versions: 2+, tag: 8
+    pub last_known_elr: Option<Vec<BrokerId>>,
+}
+
+impl Default for PartitionChangeRecord {
+    fn default() -> Self {
+        PartitionChangeRecord {
+            partition_id: -1,
+            topic_id: TopicId::ZERO,
+            isr: None,
+            leader: NO_LEADER_CHANGE,
+            replicas: None,
+            removing_replicas: None,
+            adding_replicas: None,
+            leader_recovery_state: -1,
+            directories: None,
+            eligible_leader_replicas: None,
+            last_known_elr: None,
+        }
+    }
+}
+
+builder! {
+    PartitionChangeRecord => PartitionChangeRecordBuilder {
+        partition_id: i32,
+        topic_id: TopicId,
+        isr: Option<Vec<BrokerId>>,
+        leader: BrokerId,
+        replicas: Option<Vec<BrokerId>>,
+        removing_replicas: Option<Vec<BrokerId>>,
+        adding_replicas: Option<Vec<BrokerId>>,
+        leader_recovery_state: i8,
+        directories: Option<Vec<uuid::Uuid>>,
+        eligible_leader_replicas: Option<Vec<BrokerId>>,
+        last_known_elr: Option<Vec<BrokerId>>,
+    }
+}
+
+impl KafkaProtoEncodable for PartitionChangeRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.partition_id)?;
+        s.emit_uuid(self.topic_id.0)?;
+
+        if ver < 1 {
+            s.check_absent_field("directories", ver, self.directories.is_none())?;
+        }
+        if ver < 2 {
+            s.check_absent_field(
+                "eligible_leader_replicas",
+                ver,
+                self.eligible_leader_replicas.is_none(),
+            )?;
+            s.check_absent_field("last_known_elr", ver, self.last_known_elr.is_none())?;
+        }
+
+        let emit_leader = self.leader != NO_LEADER_CHANGE;
+        let emit_leader_recovery_state = self.leader_recovery_state != -1;
+        let mut r = s.emit_tagged_fields_hdr(
+            self.isr.is_some() as usize
+                + emit_leader as usize
+                + self.replicas.is_some() as usize
+                + self.removing_replicas.is_some() as usize
+                + self.adding_replicas.is_some() as usize
+                + emit_leader_recovery_state as usize
+                + self.directories.is_some() as usize
+                + self.eligible_leader_replicas.is_some() as usize
+                + self.last_known_elr.is_some() as usize,
+        )?;
+        if let Some(isr) = &self.isr {
+            r = s.emit_tagged_field(0, ver, isr)?;
+        }
+        if emit_leader {
+            r = s.emit_tagged_field(1, ver, &self.leader)?;
+        }
+        if let Some(replicas) = &self.replicas {
+            r = s.emit_tagged_field(2, ver, replicas)?;
+        }
+        if let Some(replicas) = &self.removing_replicas {
+            r = s.emit_tagged_field(3, ver, replicas)?;
+        }
+        if let Some(replicas) = &self.adding_replicas {
+            r = s.emit_tagged_field(4, ver, replicas)?;
+        }
+        if emit_leader_recovery_state {
+            r = s.emit_tagged_field(5, ver, &self.leader_recovery_state)?;
+        }
+        if let Some(directories) = &self.directories {
+            r = s.emit_tagged_field(6, ver, directories)?;
+        }
+        if let Some(elr) = &self.eligible_leader_replicas {
+            r = s.emit_tagged_field(7, ver, elr)?;
+        }
+        if let Some(elr) = &self.last_known_elr {
+            r = s.emit_tagged_field(8, ver, elr)?;
+        }
+        Ok(r)
+    }
+}
+
+impl KafkaProtoDecodable for PartitionChangeRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let mut r = PartitionChangeRecord {
+            partition_id: d.read_field("partition_id", |d| d.read_int32())?,
+            topic_id: TopicId(d.read_field("topic_id", |d| d.read_uuid())?),
+            isr: None,
+            leader: NO_LEADER_CHANGE,
+            replicas: None,
+            removing_replicas: None,
+            adding_replicas: None,
+            leader_recovery_state: -1,
+            directories: None,
+            eligible_leader_replicas: None,
+            last_known_elr: None,
+        };
+        d.read_tagged_fields(|d, tag, _| match tag {
+            0 => {
+                r.isr = Some(d.read_field("isr", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            1 => {
+                r.leader = d.read_field("leader", |d| d.read_int32())?;
+                Ok(true)
+            }
+            2 => {
+                r.replicas = Some(d.read_field("replicas", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            3 => {
+                r.removing_replicas =
+                    Some(d.read_field("removing_replicas", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            4 => {
+                r.adding_replicas = Some(d.read_field("adding_replicas", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            5 => {
+                r.leader_recovery_state =
+                    d.read_field("leader_recovery_state", |d| d.read_int8())?;
+                Ok(true)
+            }
+            6 if ver >= 1 => {
+                r.directories = Some(d.read_field("directories", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            7 if ver >= 2 => {
+                r.eligible_leader_replicas =
+                    Some(d.read_field("eligible_leader_replicas", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            8 if ver >= 2 => {
+                r.last_known_elr = Some(d.read_field("last_known_elr", |d| d.read_array(ver))?);
+                Ok(true)
+            }
+            _ => Ok(false),
+        })?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for PartitionChangeRecord {
+    const API_KEY: i16 = 5;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 2;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
+
+#[derive(Default)]
+pub struct RemoveTopicRecord {
+    pub topic_id: TopicId,
+}
+
+builder! {
+    RemoveTopicRecord => RemoveTopicRecordBuilder {
+        topic_id: TopicId,
+    }
+}
+
+impl KafkaProtoEncodable for RemoveTopicRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(self.topic_id.0)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for RemoveTopicRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = RemoveTopicRecord {
+            topic_id: TopicId(d.read_field("topic_id", |d| d.read_uuid())?),
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+impl KafkaMessage for RemoveTopicRecord {
+    const API_KEY: i16 = 10;
+
+    const MIN_API_VERSION: i16 = 0;
+    const MAX_API_VERSION: i16 = 0;
+    const FLEXIBLE_VERSION: i16 = 0;
+}
@@ -0,0 +1,57 @@
+use bytes::Bytes;
+
+use super::{is_partial_batch, resume_at, ReadCommitted, RecordBatch};
+use crate::error::Result;
+use crate::messages::FetchResponsePartition;
+
+impl RecordBatch<Bytes> {
+    // Same as RecordBatch::read, payloads of records are slices sharing buf
+    // instead of copies
+    pub fn read_shared(buf: &Bytes) -> Result<(RecordBatch<Bytes>, usize)> {
+        RecordBatch::read_with(buf, |b| buf.slice_ref(b))
+    }
+}
+
+// RecordBatches over a refcounted buffer, e.g. records of a fetched partition
+// kept around after the response is gone
+pub struct SharedRecordBatches {
+    buf: Bytes,
+}
+
+impl SharedRecordBatches {
+    pub fn new(buf: Bytes) -> SharedRecordBatches {
+        SharedRecordBatches { buf }
+    }
+}
+
+impl Iterator for SharedRecordBatches {
+    type Item = Result<RecordBatch<Bytes>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if is_partial_batch(&self.buf) {
+            return None;
+        }
+
+        match RecordBatch::read_shared(&self.buf) {
+            Ok((batch, size)) => {
+                let _ = self.buf.split_to(size);
+                Some(Ok(batch))
+            }
+            Err(e) => {
+                let _ = self.buf.split_to(resume_at(&self.buf, &e));
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl FetchResponsePartition {
+    // Same as committed_batches, payloads of records share the records of the
+    // partition
+    pub fn committed_shared_batches(&self) -> ReadCommitted<SharedRecordBatches> {
+        ReadCommitted::new(
+            SharedRecordBatches::new(self.records.clone().unwrap_or_default()),
+            self.aborted_transactions.as_deref().unwrap_or_default(),
+        )
+    }
+}
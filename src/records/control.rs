@@ -0,0 +1,347 @@
+use super::Record;
+use crate::de::{deserialize_versioned, KafkaDeserializer, KafkaFlexibleDecoder};
+use crate::de::{KafkaProtoDecodable, KafkaProtoDecoder};
+use crate::error::Error;
+use crate::ser::{serialize_versioned, KafkaFlexibleEncoder, KafkaProtoEncodable};
+use crate::ser::{KafkaProtoEncoder, KafkaSerializer};
+use crate::{BrokerId, Bytes};
+
+// Key of every record in a batch with the control flag set:
+//
+//   version => INT16, always 0
+//   type => INT16
+//
+// Ref: https://github.com/apache/kafka/blob/3.7.0/clients/src/main/java/org/apache/kafka/common/record/ControlRecordType.java
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i16)]
+pub enum ControlRecordType {
+    Abort = 0,
+    Commit = 1,
+    LeaderChange = 2,
+    SnapshotHeader = 3,
+    SnapshotFooter = 4,
+    KRaftVersion = 5,
+    KRaftVoters = 6,
+}
+
+impl ControlRecordType {
+    pub const CURRENT_VERSION: i16 = 0;
+
+    pub fn from_i16(v: i16) -> Option<Self> {
+        match v {
+            0 => Some(ControlRecordType::Abort),
+            1 => Some(ControlRecordType::Commit),
+            2 => Some(ControlRecordType::LeaderChange),
+            3 => Some(ControlRecordType::SnapshotHeader),
+            4 => Some(ControlRecordType::SnapshotFooter),
+            5 => Some(ControlRecordType::KRaftVersion),
+            6 => Some(ControlRecordType::KRaftVoters),
+            _ => None,
+        }
+    }
+
+    pub fn to_key_bytes(self) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::with_capacity(4);
+        let mut s = KafkaSerializer::new(&mut buf);
+        s.emit_int16(Self::CURRENT_VERSION)?;
+        s.emit_int16(self as i16)?;
+        Ok(buf)
+    }
+
+    // Returns raw type, so that control records of unknown types can be skipped
+    pub fn parse_key(buf: &[u8]) -> crate::error::Result<i16> {
+        let mut d = KafkaDeserializer::new(buf);
+        let ver = d.read_int16()?;
+        if ver < 0 {
+            return Err(Error::UnsupportedVersion(ver));
+        }
+        d.read_int16()
+    }
+}
+
+// First record of a KRaft snapshot. Values of control records start with their
+// version, just like versioned payloads.
+#[derive(Default)]
+pub struct SnapshotHeaderRecord {
+    // The append time of the last record from the log contained in this
+    // snapshot.
+    pub last_contained_log_timestamp: i64,
+}
+
+impl SnapshotHeaderRecord {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 0;
+    pub const FLEXIBLE_VERSION: i16 = 0;
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for SnapshotHeaderRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int64(self.last_contained_log_timestamp)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for SnapshotHeaderRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = SnapshotHeaderRecord {
+            last_contained_log_timestamp: d
+                .read_field("last_contained_log_timestamp", |d| d.read_int64())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// Last record of a complete KRaft snapshot
+pub struct SnapshotFooterRecord;
+
+impl SnapshotFooterRecord {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 0;
+    pub const FLEXIBLE_VERSION: i16 = 0;
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for SnapshotFooterRecord {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for SnapshotFooterRecord {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        d.skip_tagged_fields()?;
+        Ok(SnapshotFooterRecord)
+    }
+}
+
+// Value of COMMIT and ABORT control records
+#[derive(Default)]
+pub struct EndTransactionMarker {
+    pub coordinator_epoch: i32,
+}
+
+impl EndTransactionMarker {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 0;
+    pub const FLEXIBLE_VERSION: i16 = i16::MAX; // flexibleVersions: none
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for EndTransactionMarker {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.coordinator_epoch)
+    }
+}
+
+impl KafkaProtoDecodable for EndTransactionMarker {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        Ok(EndTransactionMarker {
+            coordinator_epoch: d.read_field("coordinator_epoch", |d| d.read_int32())?,
+        })
+    }
+}
+
+// Written by a newly elected KRaft leader
+#[derive(Default)]
+pub struct LeaderChangeMessage {
+    // The ID of the newly elected leader
+    pub leader_id: BrokerId,
+    // The set of voters in the quorum for this epoch
+    pub voters: Vec<Voter>,
+    // The voters who voted for the leader at the time of election
+    pub granting_voters: Vec<Voter>,
+}
+
+impl LeaderChangeMessage {
+    pub const MIN_VERSION: i16 = 0;
+    pub const MAX_VERSION: i16 = 0;
+    pub const FLEXIBLE_VERSION: i16 = 0;
+
+    pub fn to_bytes(&self, ver: i16) -> crate::error::Result<Bytes> {
+        let mut buf = Vec::new();
+        serialize_versioned(self, ver, Self::FLEXIBLE_VERSION, &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn from_bytes(buf: &[u8]) -> crate::error::Result<Self> {
+        let (_, r) = deserialize_versioned(
+            Self::MIN_VERSION,
+            Self::MAX_VERSION,
+            Self::FLEXIBLE_VERSION,
+            buf,
+        )?;
+        Ok(r)
+    }
+}
+
+impl KafkaProtoEncodable for LeaderChangeMessage {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.leader_id)?;
+        s.emit_array(ver, self.voters.iter())?;
+        s.emit_array(ver, self.granting_voters.iter())?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for LeaderChangeMessage {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(ver: i16, d: &mut D) -> Result<Self, D::Error> {
+        let r = LeaderChangeMessage {
+            leader_id: d.read_field("leader_id", |d| d.read_int32())?,
+            voters: d.read_field("voters", |d| d.read_array(ver))?,
+            granting_voters: d.read_field("granting_voters", |d| d.read_array(ver))?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+#[derive(Default)]
+pub struct Voter {
+    pub voter_id: BrokerId,
+}
+
+impl KafkaProtoEncodable for Voter {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(self.voter_id)?;
+        s.emit_empty_tagged_fields()
+    }
+}
+
+impl KafkaProtoDecodable for Voter {
+    fn deserialize<'a, D: KafkaFlexibleDecoder<'a>>(
+        _ver: i16,
+        d: &mut D,
+    ) -> Result<Self, D::Error> {
+        let r = Voter {
+            voter_id: d.read_field("voter_id", |d| d.read_int32())?,
+        };
+        d.skip_tagged_fields()?;
+        Ok(r)
+    }
+}
+
+// Typed key and value of a control record
+pub enum ControlRecord {
+    Abort(EndTransactionMarker),
+    Commit(EndTransactionMarker),
+    LeaderChange(LeaderChangeMessage),
+    SnapshotHeader(SnapshotHeaderRecord),
+    SnapshotFooter(SnapshotFooterRecord),
+    // Control records this crate doesn't decode, with their raw type
+    Unknown(i16),
+}
+
+impl ControlRecord {
+    pub fn record_type(&self) -> i16 {
+        match self {
+            ControlRecord::Abort(_) => ControlRecordType::Abort as i16,
+            ControlRecord::Commit(_) => ControlRecordType::Commit as i16,
+            ControlRecord::LeaderChange(_) => ControlRecordType::LeaderChange as i16,
+            ControlRecord::SnapshotHeader(_) => ControlRecordType::SnapshotHeader as i16,
+            ControlRecord::SnapshotFooter(_) => ControlRecordType::SnapshotFooter as i16,
+            ControlRecord::Unknown(t) => *t,
+        }
+    }
+
+    pub fn from_record<B: AsRef<[u8]>>(r: &Record<B>) -> crate::error::Result<Self> {
+        let key = r.key.as_ref().ok_or(Error::InvalidRecord)?.as_ref();
+        let t = ControlRecordType::parse_key(key)?;
+        let value = r.value.as_ref().map_or(&[][..], AsRef::as_ref);
+        let c = match ControlRecordType::from_i16(t) {
+            Some(ControlRecordType::Abort) => {
+                ControlRecord::Abort(EndTransactionMarker::from_bytes(value)?)
+            }
+            Some(ControlRecordType::Commit) => {
+                ControlRecord::Commit(EndTransactionMarker::from_bytes(value)?)
+            }
+            Some(ControlRecordType::LeaderChange) => {
+                ControlRecord::LeaderChange(LeaderChangeMessage::from_bytes(value)?)
+            }
+            Some(ControlRecordType::SnapshotHeader) => {
+                ControlRecord::SnapshotHeader(SnapshotHeaderRecord::from_bytes(value)?)
+            }
+            Some(ControlRecordType::SnapshotFooter) => {
+                ControlRecord::SnapshotFooter(SnapshotFooterRecord::from_bytes(value)?)
+            }
+            _ => ControlRecord::Unknown(t),
+        };
+        Ok(c)
+    }
+
+    // Record which goes into a batch with the control flag set. Values are
+    // written with version 0.
+    pub fn to_record(&self, offset_delta: i32) -> crate::error::Result<Record> {
+        let (t, value) = match self {
+            ControlRecord::Abort(m) => (ControlRecordType::Abort, m.to_bytes(0)?),
+            ControlRecord::Commit(m) => (ControlRecordType::Commit, m.to_bytes(0)?),
+            ControlRecord::LeaderChange(m) => (ControlRecordType::LeaderChange, m.to_bytes(0)?),
+            ControlRecord::SnapshotHeader(m) => (ControlRecordType::SnapshotHeader, m.to_bytes(0)?),
+            ControlRecord::SnapshotFooter(m) => (ControlRecordType::SnapshotFooter, m.to_bytes(0)?),
+            ControlRecord::Unknown(t) => return Err(Error::UnknownControlRecordType(*t)),
+        };
+        Ok(Record {
+            attributes: 0,
+            timestamp_delta: 0,
+            offset_delta,
+            key: Some(t.to_key_bytes()?),
+            value: Some(value),
+            headers: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,393 @@
+// Record batches as they are stored in the log and carried by Produce and
+// Fetch. Only magic v2 is supported:
+//
+//   baseOffset => INT64
+//   batchLength => INT32, size of the batch following this field
+//   partitionLeaderEpoch => INT32
+//   magic => INT8, always 2
+//   crc => UINT32, CRC-32C of everything from attributes to the end of the batch
+//   attributes => INT16
+//   lastOffsetDelta => INT32
+//   baseTimestamp => INT64
+//   maxTimestamp => INT64
+//   producerId => INT64
+//   producerEpoch => INT16
+//   baseSequence => INT32
+//   records => INT32 count followed by records
+//
+// Ref: https://kafka.apache.org/documentation/#recordbatch
+use std::convert::TryInto;
+use std::io::Write;
+
+use crate::de::{KafkaDeserializer, KafkaProtoDecoder};
+use crate::error::{Error, Result};
+use crate::ser::{KafkaProtoEncoder, KafkaSerializer};
+use crate::{Bytes, ProducerId};
+
+#[cfg(feature = "bytes")]
+mod bytes;
+mod control;
+pub(crate) mod crc32c;
+mod read_committed;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "bytes")]
+pub use self::bytes::*;
+pub use control::*;
+pub use read_committed::*;
+
+pub const MAGIC: i8 = 2;
+
+pub const NO_PRODUCER_ID: ProducerId = -1;
+pub const NO_PRODUCER_EPOCH: i16 = -1;
+pub const NO_SEQUENCE: i32 = -1;
+pub const NO_PARTITION_LEADER_EPOCH: i32 = -1;
+
+// Size of the batch header up to and including the records count
+pub const RECORD_BATCH_OVERHEAD: usize = 61;
+
+// Offset of the attributes field, which is where CRC coverage starts
+const ATTRIBUTES_OFFSET: usize = 21;
+
+// Size of baseOffset and batchLength, which are not counted in batchLength
+const LOG_OVERHEAD: usize = 12;
+
+// Payloads (keys, values and header values of records) are Vec<u8> by default,
+// with the bytes feature they can also be refcounted slices of the buffer
+// batches are read from, see RecordBatch::read_shared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordBatch<B = Bytes> {
+    pub base_offset: i64,
+    pub partition_leader_epoch: i32,
+    pub attributes: i16,
+    pub last_offset_delta: i32,
+    pub base_timestamp: i64,
+    pub max_timestamp: i64,
+    pub producer_id: ProducerId,
+    pub producer_epoch: i16,
+    pub base_sequence: i32,
+    pub records: Vec<Record<B>>,
+}
+
+impl RecordBatch {
+    pub const COMPRESSION_CODEC_MASK: i16 = 0x07;
+    pub const TIMESTAMP_TYPE_MASK: i16 = 0x08;
+    pub const TRANSACTIONAL_FLAG_MASK: i16 = 0x10;
+    pub const CONTROL_FLAG_MASK: i16 = 0x20;
+
+    // Decodes the batch at the start of buf. Returns it alongside the number of
+    // bytes it occupies.
+    pub fn read(buf: &[u8]) -> Result<(RecordBatch, usize)> {
+        RecordBatch::read_with(buf, <[u8]>::to_vec)
+    }
+
+    // Same as RecordBatch::read, but records are left undecoded. Returns the
+    // batch without records, its records field (count followed by records,
+    // which are compressed as a whole if the batch is) and the size of the
+    // batch. Compressed batches can be passed on or inspected this way.
+    pub fn read_header(buf: &[u8]) -> Result<(RecordBatch, &[u8], usize)> {
+        RecordBatch::parse_header(buf)
+    }
+}
+
+impl<B> RecordBatch<B> {
+    // 0: none, 1: gzip, 2: snappy, 3: lz4, 4: zstd
+    pub fn compression(&self) -> i16 {
+        self.attributes & RecordBatch::COMPRESSION_CODEC_MASK
+    }
+
+    // Timestamps are LogAppendTime rather than CreateTime
+    pub fn is_log_append_time(&self) -> bool {
+        self.attributes & RecordBatch::TIMESTAMP_TYPE_MASK != 0
+    }
+
+    pub fn is_transactional(&self) -> bool {
+        self.attributes & RecordBatch::TRANSACTIONAL_FLAG_MASK != 0
+    }
+
+    // Batch holds control records, e.g. transaction markers
+    pub fn is_control(&self) -> bool {
+        self.attributes & RecordBatch::CONTROL_FLAG_MASK != 0
+    }
+
+    pub fn last_offset(&self) -> i64 {
+        self.base_offset + self.last_offset_delta as i64
+    }
+
+    // Control batch holds a single control record, None for data batches
+    pub fn control_record(&self) -> Result<Option<ControlRecord>>
+    where
+        B: AsRef<[u8]>,
+    {
+        if !self.is_control() {
+            return Ok(None);
+        }
+        let r = self.records.first().ok_or(Error::InvalidRecord)?;
+        ControlRecord::from_record(r).map(Some)
+    }
+
+    // Validates the batch at the start of buf and decodes its header. Returns
+    // the batch without records, its records field as is and its size.
+    fn parse_header(buf: &[u8]) -> Result<(RecordBatch<B>, &[u8], usize)> {
+        let mut d = KafkaDeserializer::new(buf);
+        let base_offset = d.read_int64()?;
+        let size = LOG_OVERHEAD + TryInto::<usize>::try_into(d.read_int32()?)?;
+        if size < RECORD_BATCH_OVERHEAD || buf.len() < size {
+            return Err(Error::OutOfBounds);
+        }
+        let partition_leader_epoch = d.read_int32()?;
+        let magic = d.read_int8()?;
+        if magic != MAGIC {
+            return Err(Error::UnsupportedMagic(magic));
+        }
+        if d.read_uint32()? != crc32c::crc32c(&buf[ATTRIBUTES_OFFSET..size]) {
+            return Err(Error::CorruptRecordBatch);
+        }
+
+        let mut d = KafkaDeserializer::new(&buf[ATTRIBUTES_OFFSET..size]);
+        let batch = RecordBatch {
+            base_offset,
+            partition_leader_epoch,
+            attributes: d.read_int16()?,
+            last_offset_delta: d.read_int32()?,
+            base_timestamp: d.read_int64()?,
+            max_timestamp: d.read_int64()?,
+            producer_id: d.read_int64()?,
+            producer_epoch: d.read_int16()?,
+            base_sequence: d.read_int32()?,
+            records: Vec::new(),
+        };
+        Ok((batch, &buf[RECORD_BATCH_OVERHEAD - 4..size], size))
+    }
+
+    // `payload` turns slices of buf into payloads of records
+    fn read_with<F>(buf: &[u8], payload: F) -> Result<(RecordBatch<B>, usize)>
+    where
+        F: Fn(&[u8]) -> B,
+    {
+        let (mut batch, records, size) = RecordBatch::parse_header(buf)?;
+        if batch.compression() != 0 {
+            return Err(Error::UnsupportedCompression(batch.compression()));
+        }
+        // Every record takes at least a byte, so bogus count can't make it
+        // reserve more than the batch holds
+        let mut d = KafkaDeserializer::new(records);
+        let count: usize = d.read_int32()?.try_into()?;
+        batch.records.reserve(count.min(d.remaining()));
+        for _ in 0..count {
+            batch.records.push(Record::read(&mut d, &payload)?);
+        }
+        Ok((batch, size))
+    }
+}
+
+impl<B: AsRef<[u8]>> RecordBatch<B> {
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<()> {
+        if self.compression() != 0 {
+            return Err(Error::UnsupportedCompression(self.compression()));
+        }
+
+        let mut body = Vec::new();
+        let mut s = KafkaSerializer::new(&mut body);
+        s.emit_int16(self.attributes)?;
+        s.emit_int32(self.last_offset_delta)?;
+        s.emit_int64(self.base_timestamp)?;
+        s.emit_int64(self.max_timestamp)?;
+        s.emit_int64(self.producer_id)?;
+        s.emit_int16(self.producer_epoch)?;
+        s.emit_int32(self.base_sequence)?;
+        s.emit_int32(self.records.len().try_into()?)?;
+        for r in &self.records {
+            r.write(&mut s)?;
+        }
+
+        // partitionLeaderEpoch, magic and crc precede the body
+        let batch_length = 4 + 1 + 4 + body.len();
+        let mut s = KafkaSerializer::new(w);
+        s.emit_int64(self.base_offset)?;
+        s.emit_int32(batch_length.try_into()?)?;
+        s.emit_int32(self.partition_leader_epoch)?;
+        s.emit_int8(MAGIC)?;
+        s.emit_uint32(crc32c::crc32c(&body))?;
+        s.emit_raw_bytes(&body)
+    }
+
+    pub fn to_bytes(&self) -> Result<Bytes> {
+        let mut buf = Vec::new();
+        self.write(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+// Iterates over batches laid out back to back, e.g. a log segment or records of
+// a fetched partition. Trailing partial batch, which brokers may return in Fetch
+// responses, is ignored. Iteration stops after the first error, except for
+// UnsupportedCompression: the batch is intact, so it goes on past it. Callers
+// which need such batches read them with RecordBatch::read_header.
+pub struct RecordBatches<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> RecordBatches<'a> {
+    pub fn new(buf: &'a [u8]) -> RecordBatches<'a> {
+        RecordBatches { buf }
+    }
+}
+
+impl Iterator for RecordBatches<'_> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if is_partial_batch(self.buf) {
+            return None;
+        }
+
+        match RecordBatch::read(self.buf) {
+            Ok((batch, size)) => {
+                self.buf = &self.buf[size..];
+                Some(Ok(batch))
+            }
+            Err(e) => {
+                self.buf = &self.buf[resume_at(self.buf, &e)..];
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// Whether buf ends before the batch at its start does. Batches with a bogus
+// length are not partial, reading them reports an error.
+fn is_partial_batch(buf: &[u8]) -> bool {
+    if buf.len() < LOG_OVERHEAD {
+        return true;
+    }
+    let batch_length = i32::from_be_bytes(buf[8..LOG_OVERHEAD].try_into().unwrap());
+    match TryInto::<usize>::try_into(batch_length) {
+        Ok(len) => buf.len() < LOG_OVERHEAD + len,
+        Err(_) => false,
+    }
+}
+
+// Position in buf to go on from after reading the batch at its start failed
+pub(crate) fn resume_at(buf: &[u8], e: &Error) -> usize {
+    match (e, peek_batch(buf)) {
+        (Error::UnsupportedCompression(_), Some((_, _, size))) => size,
+        _ => buf.len(),
+    }
+}
+
+// Reads header fields of the batch at the start of buf without decoding or
+// validating it. Returns (last offset, max timestamp, size) if the whole batch
+// is in buf.
+pub(crate) fn peek_batch(buf: &[u8]) -> Option<(i64, i64, usize)> {
+    if buf.len() < RECORD_BATCH_OVERHEAD {
+        return None;
+    }
+    let field = |at: usize, len: usize| &buf[at..at + len];
+    let base_offset = i64::from_be_bytes(field(0, 8).try_into().unwrap());
+    let batch_length = i32::from_be_bytes(field(8, 4).try_into().unwrap());
+    let last_offset_delta = i32::from_be_bytes(field(23, 4).try_into().unwrap());
+    let max_timestamp = i64::from_be_bytes(field(35, 8).try_into().unwrap());
+
+    let size = LOG_OVERHEAD + TryInto::<usize>::try_into(batch_length).ok()?;
+    if buf.len() < size {
+        return None;
+    }
+    Some((base_offset + last_offset_delta as i64, max_timestamp, size))
+}
+
+// Record within a batch. Key, value and header values are prefixed with varint
+// length, where -1 stands for null:
+//
+//   length => VARINT
+//   attributes => INT8, unused
+//   timestampDelta => VARLONG
+//   offsetDelta => VARINT
+//   key => VARINT length followed by bytes
+//   value => VARINT length followed by bytes
+//   headers => VARINT count followed by headers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record<B = Bytes> {
+    pub attributes: i8,
+    pub timestamp_delta: i64,
+    pub offset_delta: i32,
+    pub key: Option<B>,
+    pub value: Option<B>,
+    pub headers: Vec<RecordHeader<B>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordHeader<B = Bytes> {
+    pub key: String,
+    pub value: Option<B>,
+}
+
+impl<B: AsRef<[u8]>> Record<B> {
+    fn write<W: Write>(&self, s: &mut KafkaSerializer<W>) -> Result<()> {
+        // Record is prefixed with its size, so it has to be buffered first
+        let mut buf = Vec::new();
+        let mut r = KafkaSerializer::new(&mut buf);
+        r.emit_int8(self.attributes)?;
+        r.emit_varlong(self.timestamp_delta)?;
+        r.emit_varint(self.offset_delta)?;
+        emit_varint_bytes(&mut r, self.key.as_ref().map(AsRef::as_ref))?;
+        emit_varint_bytes(&mut r, self.value.as_ref().map(AsRef::as_ref))?;
+        r.emit_varint(self.headers.len().try_into()?)?;
+        for h in &self.headers {
+            emit_varint_bytes(&mut r, Some(h.key.as_bytes()))?;
+            emit_varint_bytes(&mut r, h.value.as_ref().map(AsRef::as_ref))?;
+        }
+
+        s.emit_varint(buf.len().try_into()?)?;
+        s.emit_raw_bytes(&buf)
+    }
+}
+
+impl<B> Record<B> {
+    fn read<F>(d: &mut KafkaDeserializer, payload: &F) -> Result<Record<B>>
+    where
+        F: Fn(&[u8]) -> B,
+    {
+        let len: usize = d.read_varint()?.try_into()?;
+        let mut r = KafkaDeserializer::new(d.read_raw_bytes(len)?);
+        Ok(Record {
+            attributes: r.read_int8()?,
+            timestamp_delta: r.read_varlong()?,
+            offset_delta: r.read_varint()?,
+            key: read_varint_bytes(&mut r)?.map(payload),
+            value: read_varint_bytes(&mut r)?.map(payload),
+            headers: {
+                let count: usize = r.read_varint()?.try_into()?;
+                let mut headers = Vec::with_capacity(count.min(r.remaining()));
+                for _ in 0..count {
+                    let key = read_varint_bytes(&mut r)?.ok_or(Error::OutOfBounds)?;
+                    headers.push(RecordHeader {
+                        key: std::str::from_utf8(key)?.to_string(),
+                        value: read_varint_bytes(&mut r)?.map(payload),
+                    });
+                }
+                headers
+            },
+        })
+    }
+}
+
+fn emit_varint_bytes<W: Write>(s: &mut KafkaSerializer<W>, v: Option<&[u8]>) -> Result<()> {
+    match v {
+        Some(b) => {
+            s.emit_varint(b.len().try_into()?)?;
+            s.emit_raw_bytes(b)
+        }
+        None => s.emit_varint(-1),
+    }
+}
+
+fn read_varint_bytes<'a>(d: &mut KafkaDeserializer<'a>) -> Result<Option<&'a [u8]>> {
+    let len = d.read_varint()?;
+    if len < 0 {
+        Ok(None)
+    } else {
+        Ok(Some(d.read_raw_bytes(len.try_into()?)?))
+    }
+}
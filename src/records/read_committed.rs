@@ -0,0 +1,113 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use super::{ControlRecordType, RecordBatch, RecordBatches};
+use crate::error::{Error, Result};
+use crate::messages::{FetchResponseAbortedTransaction, FetchResponsePartition};
+use crate::ProducerId;
+
+// Filters batches of a fetched partition for read_committed isolation. Aborted
+// transactional batches and all control batches are dropped, so every batch
+// yielded holds committed records only.
+//
+// Aborted transactions are activated once a batch reaches their first offset
+// and stay so until the ABORT marker of the producer is seen.
+//
+// Ref: https://github.com/apache/kafka/blob/3.7.0/clients/src/main/java/org/apache/kafka/clients/consumer/internals/CompletedFetch.java
+pub struct ReadCommitted<I> {
+    batches: I,
+    // (first offset, producer id), smallest first offset on top
+    aborted_transactions: BinaryHeap<Reverse<(i64, ProducerId)>>,
+    aborted_producer_ids: HashSet<ProducerId>,
+}
+
+impl<I, B> ReadCommitted<I>
+where
+    I: Iterator<Item = Result<RecordBatch<B>>>,
+    B: AsRef<[u8]>,
+{
+    pub fn new(batches: I, aborted_transactions: &[FetchResponseAbortedTransaction]) -> Self {
+        ReadCommitted {
+            batches,
+            aborted_transactions: aborted_transactions
+                .iter()
+                .map(|t| Reverse((t.first_offset, t.producer_id)))
+                .collect(),
+            aborted_producer_ids: HashSet::new(),
+        }
+    }
+
+    fn consume_aborted_transactions_up_to(&mut self, offset: i64) {
+        while let Some(Reverse((first_offset, producer_id))) = self.aborted_transactions.peek() {
+            if *first_offset > offset {
+                break;
+            }
+            self.aborted_producer_ids.insert(*producer_id);
+            self.aborted_transactions.pop();
+        }
+    }
+
+    // Returns whether batch must be dropped
+    fn skip(&mut self, batch: &RecordBatch<B>) -> Result<bool> {
+        if batch.producer_id != super::NO_PRODUCER_ID {
+            self.consume_aborted_transactions_up_to(batch.last_offset());
+
+            if is_abort_marker(batch)? {
+                self.aborted_producer_ids.remove(&batch.producer_id);
+            } else if batch.is_transactional()
+                && self.aborted_producer_ids.contains(&batch.producer_id)
+            {
+                return Ok(true);
+            }
+        }
+        Ok(batch.is_control())
+    }
+}
+
+// Only the key of the control record is read, values of markers don't matter
+// here. Empty control batches, which the log cleaner may leave behind, are not
+// abort markers.
+fn is_abort_marker<B: AsRef<[u8]>>(batch: &RecordBatch<B>) -> Result<bool> {
+    if !batch.is_control() {
+        return Ok(false);
+    }
+    match batch.records.first() {
+        Some(r) => {
+            let key = r.key.as_ref().ok_or(Error::InvalidRecord)?.as_ref();
+            Ok(ControlRecordType::parse_key(key)? == ControlRecordType::Abort as i16)
+        }
+        None => Ok(false),
+    }
+}
+
+impl<I, B> Iterator for ReadCommitted<I>
+where
+    I: Iterator<Item = Result<RecordBatch<B>>>,
+    B: AsRef<[u8]>,
+{
+    type Item = Result<RecordBatch<B>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let batch = match self.batches.next()? {
+                Ok(batch) => batch,
+                Err(e) => return Some(Err(e)),
+            };
+            match self.skip(&batch) {
+                Ok(true) => continue,
+                Ok(false) => return Some(Ok(batch)),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+impl FetchResponsePartition {
+    // Committed batches of the partition, see ReadCommitted
+    pub fn committed_batches(&self) -> ReadCommitted<RecordBatches<'_>> {
+        ReadCommitted::new(
+            RecordBatches::new(self.records.as_deref().unwrap_or_default()),
+            self.aborted_transactions.as_deref().unwrap_or_default(),
+        )
+    }
+}
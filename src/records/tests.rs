@@ -0,0 +1,496 @@
+use std::convert::TryInto;
+
+use super::*;
+use crate::messages::{FetchResponseAbortedTransaction, FetchResponsePartition};
+
+fn record(offset_delta: i32, key: Option<&[u8]>, value: Option<&[u8]>) -> Record {
+    Record {
+        attributes: 0,
+        timestamp_delta: offset_delta as i64 * 10,
+        offset_delta,
+        key: key.map(<[u8]>::to_vec),
+        value: value.map(<[u8]>::to_vec),
+        headers: Vec::new(),
+    }
+}
+
+fn batch(base_offset: i64, records: Vec<Record>) -> RecordBatch {
+    RecordBatch {
+        base_offset,
+        partition_leader_epoch: 5,
+        attributes: 0,
+        last_offset_delta: records.len() as i32 - 1,
+        base_timestamp: 1_600_000_000_000,
+        max_timestamp: 1_600_000_000_000 + records.len() as i64 * 10,
+        producer_id: 42,
+        producer_epoch: 1,
+        base_sequence: 0,
+        records,
+    }
+}
+
+#[test]
+fn record_batch_round_trip() {
+    let mut with_headers = record(2, Some(b"k"), Some(b"v"));
+    with_headers.headers = vec![
+        RecordHeader {
+            key: "h1".to_string(),
+            value: Some(b"x".to_vec()),
+        },
+        RecordHeader {
+            key: "h2".to_string(),
+            value: None,
+        },
+    ];
+    let b = batch(
+        100,
+        vec![
+            record(0, None, Some(b"value")),
+            record(1, Some(b"key"), None),
+            with_headers,
+        ],
+    );
+
+    let buf = b.to_bytes().unwrap();
+    let (read, size) = RecordBatch::read(&buf).unwrap();
+    assert_eq!(size, buf.len());
+    assert_eq!(read, b);
+    assert_eq!(read.last_offset(), 102);
+    assert_eq!(peek_batch(&buf), Some((102, b.max_timestamp, buf.len())));
+}
+
+#[test]
+fn record_batches_skip_trailing_partial_batch() {
+    let mut buf = batch(0, vec![record(0, None, Some(b"a"))])
+        .to_bytes()
+        .unwrap();
+    let second = batch(1, vec![record(0, None, Some(b"b"))])
+        .to_bytes()
+        .unwrap();
+    buf.extend_from_slice(&second);
+    buf.extend_from_slice(&second[..second.len() - 1]);
+
+    let offsets: Vec<i64> = RecordBatches::new(&buf)
+        .map(|b| b.unwrap().base_offset)
+        .collect();
+    assert_eq!(offsets, vec![0, 1]);
+}
+
+#[test]
+fn crc32c_check_value() {
+    assert_eq!(crc32c::crc32c(b""), 0);
+    assert_eq!(crc32c::crc32c(b"123456789"), 0xe306_9283);
+}
+
+#[test]
+fn record_batch_crc_mismatch() {
+    let b = batch(0, vec![record(0, Some(b"key"), Some(b"value"))]);
+    let buf = b.to_bytes().unwrap();
+    let crc = u32::from_be_bytes(buf[17..ATTRIBUTES_OFFSET].try_into().unwrap());
+    assert_eq!(crc, crc32c::crc32c(&buf[ATTRIBUTES_OFFSET..]));
+
+    // Flipping any covered byte, e.g. last byte of the value, is detected
+    let mut corrupt = buf.clone();
+    *corrupt.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        RecordBatch::read(&corrupt),
+        Err(Error::CorruptRecordBatch)
+    ));
+}
+
+#[test]
+fn record_batch_bogus_record_count() {
+    let b = batch(0, vec![record(0, None, Some(b"value"))]);
+    let mut buf = b.to_bytes().unwrap();
+    buf[RECORD_BATCH_OVERHEAD - 4..RECORD_BATCH_OVERHEAD].copy_from_slice(&i32::MAX.to_be_bytes());
+    let crc = crc32c::crc32c(&buf[ATTRIBUTES_OFFSET..]);
+    buf[17..ATTRIBUTES_OFFSET].copy_from_slice(&crc.to_be_bytes());
+
+    assert!(matches!(RecordBatch::read(&buf), Err(Error::OutOfBounds)));
+}
+
+// Batch as written by a producer with gzip compression. Records are not
+// actually compressed, nothing looks past the header of such batches.
+fn compressed(b: &RecordBatch) -> Vec<u8> {
+    let mut buf = b.to_bytes().unwrap();
+    buf[ATTRIBUTES_OFFSET + 1] |= 1;
+    let crc = crc32c::crc32c(&buf[ATTRIBUTES_OFFSET..]);
+    buf[17..ATTRIBUTES_OFFSET].copy_from_slice(&crc.to_be_bytes());
+    buf
+}
+
+#[test]
+fn record_batches_step_over_compressed_batch() {
+    let mut buf = batch(0, vec![record(0, None, Some(b"a"))])
+        .to_bytes()
+        .unwrap();
+    let gzip = compressed(&batch(1, vec![record(0, None, Some(b"b"))]));
+    buf.extend_from_slice(&gzip);
+    batch(2, vec![record(0, None, Some(b"c"))])
+        .write(&mut buf)
+        .unwrap();
+
+    let read: Vec<Result<i64>> = RecordBatches::new(&buf)
+        .map(|b| b.map(|b| b.base_offset))
+        .collect();
+    assert_eq!(read.len(), 3);
+    assert_eq!(read[0].as_ref().unwrap(), &0);
+    assert!(matches!(read[1], Err(Error::UnsupportedCompression(1))));
+    assert_eq!(read[2].as_ref().unwrap(), &2);
+
+    // Header and raw records of the compressed batch
+    let (header, records, size) = RecordBatch::read_header(&gzip).unwrap();
+    assert_eq!(size, gzip.len());
+    assert_eq!((header.base_offset, header.compression()), (1, 1));
+    assert!(header.records.is_empty());
+    assert_eq!(records, &gzip[RECORD_BATCH_OVERHEAD - 4..]);
+    assert_eq!(records[..4], 1i32.to_be_bytes());
+
+    // Other errors still end iteration
+    let mut corrupt = buf.clone();
+    corrupt[ATTRIBUTES_OFFSET] ^= 1;
+    assert_eq!(RecordBatches::new(&corrupt).count(), 1);
+}
+
+// Single record batch of `producer_id` at `offset`
+fn txn_batch(offset: i64, producer_id: ProducerId) -> RecordBatch {
+    RecordBatch {
+        attributes: RecordBatch::TRANSACTIONAL_FLAG_MASK,
+        producer_id,
+        ..batch(offset, vec![record(0, None, Some(b"v"))])
+    }
+}
+
+fn marker(offset: i64, producer_id: ProducerId, commit: bool) -> RecordBatch {
+    let m = EndTransactionMarker {
+        coordinator_epoch: 0,
+    };
+    let c = if commit {
+        ControlRecord::Commit(m)
+    } else {
+        ControlRecord::Abort(m)
+    };
+    RecordBatch {
+        attributes: RecordBatch::TRANSACTIONAL_FLAG_MASK | RecordBatch::CONTROL_FLAG_MASK,
+        producer_id,
+        ..batch(offset, vec![c.to_record(0).unwrap()])
+    }
+}
+
+fn plain_batch(offset: i64) -> RecordBatch {
+    RecordBatch {
+        producer_id: NO_PRODUCER_ID,
+        producer_epoch: NO_PRODUCER_EPOCH,
+        base_sequence: NO_SEQUENCE,
+        ..batch(offset, vec![record(0, None, Some(b"v"))])
+    }
+}
+
+fn aborted(producer_id: ProducerId, first_offset: i64) -> FetchResponseAbortedTransaction {
+    FetchResponseAbortedTransaction {
+        producer_id,
+        first_offset,
+    }
+}
+
+fn committed_offsets(
+    batches: Vec<RecordBatch>,
+    aborted_transactions: &[FetchResponseAbortedTransaction],
+) -> Vec<i64> {
+    ReadCommitted::new(batches.into_iter().map(Ok), aborted_transactions)
+        .map(|b| b.unwrap().base_offset)
+        .collect()
+}
+
+#[test]
+fn read_committed_drops_aborted_transaction() {
+    let batches = vec![
+        txn_batch(0, 1),
+        txn_batch(1, 1),
+        marker(2, 1, false),
+        plain_batch(3),
+    ];
+    assert_eq!(committed_offsets(batches, &[aborted(1, 0)]), vec![3]);
+}
+
+#[test]
+fn read_committed_keeps_committed_transaction() {
+    let batches = vec![
+        txn_batch(0, 2),
+        plain_batch(1),
+        txn_batch(2, 2),
+        marker(3, 2, true),
+    ];
+    assert_eq!(committed_offsets(batches, &[]), vec![0, 1, 2]);
+}
+
+#[test]
+fn read_committed_interleaved_transactions() {
+    // Producer 1 aborts twice and commits in between, producer 2 commits while
+    // the first transaction of producer 1 is open
+    let batches = vec![
+        txn_batch(0, 1),
+        txn_batch(1, 2),
+        txn_batch(2, 1),
+        marker(3, 1, false),
+        txn_batch(4, 2),
+        txn_batch(5, 1),
+        marker(6, 2, true),
+        marker(7, 1, true),
+        txn_batch(8, 1),
+        plain_batch(9),
+        marker(10, 1, false),
+        txn_batch(11, 1),
+    ];
+    let aborted_transactions = [aborted(1, 8), aborted(1, 0)];
+    assert_eq!(
+        committed_offsets(batches, &aborted_transactions),
+        vec![1, 4, 5, 9, 11]
+    );
+}
+
+#[test]
+fn read_committed_empty_control_batch() {
+    // Cleaned control batch ends no transaction, the aborted one goes on
+    // until its ABORT marker
+    let mut empty = marker(1, 1, false);
+    empty.records.clear();
+    let batches = vec![
+        txn_batch(0, 1),
+        empty,
+        txn_batch(2, 1),
+        marker(3, 1, false),
+        plain_batch(4),
+    ];
+    assert_eq!(committed_offsets(batches, &[aborted(1, 0)]), vec![4]);
+}
+
+#[test]
+fn read_committed_ignores_marker_value() {
+    let mut commit = marker(1, 2, true);
+    commit.records[0].value = Some(b"x".to_vec());
+    let batches = vec![txn_batch(0, 2), commit, plain_batch(2)];
+    assert_eq!(committed_offsets(batches, &[]), vec![0, 2]);
+}
+
+#[test]
+fn committed_batches_of_fetch_response_partition() {
+    let mut records = Vec::new();
+    for b in [txn_batch(0, 1), marker(1, 1, false), txn_batch(2, 3)] {
+        b.write(&mut records).unwrap();
+    }
+    let partition = FetchResponsePartition {
+        // Vec<u8> or, with the bytes feature, Bytes
+        records: Some(records.into_iter().collect()),
+        aborted_transactions: Some(vec![aborted(1, 0)]),
+        ..Default::default()
+    };
+    let offsets: Vec<i64> = partition
+        .committed_batches()
+        .map(|b| b.unwrap().base_offset)
+        .collect();
+    assert_eq!(offsets, vec![2]);
+}
+
+fn control_round_trip(c: ControlRecord, key: [u8; 4], value: &[u8]) -> ControlRecord {
+    let r = c.to_record(3).unwrap();
+    assert_eq!(r.offset_delta, 3);
+    assert_eq!(r.key.as_deref(), Some(&key[..]));
+    assert_eq!(r.value.as_deref(), Some(value));
+    ControlRecord::from_record(&r).unwrap()
+}
+
+#[test]
+fn end_transaction_marker_records() {
+    let m = || EndTransactionMarker {
+        coordinator_epoch: 0x0102_0304,
+    };
+    // Version 0 followed by the epoch, no tagged fields
+    let value = [0, 0, 1, 2, 3, 4];
+    match control_round_trip(ControlRecord::Abort(m()), [0, 0, 0, 0], &value) {
+        ControlRecord::Abort(m) => assert_eq!(m.coordinator_epoch, 0x0102_0304),
+        c => panic!("unexpected control record {}", c.record_type()),
+    }
+    match control_round_trip(ControlRecord::Commit(m()), [0, 0, 0, 1], &value) {
+        ControlRecord::Commit(m) => assert_eq!(m.coordinator_epoch, 0x0102_0304),
+        c => panic!("unexpected control record {}", c.record_type()),
+    }
+
+    assert!(matches!(
+        EndTransactionMarker::from_bytes(&value[..5]),
+        Err(Error::Decode { .. })
+    ));
+    // Newer versions are read as the latest known one
+    let m = EndTransactionMarker::from_bytes(&[0, 1, 1, 2, 3, 4, 5]).unwrap();
+    assert_eq!(m.coordinator_epoch, 0x0102_0304);
+    assert!(matches!(
+        EndTransactionMarker::from_bytes(&[0xff, 0xff, 1, 2, 3, 4]),
+        Err(Error::UnsupportedVersion(-1))
+    ));
+}
+
+#[test]
+fn leader_change_record() {
+    let m = LeaderChangeMessage {
+        leader_id: 2,
+        voters: vec![Voter { voter_id: 1 }, Voter { voter_id: 2 }],
+        granting_voters: vec![Voter { voter_id: 2 }],
+    };
+    // Flexible from version 0: compact arrays, tagged fields after every struct
+    let value = [
+        0, 0, // version
+        0, 0, 0, 2, // leader_id
+        3, 0, 0, 0, 1, 0, 0, 0, 0, 2, 0, // voters
+        2, 0, 0, 0, 2, 0, // granting_voters
+        0, // tagged fields
+    ];
+    match control_round_trip(ControlRecord::LeaderChange(m), [0, 0, 0, 2], &value) {
+        ControlRecord::LeaderChange(m) => {
+            assert_eq!(m.leader_id, 2);
+            let ids = |v: &[Voter]| v.iter().map(|v| v.voter_id).collect::<Vec<_>>();
+            assert_eq!(ids(&m.voters), vec![1, 2]);
+            assert_eq!(ids(&m.granting_voters), vec![2]);
+        }
+        c => panic!("unexpected control record {}", c.record_type()),
+    }
+}
+
+#[test]
+fn snapshot_control_records() {
+    let header = SnapshotHeaderRecord {
+        last_contained_log_timestamp: 0x0102,
+    };
+    let value = [0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 0];
+    match control_round_trip(ControlRecord::SnapshotHeader(header), [0, 0, 0, 3], &value) {
+        ControlRecord::SnapshotHeader(h) => assert_eq!(h.last_contained_log_timestamp, 0x0102),
+        c => panic!("unexpected control record {}", c.record_type()),
+    }
+    let footer = ControlRecord::SnapshotFooter(SnapshotFooterRecord);
+    assert!(matches!(
+        control_round_trip(footer, [0, 0, 0, 4], &[0, 0, 0]),
+        ControlRecord::SnapshotFooter(_)
+    ));
+}
+
+#[test]
+fn unknown_control_records() {
+    // Types without a decoder are kept raw, value is not looked at
+    let mut r = record(0, Some(&[0, 0, 0, 6]), Some(b"?"));
+    match ControlRecord::from_record(&r).unwrap() {
+        ControlRecord::Unknown(t) => assert_eq!(t, ControlRecordType::KRaftVoters as i16),
+        c => panic!("unexpected control record {}", c.record_type()),
+    }
+    r.key = Some(vec![0, 0, 0x7f, 0xff]);
+    let c = ControlRecord::from_record(&r).unwrap();
+    assert_eq!(c.record_type(), i16::MAX);
+    assert!(matches!(
+        c.to_record(0),
+        Err(Error::UnknownControlRecordType(i16::MAX))
+    ));
+
+    r.key = Some(vec![0xff, 0xff, 0, 0]);
+    assert!(matches!(
+        ControlRecord::from_record(&r),
+        Err(Error::UnsupportedVersion(-1))
+    ));
+    r.key = Some(vec![0, 0, 0]);
+    assert!(matches!(
+        ControlRecord::from_record(&r),
+        Err(Error::OutOfBounds)
+    ));
+    r.key = None;
+    assert!(matches!(
+        ControlRecord::from_record(&r),
+        Err(Error::InvalidRecord)
+    ));
+}
+
+#[cfg(feature = "bytes")]
+fn shares(buf: &::bytes::Bytes, v: &Option<::bytes::Bytes>) -> bool {
+    let v = v.as_ref().unwrap();
+    buf.as_ptr_range().contains(&v.as_ptr())
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn shared_batches_slice_buffer() {
+    let b = batch(0, vec![record(0, Some(b"k"), Some(b"v"))]);
+    let mut buf = b.to_bytes().unwrap();
+    buf.extend_from_slice(&compressed(&batch(1, vec![record(0, None, Some(b"c"))])));
+    batch(2, vec![record(0, None, Some(b"w"))])
+        .write(&mut buf)
+        .unwrap();
+    let buf = ::bytes::Bytes::from(buf);
+
+    let (read, size) = RecordBatch::read_shared(&buf).unwrap();
+    let (copy, _) = RecordBatch::read(&buf).unwrap();
+    assert_eq!(size, b.to_bytes().unwrap().len());
+    assert_eq!(
+        read.records[0].value.as_deref(),
+        copy.records[0].value.as_deref()
+    );
+    assert!(shares(&buf, &read.records[0].key));
+    assert!(shares(&buf, &read.records[0].value));
+
+    // Compressed batch is stepped over just like by RecordBatches
+    let read: Vec<Result<RecordBatch<::bytes::Bytes>>> =
+        SharedRecordBatches::new(buf.clone()).collect();
+    assert_eq!(read.len(), 3);
+    assert!(matches!(read[1], Err(Error::UnsupportedCompression(1))));
+    let last = read[2].as_ref().unwrap();
+    assert_eq!(last.base_offset, 2);
+    assert!(shares(&buf, &last.records[0].value));
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn shared_fetch_response_records() {
+    use crate::de::{deserialize_response_shared, KafkaFlexiDeserializer, KafkaProtoDecodable};
+    use crate::messages::{FetchResponse, FetchResponseTopic};
+    use crate::ser::{KafkaFlexiSerializer, KafkaFlexibleEncoder, KafkaProtoEncodable};
+    use crate::KafkaMessage;
+
+    let mut records = Vec::new();
+    for b in [txn_batch(0, 1), marker(1, 1, false), txn_batch(2, 3)] {
+        b.write(&mut records).unwrap();
+    }
+    let m = FetchResponse {
+        responses: vec![FetchResponseTopic {
+            topic: "t".to_string(),
+            partitions: vec![FetchResponsePartition {
+                records: Some(records.into()),
+                aborted_transactions: Some(vec![aborted(1, 0)]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    for ver in [4, 12] {
+        // Correlation id, then tagged fields of response header v1 if flexible
+        let use_flexible = ver >= FetchResponse::FLEXIBLE_VERSION;
+        let mut buf = vec![0, 0, 0, 9];
+        let mut s = KafkaFlexiSerializer::new(use_flexible, &mut buf);
+        s.emit_empty_tagged_fields().unwrap();
+        m.serialize(ver, &mut s).unwrap();
+        let buf = ::bytes::Bytes::from(buf);
+
+        let (hdr, r) = deserialize_response_shared::<FetchResponse>(ver, &buf).unwrap();
+        assert_eq!(hdr.correlation_id, 9);
+        let partition = &r.responses[0].partitions[0];
+        assert!(shares(&buf, &partition.records));
+        let committed: Vec<RecordBatch<::bytes::Bytes>> = partition
+            .committed_shared_batches()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(committed.len(), 1);
+        assert_eq!(committed[0].base_offset, 2);
+        assert!(shares(&buf, &committed[0].records[0].value));
+
+        // Plain deserializer copies
+        let mut d = KafkaFlexiDeserializer::new(use_flexible, &buf[4 + use_flexible as usize..]);
+        let r = FetchResponse::deserialize(ver, &mut d).unwrap();
+        assert!(!shares(&buf, &r.responses[0].partitions[0].records));
+    }
+}
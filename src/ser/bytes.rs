@@ -0,0 +1,39 @@
+use std::convert::TryInto;
+
+use bytes::{BufMut, BytesMut};
+
+use super::{message_size, serialize_message, KafkaProtoEncodable};
+use crate::error::Result;
+use crate::KafkaMessage;
+
+// Same as serialize_message, buf is grown at most once as the size is known
+// upfront
+pub fn serialize_message_bytes<M>(
+    m: &M,
+    ver: i16,
+    client_id: &str,
+    buf: &mut BytesMut,
+) -> Result<()>
+where
+    M: KafkaProtoEncodable + KafkaMessage,
+{
+    buf.reserve(message_size(m, ver, client_id)?);
+    serialize_message(m, ver, client_id, &mut buf.writer())
+}
+
+// Request prefixed with its INT32 size, as it goes over the wire, e.g. from an
+// encoder of a tokio codec
+pub fn serialize_request_frame<M>(
+    m: &M,
+    ver: i16,
+    client_id: &str,
+    buf: &mut BytesMut,
+) -> Result<()>
+where
+    M: KafkaProtoEncodable + KafkaMessage,
+{
+    let size = message_size(m, ver, client_id)?;
+    buf.reserve(4 + size);
+    buf.put_i32(size.try_into()?);
+    serialize_message(m, ver, client_id, &mut buf.writer())
+}
@@ -7,9 +7,9 @@ use uuid;
 // followed by serialized message content.
 // `serialize` method should not be serializing header, only message content.
 //
-// Some implementations can ignore version argument, usually these are
-// elements of arrays, which are not top-level protocol messages themselves, and
-// therefore emit same bytes regardless of version.
+// Elements of arrays are not top-level protocol messages themselves, they
+// receive version of the enclosing message. Implementations which emit same
+// bytes regardless of version can ignore version argument.
 pub trait KafkaProtoEncodable {
     fn serialize<S: KafkaFlexibleEncoder>(
         &self,
@@ -18,6 +18,61 @@ pub trait KafkaProtoEncodable {
     ) -> Result<S::Ok, S::Error>;
 }
 
+// Primitive types, which appear as array elements and tagged fields
+impl KafkaProtoEncodable for i8 {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int8(*self)
+    }
+}
+
+impl KafkaProtoEncodable for i16 {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int16(*self)
+    }
+}
+
+impl KafkaProtoEncodable for i32 {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int32(*self)
+    }
+}
+
+impl KafkaProtoEncodable for i64 {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_int64(*self)
+    }
+}
+
+impl KafkaProtoEncodable for uuid::Uuid {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(*self)
+    }
+}
+
+impl KafkaProtoEncodable for crate::TopicId {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_uuid(self.0)
+    }
+}
+
+impl KafkaProtoEncodable for String {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_string(self)
+    }
+}
+
+impl KafkaProtoEncodable for Option<String> {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, _v: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_nullable_string(self.as_deref())
+    }
+}
+
+impl<T: KafkaProtoEncodable> KafkaProtoEncodable for Vec<T> {
+    fn serialize<S: KafkaFlexibleEncoder>(&self, ver: i16, s: &mut S) -> Result<S::Ok, S::Error> {
+        s.emit_array(ver, self.iter())
+    }
+}
+
 // Low level Kafka protocol primitive types
 // Ref: https://kafka.apache.org/protocol#protocol_types
 pub trait KafkaProtoEncoder {
@@ -29,9 +84,11 @@ pub trait KafkaProtoEncoder {
     fn emit_int16(&mut self, v: i16) -> Result<Self::Ok, Self::Error>;
     fn emit_int32(&mut self, v: i32) -> Result<Self::Ok, Self::Error>;
     fn emit_int64(&mut self, v: i64) -> Result<Self::Ok, Self::Error>;
+    fn emit_uint16(&mut self, v: u16) -> Result<Self::Ok, Self::Error>;
     fn emit_uint32(&mut self, v: u32) -> Result<Self::Ok, Self::Error>;
     fn emit_varint(&mut self, v: i32) -> Result<Self::Ok, Self::Error>;
     fn emit_varlong(&mut self, v: i64) -> Result<Self::Ok, Self::Error>;
+    fn emit_unsigned_varint(&mut self, v: u32) -> Result<Self::Ok, Self::Error>;
     fn emit_uuid(&mut self, v: uuid::Uuid) -> Result<Self::Ok, Self::Error>;
 
     fn emit_float64(&mut self, v: f64) -> Result<Self::Ok, Self::Error>;
@@ -45,7 +102,12 @@ pub trait KafkaProtoEncoder {
     fn emit_compact_nullable_bytes(&mut self, v: Option<&[u8]>) -> Result<Self::Ok, Self::Error>;
     //fn emit_records(&mut self, v: &[Records]) -> Result<Self::Ok, Self::Error>;
     fn emit_array_hdr(&mut self, len: usize) -> Result<Self::Ok, Self::Error>;
+    fn emit_nullable_array_hdr(&mut self, len: Option<usize>) -> Result<Self::Ok, Self::Error>;
     fn emit_compact_array_hdr(&mut self, len: usize) -> Result<Self::Ok, Self::Error>;
+    fn emit_compact_nullable_array_hdr(
+        &mut self,
+        len: Option<usize>,
+    ) -> Result<Self::Ok, Self::Error>;
 }
 
 // Encoder, which automatically selects implementation for bytes,strings and arrays,
@@ -59,6 +121,7 @@ pub trait KafkaFlexibleEncoder {
     fn emit_int16(&mut self, v: i16) -> Result<Self::Ok, Self::Error>;
     fn emit_int32(&mut self, v: i32) -> Result<Self::Ok, Self::Error>;
     fn emit_int64(&mut self, v: i64) -> Result<Self::Ok, Self::Error>;
+    fn emit_uint16(&mut self, v: u16) -> Result<Self::Ok, Self::Error>;
     fn emit_uint32(&mut self, v: u32) -> Result<Self::Ok, Self::Error>;
     fn emit_varint(&mut self, v: i32) -> Result<Self::Ok, Self::Error>;
     fn emit_varlong(&mut self, v: i64) -> Result<Self::Ok, Self::Error>;
@@ -70,10 +133,49 @@ pub trait KafkaFlexibleEncoder {
     fn emit_bytes(&mut self, v: &[u8]) -> Result<Self::Ok, Self::Error>;
     fn emit_nullable_bytes(&mut self, v: Option<&[u8]>) -> Result<Self::Ok, Self::Error>;
     //fn emit_records(&mut self, v: &[Records]) -> Result<Self::Ok, Self::Error>;
-    fn emit_array<'a, T: 'a>(
+    fn emit_array<'a, T: KafkaProtoEncodable + 'a>(
         &mut self,
+        version: i16,
         v: impl ExactSizeIterator<Item = &'a T>,
-    ) -> Result<Self::Ok, Self::Error>
-    where
-        T: KafkaProtoEncodable;
+    ) -> Result<Self::Ok, Self::Error>;
+    // None is encoded as null, unlike an empty array
+    fn emit_nullable_array<'a, T: KafkaProtoEncodable + 'a>(
+        &mut self,
+        version: i16,
+        v: Option<impl ExactSizeIterator<Item = &'a T>>,
+    ) -> Result<Self::Ok, Self::Error>;
+
+    // Tagged fields section, which ends every struct in flexible versions.
+    // In non-flexible versions there is no such section and nothing is emitted.
+    //
+    // `count` is a number of `emit_tagged_field` calls which follow the header.
+    fn emit_tagged_fields_hdr(&mut self, count: usize) -> Result<Self::Ok, Self::Error>;
+    fn emit_tagged_field<T: KafkaProtoEncodable>(
+        &mut self,
+        tag: u32,
+        version: i16,
+        v: &T,
+    ) -> Result<Self::Ok, Self::Error>;
+
+    fn emit_empty_tagged_fields(&mut self) -> Result<Self::Ok, Self::Error> {
+        self.emit_tagged_fields_hdr(0)
+    }
+
+    // Error of a field set to a non-default value, which `version` lacks.
+    // Fields the schema marks as ignorable are dropped silently instead.
+    fn unsupported_field(&self, name: &'static str, version: i16) -> Self::Error;
+
+    // Checks that a field absent in `version` holds its default value
+    fn check_absent_field(
+        &self,
+        name: &'static str,
+        version: i16,
+        is_default: bool,
+    ) -> Result<(), Self::Error> {
+        if is_default {
+            Ok(())
+        } else {
+            Err(self.unsupported_field(name, version))
+        }
+    }
 }
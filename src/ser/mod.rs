@@ -1,10 +1,18 @@
 use std::convert::TryInto;
 use std::io::Write;
 
+#[cfg(feature = "bytes")]
+pub use self::bytes::*;
 use crate::error::{Error, Result};
 pub use encoder::*;
+pub use size::*;
 
+#[cfg(feature = "bytes")]
+mod bytes;
 mod encoder;
+mod size;
+#[cfg(test)]
+mod tests;
 mod varint;
 
 pub struct KafkaSerializer<W> {
@@ -12,10 +20,15 @@ pub struct KafkaSerializer<W> {
 }
 
 impl<W: Write> KafkaSerializer<W> {
-    fn new(writer: W) -> KafkaSerializer<W> {
+    pub fn new(writer: W) -> KafkaSerializer<W> {
         KafkaSerializer { writer }
     }
 
+    // Bytes which are not length prefixed, e.g. key and value of a record
+    pub(crate) fn emit_raw_bytes(&mut self, v: &[u8]) -> Result<()> {
+        self.writer.write_all(v).map_err(Into::into)
+    }
+
     fn emit_varuint(&mut self, v: u32) -> Result<()> {
         let mut buf = [0u8; 5];
         let count = varint::encode_varint32(v, &mut buf);
@@ -59,6 +72,11 @@ impl<W: Write> KafkaProtoEncoder for KafkaSerializer<W> {
         self.writer.write_all(&v.to_be_bytes()).map_err(Into::into)
     }
 
+    // KF: UINT16
+    fn emit_uint16(&mut self, v: u16) -> Result<()> {
+        self.writer.write_all(&v.to_be_bytes()).map_err(Into::into)
+    }
+
     // KF: UINT32
     fn emit_uint32(&mut self, v: u32) -> Result<()> {
         self.writer.write_all(&v.to_be_bytes()).map_err(Into::into)
@@ -74,8 +92,14 @@ impl<W: Write> KafkaProtoEncoder for KafkaSerializer<W> {
         self.emit_varulong(varint::encode_zig_zag_64(v))
     }
 
-    fn emit_uuid(&mut self, _v: uuid::Uuid) -> Result<()> {
-        unimplemented!()
+    // KF: UNSIGNED_VARINT
+    fn emit_unsigned_varint(&mut self, v: u32) -> Result<()> {
+        self.emit_varuint(v)
+    }
+
+    // KF: UUID, 16 bytes big-endian
+    fn emit_uuid(&mut self, v: uuid::Uuid) -> Result<()> {
+        self.writer.write_all(v.as_bytes()).map_err(Into::into)
     }
 
     // KF: FLOAT64
@@ -86,12 +110,12 @@ impl<W: Write> KafkaProtoEncoder for KafkaSerializer<W> {
     // KF: STRING
     fn emit_string(&mut self, v: &str) -> Result<()> {
         self.emit_int16(v.len().try_into()?)?;
-        self.writer.write_all(&v.as_bytes()).map_err(Into::into)
+        self.writer.write_all(v.as_bytes()).map_err(Into::into)
     }
 
-    // KF: COMPACT_STRING
+    // KF: COMPACT_STRING, length is encoded as N+1
     fn emit_compact_string(&mut self, v: &str) -> Result<()> {
-        self.emit_varuint(v.len().try_into()?)?;
+        self.emit_varuint((v.len() + 1).try_into()?)?;
         self.writer.write_all(v.as_bytes()).map_err(Into::into)
     }
 
@@ -107,7 +131,7 @@ impl<W: Write> KafkaProtoEncoder for KafkaSerializer<W> {
     fn emit_compact_nullable_string(&mut self, v: Option<&str>) -> Result<()> {
         match v {
             Some(s) => self.emit_compact_string(s),
-            None => self.emit_varint(0),
+            None => self.emit_varuint(0),
         }
     }
 
@@ -117,9 +141,9 @@ impl<W: Write> KafkaProtoEncoder for KafkaSerializer<W> {
         self.writer.write_all(v).map_err(Into::into)
     }
 
-    // KF: COMPACT_BYTES
+    // KF: COMPACT_BYTES, length is encoded as N+1
     fn emit_compact_bytes(&mut self, v: &[u8]) -> Result<()> {
-        self.emit_varuint(v.len().try_into()?)?;
+        self.emit_varuint((v.len() + 1).try_into()?)?;
         self.writer.write_all(v).map_err(Into::into)
     }
 
@@ -134,30 +158,37 @@ impl<W: Write> KafkaProtoEncoder for KafkaSerializer<W> {
         }
     }
 
-    // KF: COMPACT_BYTES
+    // KF: COMPACT_NULLABLE_BYTES
     fn emit_compact_nullable_bytes(&mut self, v: Option<&[u8]>) -> Result<()> {
         match v {
-            Some(s) => {
-                self.emit_varuint(s.len().try_into()?)?;
-                self.writer.write_all(s).map_err(Into::into)
-            }
+            Some(s) => self.emit_compact_bytes(s),
             None => self.emit_varuint(0),
         }
     }
 
+    // KF: ARRAY
     fn emit_array_hdr(&mut self, len: usize) -> Result<()> {
-        if len > 0 {
-            self.emit_int32(len.try_into()?)
-        } else {
-            self.emit_int32(-1)
+        self.emit_int32(len.try_into()?)
+    }
+
+    // KF: NULLABLE_ARRAY
+    fn emit_nullable_array_hdr(&mut self, len: Option<usize>) -> Result<()> {
+        match len {
+            Some(len) => self.emit_array_hdr(len),
+            None => self.emit_int32(-1),
         }
     }
 
+    // KF: COMPACT_ARRAY, length is encoded as N+1
     fn emit_compact_array_hdr(&mut self, len: usize) -> Result<()> {
-        if len > 0 {
-            self.emit_varuint(len.try_into()?)
-        } else {
-            self.emit_varuint(0)
+        self.emit_varuint((len + 1).try_into()?)
+    }
+
+    // KF: COMPACT_NULLABLE_ARRAY
+    fn emit_compact_nullable_array_hdr(&mut self, len: Option<usize>) -> Result<()> {
+        match len {
+            Some(len) => self.emit_compact_array_hdr(len),
+            None => self.emit_varuint(0),
         }
     }
 }
@@ -198,6 +229,10 @@ impl<W: Write> KafkaFlexibleEncoder for KafkaFlexiSerializer<W> {
         self.serializer.emit_int64(v)
     }
 
+    fn emit_uint16(&mut self, v: u16) -> Result<()> {
+        self.serializer.emit_uint16(v)
+    }
+
     fn emit_uint32(&mut self, v: u32) -> Result<()> {
         self.serializer.emit_uint32(v)
     }
@@ -250,10 +285,11 @@ impl<W: Write> KafkaFlexibleEncoder for KafkaFlexiSerializer<W> {
         }
     }
     //fn emit_records(&mut self, v: &[Records]) -> Result<()>;
-    fn emit_array<'a, T: 'a>(&mut self, v: impl ExactSizeIterator<Item = &'a T>) -> Result<()>
-    where
-        T: KafkaProtoEncodable,
-    {
+    fn emit_array<'a, T: KafkaProtoEncodable + 'a>(
+        &mut self,
+        version: i16,
+        v: impl ExactSizeIterator<Item = &'a T>,
+    ) -> Result<()> {
         if self.use_flexible {
             self.serializer.emit_compact_array_hdr(v.len())?;
         } else {
@@ -261,10 +297,56 @@ impl<W: Write> KafkaFlexibleEncoder for KafkaFlexiSerializer<W> {
         };
 
         for e in v {
-            e.serialize(0, self)?;
+            e.serialize(version, self)?;
         }
         Ok(())
     }
+
+    fn emit_nullable_array<'a, T: KafkaProtoEncodable + 'a>(
+        &mut self,
+        version: i16,
+        v: Option<impl ExactSizeIterator<Item = &'a T>>,
+    ) -> Result<()> {
+        match v {
+            Some(v) => self.emit_array(version, v),
+            None if self.use_flexible => self.serializer.emit_compact_nullable_array_hdr(None),
+            None => self.serializer.emit_nullable_array_hdr(None),
+        }
+    }
+
+    fn emit_tagged_fields_hdr(&mut self, count: usize) -> Result<()> {
+        if self.use_flexible {
+            self.serializer.emit_varuint(count.try_into()?)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn emit_tagged_field<T: KafkaProtoEncodable>(
+        &mut self,
+        tag: u32,
+        version: i16,
+        v: &T,
+    ) -> Result<()> {
+        if !self.use_flexible {
+            return Ok(());
+        }
+
+        // Tagged field is prefixed with its size, so it has to be buffered first
+        let mut buf = Vec::new();
+        v.serialize(version, &mut KafkaFlexiSerializer::new(true, &mut buf))?;
+
+        self.serializer.emit_varuint(tag)?;
+        self.serializer.emit_varuint(buf.len().try_into()?)?;
+        self.serializer.writer.write_all(&buf).map_err(Into::into)
+    }
+
+    fn unsupported_field(&self, name: &'static str, version: i16) -> Error {
+        Error::UnsupportedFieldForVersion {
+            field: name,
+            version,
+        }
+    }
 }
 
 use crate::messages::RequestHeader;
@@ -285,17 +367,63 @@ where
         request_api_key: M::API_KEY,
         request_api_version: ver,
         correlation_id: 0,
-        client_id: client_id,
+        client_id,
     };
 
     let is_flexible = ver >= M::FLEXIBLE_VERSION;
-
-    // Ref: https://github.com/apache/kafka/blob/2.5.0/generator/src/main/java/org/apache/kafka/message/ApiMessageTypeGenerator.java#L252-L318
-    // header version doesn't seem to affect bytes on a wire, but leaving it is here for completeness
-    let hdr_ver = if is_flexible { 2i16 } else { 1i16 };
+    let hdr_ver = M::request_header_version(ver);
 
     let mut s = ser::KafkaFlexiSerializer::new(is_flexible, w);
 
     hdr.serialize(hdr_ver, &mut s.serializer)?;
     m.serialize(ver, &mut s)
 }
+
+// Exact number of bytes serialize_message writes, e.g. for the INT32 size prefix
+// of a request frame or to preallocate a buffer
+pub fn message_size<M>(m: &M, ver: i16, client_id: &str) -> crate::error::Result<usize>
+where
+    M: KafkaProtoEncodable + KafkaMessage,
+{
+    let hdr = RequestHeader {
+        request_api_key: M::API_KEY,
+        request_api_version: ver,
+        correlation_id: 0,
+        client_id,
+    };
+
+    let mut s = KafkaFlexiSizeCounter::new(ver >= M::FLEXIBLE_VERSION);
+
+    hdr.serialize(M::request_header_version(ver), &mut s.counter)?;
+    m.serialize(ver, &mut s)?;
+    Ok(s.size())
+}
+
+// Exact number of bytes a struct occupies at the version, without a header
+pub fn serialized_size<T: KafkaProtoEncodable>(
+    v: &T,
+    ver: i16,
+    use_flexible: bool,
+) -> crate::error::Result<usize> {
+    let mut s = KafkaFlexiSizeCounter::new(use_flexible);
+    v.serialize(ver, &mut s)?;
+    Ok(s.size())
+}
+
+// Payloads embedded into group protocol messages (e.g. ConsumerProtocol
+// subscription, which travels as JoinGroupRequestProtocol metadata) and records
+// of internal topics are prefixed with their version instead of a request header.
+pub fn serialize_versioned<M, W>(
+    m: &M,
+    ver: i16,
+    flexible_ver: i16,
+    w: &mut W,
+) -> crate::error::Result<()>
+where
+    M: KafkaProtoEncodable,
+    W: Write,
+{
+    let mut s = ser::KafkaFlexiSerializer::new(ver >= flexible_ver, w);
+    s.emit_int16(ver)?;
+    m.serialize(ver, &mut s)
+}
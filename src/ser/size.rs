@@ -0,0 +1,306 @@
+use std::convert::TryInto;
+
+use super::{KafkaFlexibleEncoder, KafkaProtoEncodable, KafkaProtoEncoder};
+use crate::error::{Error, Result};
+
+// Encoders which compute the exact number of bytes a message occupies at a
+// given version, without writing it. Mirror KafkaSerializer and
+// KafkaFlexiSerializer, including checks of lengths which don't fit their
+// prefixes.
+#[derive(Default)]
+pub struct KafkaSizeCounter {
+    size: usize,
+}
+
+impl KafkaSizeCounter {
+    pub fn new() -> KafkaSizeCounter {
+        KafkaSizeCounter { size: 0 }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn add(&mut self, n: usize) -> Result<()> {
+        self.size += n;
+        Ok(())
+    }
+
+    fn add_varuint(&mut self, v: u32) -> Result<()> {
+        self.add(varint_size(v as u64))
+    }
+}
+
+// Number of bytes unsigned varint occupies, 7 bits per byte
+fn varint_size(v: u64) -> usize {
+    let bits = 64 - (v | 1).leading_zeros() as usize;
+    bits.div_ceil(7)
+}
+
+impl KafkaProtoEncoder for KafkaSizeCounter {
+    type Ok = ();
+    type Error = Error;
+
+    fn emit_bool(&mut self, _v: bool) -> Result<()> {
+        self.add(1)
+    }
+    fn emit_int8(&mut self, _v: i8) -> Result<()> {
+        self.add(1)
+    }
+    fn emit_int16(&mut self, _v: i16) -> Result<()> {
+        self.add(2)
+    }
+    fn emit_int32(&mut self, _v: i32) -> Result<()> {
+        self.add(4)
+    }
+    fn emit_int64(&mut self, _v: i64) -> Result<()> {
+        self.add(8)
+    }
+    fn emit_uint16(&mut self, _v: u16) -> Result<()> {
+        self.add(2)
+    }
+    fn emit_uint32(&mut self, _v: u32) -> Result<()> {
+        self.add(4)
+    }
+
+    fn emit_varint(&mut self, v: i32) -> Result<()> {
+        self.add(varint_size(((v << 1) ^ (v >> 31)) as u32 as u64))
+    }
+
+    fn emit_varlong(&mut self, v: i64) -> Result<()> {
+        self.add(varint_size(((v << 1) ^ (v >> 63)) as u64))
+    }
+
+    fn emit_unsigned_varint(&mut self, v: u32) -> Result<()> {
+        self.add_varuint(v)
+    }
+
+    fn emit_uuid(&mut self, _v: uuid::Uuid) -> Result<()> {
+        self.add(16)
+    }
+
+    fn emit_float64(&mut self, _v: f64) -> Result<()> {
+        self.add(8)
+    }
+
+    fn emit_string(&mut self, v: &str) -> Result<()> {
+        let _: i16 = v.len().try_into()?;
+        self.add(2 + v.len())
+    }
+
+    fn emit_compact_string(&mut self, v: &str) -> Result<()> {
+        self.add_varuint((v.len() + 1).try_into()?)?;
+        self.add(v.len())
+    }
+
+    fn emit_nullable_string(&mut self, v: Option<&str>) -> Result<()> {
+        match v {
+            Some(s) => self.emit_string(s),
+            None => self.add(2),
+        }
+    }
+
+    fn emit_compact_nullable_string(&mut self, v: Option<&str>) -> Result<()> {
+        match v {
+            Some(s) => self.emit_compact_string(s),
+            None => self.add(1),
+        }
+    }
+
+    fn emit_bytes(&mut self, v: &[u8]) -> Result<()> {
+        let _: i32 = v.len().try_into()?;
+        self.add(4 + v.len())
+    }
+
+    fn emit_compact_bytes(&mut self, v: &[u8]) -> Result<()> {
+        self.add_varuint((v.len() + 1).try_into()?)?;
+        self.add(v.len())
+    }
+
+    fn emit_nullable_bytes(&mut self, v: Option<&[u8]>) -> Result<()> {
+        match v {
+            Some(b) => self.emit_bytes(b),
+            None => self.add(4),
+        }
+    }
+
+    fn emit_compact_nullable_bytes(&mut self, v: Option<&[u8]>) -> Result<()> {
+        match v {
+            Some(b) => self.emit_compact_bytes(b),
+            None => self.add(1),
+        }
+    }
+
+    fn emit_array_hdr(&mut self, len: usize) -> Result<()> {
+        let _: i32 = len.try_into()?;
+        self.add(4)
+    }
+
+    fn emit_nullable_array_hdr(&mut self, len: Option<usize>) -> Result<()> {
+        match len {
+            Some(len) => self.emit_array_hdr(len),
+            None => self.add(4),
+        }
+    }
+
+    fn emit_compact_array_hdr(&mut self, len: usize) -> Result<()> {
+        self.add_varuint((len + 1).try_into()?)
+    }
+
+    fn emit_compact_nullable_array_hdr(&mut self, len: Option<usize>) -> Result<()> {
+        match len {
+            Some(len) => self.emit_compact_array_hdr(len),
+            None => self.add(1),
+        }
+    }
+}
+
+pub struct KafkaFlexiSizeCounter {
+    use_flexible: bool,
+    pub counter: KafkaSizeCounter,
+}
+
+impl KafkaFlexiSizeCounter {
+    pub fn new(use_flexible: bool) -> KafkaFlexiSizeCounter {
+        KafkaFlexiSizeCounter {
+            use_flexible,
+            counter: KafkaSizeCounter::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.counter.size()
+    }
+}
+
+impl KafkaFlexibleEncoder for KafkaFlexiSizeCounter {
+    type Ok = ();
+    type Error = Error;
+
+    fn emit_bool(&mut self, v: bool) -> Result<()> {
+        self.counter.emit_bool(v)
+    }
+    fn emit_int8(&mut self, v: i8) -> Result<()> {
+        self.counter.emit_int8(v)
+    }
+    fn emit_int16(&mut self, v: i16) -> Result<()> {
+        self.counter.emit_int16(v)
+    }
+    fn emit_int32(&mut self, v: i32) -> Result<()> {
+        self.counter.emit_int32(v)
+    }
+    fn emit_int64(&mut self, v: i64) -> Result<()> {
+        self.counter.emit_int64(v)
+    }
+    fn emit_uint16(&mut self, v: u16) -> Result<()> {
+        self.counter.emit_uint16(v)
+    }
+    fn emit_uint32(&mut self, v: u32) -> Result<()> {
+        self.counter.emit_uint32(v)
+    }
+    fn emit_varint(&mut self, v: i32) -> Result<()> {
+        self.counter.emit_varint(v)
+    }
+    fn emit_varlong(&mut self, v: i64) -> Result<()> {
+        self.counter.emit_varlong(v)
+    }
+    fn emit_uuid(&mut self, v: uuid::Uuid) -> Result<()> {
+        self.counter.emit_uuid(v)
+    }
+    fn emit_float64(&mut self, v: f64) -> Result<()> {
+        self.counter.emit_float64(v)
+    }
+
+    fn emit_string(&mut self, v: &str) -> Result<()> {
+        if self.use_flexible {
+            self.counter.emit_compact_string(v)
+        } else {
+            self.counter.emit_string(v)
+        }
+    }
+
+    fn emit_nullable_string(&mut self, v: Option<&str>) -> Result<()> {
+        if self.use_flexible {
+            self.counter.emit_compact_nullable_string(v)
+        } else {
+            self.counter.emit_nullable_string(v)
+        }
+    }
+
+    fn emit_bytes(&mut self, v: &[u8]) -> Result<()> {
+        if self.use_flexible {
+            self.counter.emit_compact_bytes(v)
+        } else {
+            self.counter.emit_bytes(v)
+        }
+    }
+
+    fn emit_nullable_bytes(&mut self, v: Option<&[u8]>) -> Result<()> {
+        if self.use_flexible {
+            self.counter.emit_compact_nullable_bytes(v)
+        } else {
+            self.counter.emit_nullable_bytes(v)
+        }
+    }
+
+    fn emit_array<'a, T: KafkaProtoEncodable + 'a>(
+        &mut self,
+        version: i16,
+        v: impl ExactSizeIterator<Item = &'a T>,
+    ) -> Result<()> {
+        if self.use_flexible {
+            self.counter.emit_compact_array_hdr(v.len())?;
+        } else {
+            self.counter.emit_array_hdr(v.len())?;
+        }
+        for e in v {
+            e.serialize(version, self)?;
+        }
+        Ok(())
+    }
+
+    fn emit_nullable_array<'a, T: KafkaProtoEncodable + 'a>(
+        &mut self,
+        version: i16,
+        v: Option<impl ExactSizeIterator<Item = &'a T>>,
+    ) -> Result<()> {
+        match v {
+            Some(v) => self.emit_array(version, v),
+            None if self.use_flexible => self.counter.emit_compact_nullable_array_hdr(None),
+            None => self.counter.emit_nullable_array_hdr(None),
+        }
+    }
+
+    fn emit_tagged_fields_hdr(&mut self, count: usize) -> Result<()> {
+        if self.use_flexible {
+            self.counter.add_varuint(count.try_into()?)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn emit_tagged_field<T: KafkaProtoEncodable>(
+        &mut self,
+        tag: u32,
+        version: i16,
+        v: &T,
+    ) -> Result<()> {
+        if !self.use_flexible {
+            return Ok(());
+        }
+
+        let mut field = KafkaFlexiSizeCounter::new(true);
+        v.serialize(version, &mut field)?;
+
+        self.counter.add_varuint(tag)?;
+        self.counter.add_varuint(field.size().try_into()?)?;
+        self.counter.add(field.size())
+    }
+
+    fn unsupported_field(&self, name: &'static str, version: i16) -> Error {
+        Error::UnsupportedFieldForVersion {
+            field: name,
+            version,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use super::*;
+use crate::messages::*;
+use crate::TopicId;
+
+fn encode(
+    use_flexible: bool,
+    f: impl FnOnce(&mut KafkaFlexiSerializer<&mut Vec<u8>>) -> Result<()>,
+) -> Vec<u8> {
+    let mut buf = Vec::new();
+    f(&mut KafkaFlexiSerializer::new(use_flexible, &mut buf)).unwrap();
+    buf
+}
+
+#[test]
+fn compact_null_is_zero() {
+    assert_eq!(encode(true, |s| s.emit_nullable_string(None)), [0x00]);
+    assert_eq!(encode(true, |s| s.emit_nullable_bytes(None)), [0x00]);
+    assert_eq!(
+        encode(true, |s| s
+            .emit_nullable_array(0, None::<std::slice::Iter<i32>>)),
+        [0x00]
+    );
+}
+
+#[test]
+fn compact_empty_is_one() {
+    assert_eq!(encode(true, |s| s.emit_string("")), [0x01]);
+    assert_eq!(encode(true, |s| s.emit_nullable_string(Some(""))), [0x01]);
+    assert_eq!(encode(true, |s| s.emit_bytes(&[])), [0x01]);
+    assert_eq!(encode(true, |s| s.emit_nullable_bytes(Some(&[]))), [0x01]);
+    assert_eq!(
+        encode(true, |s| s.emit_array(0, Vec::<i32>::new().iter())),
+        [0x01]
+    );
+    assert_eq!(
+        encode(true, |s| s
+            .emit_nullable_array(0, Some(Vec::<i32>::new().iter()))),
+        [0x01]
+    );
+}
+
+#[test]
+fn compact_non_empty() {
+    assert_eq!(
+        encode(true, |s| s.emit_string("abc")),
+        [0x04, b'a', b'b', b'c']
+    );
+    assert_eq!(encode(true, |s| s.emit_bytes(&[0xff])), [0x02, 0xff]);
+    assert_eq!(
+        encode(true, |s| s.emit_array(0, [7i32].iter())),
+        [0x02, 0x00, 0x00, 0x00, 0x07]
+    );
+
+    // 127 bytes take a 2 byte length
+    let s = "x".repeat(127);
+    assert_eq!(encode(true, |e| e.emit_string(&s))[..2], [0x80, 0x01]);
+}
+
+#[test]
+fn non_flexible_null_and_empty() {
+    assert_eq!(
+        encode(false, |s| s.emit_nullable_string(None)),
+        [0xff, 0xff]
+    );
+    assert_eq!(encode(false, |s| s.emit_string("")), [0x00, 0x00]);
+    assert_eq!(encode(false, |s| s.emit_nullable_bytes(None)), [0xff; 4]);
+    assert_eq!(encode(false, |s| s.emit_bytes(&[])), [0x00; 4]);
+    assert_eq!(
+        encode(false, |s| s
+            .emit_nullable_array(0, None::<std::slice::Iter<i32>>)),
+        [0xff; 4]
+    );
+    assert_eq!(
+        encode(false, |s| s.emit_array(0, Vec::<i32>::new().iter())),
+        [0x00; 4]
+    );
+}
+
+#[test]
+fn tagged_fields() {
+    assert_eq!(encode(true, |s| s.emit_empty_tagged_fields()), [0x00]);
+    assert_eq!(encode(false, |s| s.emit_empty_tagged_fields()), []);
+
+    // Count, then tag and size of every field
+    let buf = encode(true, |s| {
+        s.emit_tagged_fields_hdr(1)?;
+        s.emit_tagged_field(3, 0, &1i32)
+    });
+    assert_eq!(buf, [0x01, 0x03, 0x04, 0x00, 0x00, 0x00, 0x01]);
+    let buf = encode(false, |s| {
+        s.emit_tagged_fields_hdr(1)?;
+        s.emit_tagged_field(3, 0, &1i32)
+    });
+    assert_eq!(buf, []);
+}
+
+fn assert_size<T: KafkaProtoEncodable>(v: &T, ver: i16, use_flexible: bool) {
+    let mut buf = Vec::new();
+    v.serialize(ver, &mut KafkaFlexiSerializer::new(use_flexible, &mut buf))
+        .unwrap();
+    assert_eq!(
+        serialized_size(v, ver, use_flexible).unwrap(),
+        buf.len(),
+        "version {}",
+        ver
+    );
+}
+
+fn fetch_request(ver: i16) -> FetchRequest {
+    FetchRequest {
+        cluster_id: if ver >= 12 {
+            Some("cluster".to_string())
+        } else {
+            None
+        },
+        replica_state: if ver >= 15 {
+            Some(FetchRequestReplicaState {
+                replica_id: 1,
+                replica_epoch: 2,
+            })
+        } else {
+            None
+        },
+        max_wait_ms: 500,
+        min_bytes: 1,
+        session_epoch: 0,
+        topics: vec![FetchRequestTopic {
+            topic: "topic".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partitions: vec![FetchRequestPartition {
+                fetch_offset: 100,
+                partition_max_bytes: 1 << 20,
+                ..Default::default()
+            }],
+        }],
+        rack_id: "rack".to_string(),
+        ..Default::default()
+    }
+}
+
+fn fetch_response(ver: i16) -> FetchResponse {
+    let tagged = ver >= 12;
+    FetchResponse {
+        responses: vec![FetchResponseTopic {
+            topic: "topic".to_string(),
+            topic_id: TopicId(uuid::Uuid::from_u128(1)),
+            partitions: vec![
+                // Null aborted transactions and records
+                FetchResponsePartition::default(),
+                FetchResponsePartition {
+                    partition_index: 1,
+                    diverging_epoch: tagged.then_some(FetchResponseEpochEndOffset {
+                        epoch: 3,
+                        end_offset: 50,
+                    }),
+                    current_leader: tagged.then_some(LeaderIdAndEpoch {
+                        leader_id: 1,
+                        leader_epoch: 3,
+                    }),
+                    snapshot_id: tagged.then_some(SnapshotId {
+                        end_offset: 40,
+                        epoch: 2,
+                    }),
+                    aborted_transactions: Some(vec![FetchResponseAbortedTransaction {
+                        producer_id: 7,
+                        first_offset: 10,
+                    }]),
+                    records: Some(std::iter::repeat_n(0, 300).collect()),
+                    ..Default::default()
+                },
+            ],
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn serialized_size_matches_encoding() {
+    for ver in FetchRequest::MIN_API_VERSION..=FetchRequest::MAX_API_VERSION {
+        let use_flexible = ver >= FetchRequest::FLEXIBLE_VERSION;
+        assert_size(&fetch_request(ver), ver, use_flexible);
+        assert_size(&fetch_response(ver), ver, use_flexible);
+    }
+
+    // Empty and null values in both encodings
+    for use_flexible in [false, true] {
+        assert_size(&String::new(), 0, use_flexible);
+        assert_size(&None::<String>, 0, use_flexible);
+        assert_size(&Vec::<i32>::new(), 0, use_flexible);
+        assert_size(&"x".repeat(200), 0, use_flexible);
+    }
+}
+
+#[test]
+fn message_size_matches_serialize_message() {
+    for ver in FetchRequest::MIN_API_VERSION..=FetchRequest::MAX_API_VERSION {
+        let m = fetch_request(ver);
+        let mut buf = Vec::new();
+        serialize_message(&m, ver, "client", &mut buf).unwrap();
+        assert_eq!(message_size(&m, ver, "client").unwrap(), buf.len());
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn request_frame_is_size_prefixed() {
+    for ver in [4, FetchRequest::MAX_API_VERSION] {
+        let m = fetch_request(ver);
+        let mut message = Vec::new();
+        serialize_message(&m, ver, "client", &mut message).unwrap();
+
+        let mut buf = ::bytes::BytesMut::new();
+        serialize_message_bytes(&m, ver, "client", &mut buf).unwrap();
+        assert_eq!(&buf[..], &message[..]);
+
+        // Frames are appended after whatever buf already holds
+        let mut buf = ::bytes::BytesMut::from(&b"xy"[..]);
+        serialize_request_frame(&m, ver, "client", &mut buf).unwrap();
+        assert_eq!(&buf[..2], b"xy");
+        assert_eq!(buf[2..6], (message.len() as i32).to_be_bytes());
+        assert_eq!(&buf[6..], &message[..]);
+    }
+}